@@ -0,0 +1,71 @@
+//! 빌드 메타데이터(git 커밋, 빌드 날짜)를 `kael version --verbose`용 환경 변수로 넘긴다.
+
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    watch_git_head();
+
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "unknown".into());
+    println!("cargo:rustc-env=KAEL_GIT_COMMIT={commit}");
+
+    // 재현 가능한 빌드를 위해 SOURCE_DATE_EPOCH를 우선한다
+    let epoch = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default()
+        });
+    println!(
+        "cargo:rustc-env=KAEL_BUILD_DATE={}",
+        civil_date(epoch / 86_400)
+    );
+}
+
+/// 커밋이 바뀌면 다시 빌드되도록 HEAD와 그것이 가리키는 ref 파일을 지켜본다.
+///
+/// 브랜치에 커밋하면 HEAD는 그대로고 `.git/refs/heads/<branch>`만 바뀐다. `git gc` 뒤에는
+/// ref가 `.git/packed-refs`로 옮겨진다. 없는 경로를 지정하면 cargo가 매번 다시 실행하므로
+/// 있는 파일만 등록한다.
+fn watch_git_head() {
+    let git = std::path::Path::new(".git");
+    let head = git.join("HEAD");
+    let Ok(content) = std::fs::read_to_string(&head) else {
+        return;
+    };
+    println!("cargo:rerun-if-changed={}", head.display());
+    let mut watched = vec![git.join("packed-refs")];
+    if let Some(reference) = content.trim().strip_prefix("ref: ") {
+        watched.push(git.join(reference));
+    }
+    for path in watched.into_iter().filter(|path| path.is_file()) {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+}
+
+/// 1970-01-01 기준 일수를 `YYYY-MM-DD`로 바꾼다. (Howard Hinnant의 civil_from_days)
+fn civil_date(days: u64) -> String {
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
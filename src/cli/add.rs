@@ -1,13 +1,229 @@
-use crate::error::Result;
+use std::path::{Path, PathBuf};
 
-pub fn run_skill(_name: &str) -> Result<()> {
-    todo!("kael add skill")
+use console::style;
+
+use crate::cli::list;
+use crate::core::manifest;
+use crate::core::project::{self, LineEnding};
+use crate::core::registry::{self, ComponentKind};
+use crate::error::{self, KaelError, Result};
+use crate::template::engine;
+
+/// `kael add skill <name>...` 또는 `kael add skill --all-for <language>`
+pub fn run_skill(names: Vec<String>, all_for: Option<&str>) -> Result<()> {
+    let names = match all_for {
+        Some(language) => language_skills(language)?,
+        None if names.is_empty() => {
+            return Err(KaelError::Project {
+                message: "Specify a skill name or --all-for <language>".into(),
+            })
+        }
+        None => names,
+    };
+    install_and_report(ComponentKind::Skill, &names)
+}
+
+/// 현재 디렉토리에 `install`하고 추가된 파일과 개수를 출력한다.
+fn install_and_report(kind: ComponentKind, names: &[String]) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let report = install(&cwd, kind, names)?;
+    for path in &report.added {
+        println!(
+            "  {} {}",
+            style("+").green(),
+            path.strip_prefix(&cwd).unwrap_or(path).display()
+        );
+    }
+    println!(
+        "\n{} {} added, {} already installed",
+        style("✓").green().bold(),
+        report.added.len(),
+        report.skipped.len()
+    );
+    Ok(())
 }
 
-pub fn run_agent(_name: &str) -> Result<()> {
-    todo!("kael add agent")
+/// 레지스트리에서 `<language>/` 아래 있는 모든 skill 이름
+fn language_skills(language: &str) -> Result<Vec<String>> {
+    let prefix = format!("{}/", list::parse_stack(language)?.as_str());
+    Ok(registry::list_components(ComponentKind::Skill)
+        .into_iter()
+        .filter(|name| name.starts_with(&prefix))
+        .collect())
 }
 
-pub fn run_command(_name: &str) -> Result<()> {
-    todo!("kael add command")
+/// `install` 결과 (`base` 기준 절대 경로)
+#[derive(Debug, Default)]
+pub struct InstallReport {
+    pub added: Vec<PathBuf>,
+    /// 이미 설치되어 건드리지 않은 파일
+    pub skipped: Vec<PathBuf>,
+}
+
+/// 레지스트리 컴포넌트를 `.claude/`에 설치하고 manifest에 기록한다.
+///
+/// 이미 있는 파일은 내용과 관계없이 건너뛴다. 레지스트리에 없는 이름이 있으면
+/// 아무것도 쓰기 전에, 찾지 못한 이름을 모두 모아 에러를 돌려준다.
+pub fn install(base: &Path, kind: ComponentKind, names: &[String]) -> Result<InstallReport> {
+    let contents = error::collect_errors(names.iter().map(|name| {
+        let content = registry::get_installable(kind, name)?;
+        Ok((name, project::normalize(&content, LineEnding::default())))
+    }))?;
+
+    let mut report = InstallReport::default();
+    for (name, content) in contents {
+        let relative = Path::new(".claude").join(kind.installed_path(name));
+        let path = base.join(&relative);
+        if path.exists() {
+            report.skipped.push(path);
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, &content)?;
+        manifest::record_installed_file(base, &relative, content.as_bytes())?;
+        report.added.push(path);
+    }
+    Ok(report)
+}
+
+/// `kael add agent <name>`
+pub fn run_agent(name: &str) -> Result<()> {
+    install_and_report(ComponentKind::Agent, &[name.to_string()])
+}
+
+/// `kael add command <name>`, `--new`면 빈 템플릿으로 새로 만든다.
+pub fn run_command(name: &str, new: bool) -> Result<()> {
+    if !new {
+        return install_and_report(ComponentKind::Command, &[name.to_string()]);
+    }
+    let cwd = std::env::current_dir()?;
+    let path = new_command(&cwd, name)?;
+    println!(
+        "{} Created {} — fill in the TODO placeholders",
+        style("✓").green(),
+        path.strip_prefix(&cwd).unwrap_or(&path).display()
+    );
+    Ok(())
+}
+
+/// 빈 커맨드 템플릿으로 `.claude/commands/<name>.md`를 만들고 manifest에 사용자 작성으로 기록한다.
+///
+/// 이미 있는 파일은 덮어쓰지 않는다.
+pub fn new_command(base: &Path, name: &str) -> Result<PathBuf> {
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err(KaelError::Project {
+            message: format!("Invalid command name '{name}': use a single name like `deploy`"),
+        });
+    }
+    let relative = Path::new(".claude").join(ComponentKind::Command.installed_path(name));
+    let path = base.join(&relative);
+    if path.exists() {
+        return Err(KaelError::FileExists { path });
+    }
+
+    let content = engine::render_new_command(name)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, &content)?;
+    manifest::record_user_file(base, &relative, content.as_bytes())?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_rust_skills_install_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let names = language_skills("rust").unwrap();
+        let expected = registry::list_components(ComponentKind::Skill)
+            .iter()
+            .filter(|name| name.starts_with("rust/"))
+            .count();
+        assert_eq!(names.len(), expected);
+        assert!(names.contains(&"rust/ffi".to_string()));
+
+        let first = install(dir.path(), ComponentKind::Skill, &names).unwrap();
+        assert_eq!(first.added.len(), expected);
+        assert!(dir
+            .path()
+            .join(".claude/skills/rust/ffi/SKILL.md")
+            .is_file());
+        let installed =
+            registry::installed_components(ComponentKind::Skill, &dir.path().join(".claude"));
+        assert_eq!(installed, names);
+
+        let again = install(dir.path(), ComponentKind::Skill, &names).unwrap();
+        assert!(again.added.is_empty());
+        assert_eq!(again.skipped.len(), expected);
+
+        let manifest = manifest::read(dir.path()).unwrap().unwrap();
+        assert_eq!(manifest.files.len(), expected);
+        assert!(manifest.files.iter().all(|f| !f.user_authored));
+
+        assert!(language_skills("cobol").is_err());
+    }
+
+    #[test]
+    fn bulk_install_reports_every_missing_skill() {
+        let dir = tempfile::tempdir().unwrap();
+        let names = ["rust/ffi", "rust/no-such", "go/nope"].map(String::from);
+
+        let err = install(dir.path(), ComponentKind::Skill, &names).unwrap_err();
+        let KaelError::Multi(errors) = &err else {
+            panic!("expected Multi, got {err:?}");
+        };
+        assert_eq!(errors.len(), 2);
+        let message = err.to_string();
+        assert!(message.starts_with("2 errors:\n  - "), "{message}");
+        assert!(message.contains("'rust/no-such'"), "{message}");
+        assert!(message.contains("'go/nope'"), "{message}");
+        // 하나라도 없으면 아무것도 쓰지 않는다
+        assert!(!dir.path().join(".claude").exists());
+    }
+
+    #[test]
+    fn agents_and_commands_install_from_registry() {
+        let dir = tempfile::tempdir().unwrap();
+        install(
+            dir.path(),
+            ComponentKind::Agent,
+            &["_base/security-auditor".into()],
+        )
+        .unwrap();
+        let report = install(dir.path(), ComponentKind::Command, &["debug".into()]).unwrap();
+
+        let claude = dir.path().join(".claude");
+        assert_eq!(report.added, vec![claude.join("commands/debug.md")]);
+        let agent =
+            std::fs::read_to_string(claude.join("agents/_base/security-auditor.md")).unwrap();
+        assert!(agent.starts_with("---\n"), "{agent}");
+        let recorded = manifest::read(dir.path()).unwrap().unwrap();
+        assert_eq!(recorded.files.len(), 2);
+    }
+
+    #[test]
+    fn new_command_is_user_authored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = new_command(dir.path(), "deploy").unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("---\ndescription: TODO — what /deploy does"));
+        assert!(content.contains("# Deploy"));
+
+        let manifest = manifest::read(dir.path()).unwrap().unwrap();
+        let entry = &manifest.files[0];
+        assert_eq!(entry.path, ".claude/commands/deploy.md");
+        assert!(entry.user_authored);
+
+        assert!(matches!(
+            new_command(dir.path(), "deploy"),
+            Err(KaelError::FileExists { .. })
+        ));
+        assert!(new_command(dir.path(), "ops/deploy").is_err());
+    }
 }
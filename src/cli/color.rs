@@ -0,0 +1,59 @@
+/// `--color` 값
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// 터미널이고 `NO_COLOR`가 없을 때만 색상 사용
+    #[default]
+    Auto,
+    /// 파이프/`NO_COLOR`와 무관하게 항상 색상 사용
+    Always,
+    /// 색상 사용 안 함 (`--no-color`와 동일)
+    Never,
+}
+
+/// 색상 사용 여부를 결정한다.
+///
+/// `always`/`never`는 TTY 감지와 `NO_COLOR`보다 우선한다.
+pub fn should_colorize(choice: ColorChoice, no_color_env: bool, is_tty: bool) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => !no_color_env && is_tty,
+    }
+}
+
+/// 결정된 색상 설정을 stdout/stderr 양쪽에 적용한다.
+pub fn apply(choice: ColorChoice) {
+    let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let stdout = should_colorize(choice, no_color_env, console::Term::stdout().is_term());
+    let stderr = should_colorize(choice, no_color_env, console::Term::stderr().is_term());
+    console::set_colors_enabled(stdout);
+    console::set_colors_enabled_stderr(stderr);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_follows_tty() {
+        assert!(should_colorize(ColorChoice::Auto, false, true));
+        assert!(!should_colorize(ColorChoice::Auto, false, false));
+    }
+
+    #[test]
+    fn auto_respects_no_color() {
+        assert!(!should_colorize(ColorChoice::Auto, true, true));
+    }
+
+    #[test]
+    fn always_overrides_tty_and_no_color() {
+        assert!(should_colorize(ColorChoice::Always, false, false));
+        assert!(should_colorize(ColorChoice::Always, true, false));
+    }
+
+    #[test]
+    fn never_disables_colors() {
+        assert!(!should_colorize(ColorChoice::Never, false, true));
+        assert!(!should_colorize(ColorChoice::Never, true, true));
+    }
+}
@@ -0,0 +1,119 @@
+use console::style;
+
+/// `--quiet` / `--verbose`로 정한 출력 수준
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// 에러와 최종 요약만
+    Quiet,
+    #[default]
+    Normal,
+    /// 매칭된 컴포넌트와 해석한 경로까지
+    Verbose,
+}
+
+impl Verbosity {
+    /// `--quiet`와 `--verbose`는 clap에서 함께 쓸 수 없다.
+    pub fn from_flags(quiet: bool, verbose: bool) -> Self {
+        if quiet {
+            Verbosity::Quiet
+        } else if verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+}
+
+/// CLI 명령이 공유하는 실행 상태. 출력은 수준에 따라 여기서 거른다.
+#[derive(Debug, Default)]
+pub struct Ctx {
+    pub verbosity: Verbosity,
+    /// `Some`이면 stdout 대신 여기에 모은다 (테스트용)
+    captured: Option<String>,
+}
+
+impl Ctx {
+    pub fn new(verbosity: Verbosity) -> Self {
+        Ctx {
+            verbosity,
+            captured: None,
+        }
+    }
+
+    /// 출력을 stdout에 쓰지 않고 모아 두는 컨텍스트
+    pub fn capturing(verbosity: Verbosity) -> Self {
+        Ctx {
+            verbosity,
+            captured: Some(String::new()),
+        }
+    }
+
+    /// `capturing`으로 모은 출력
+    pub fn captured(&self) -> &str {
+        self.captured.as_deref().unwrap_or_default()
+    }
+
+    /// 장식(색상, 기호)을 붙일지. quiet에서는 CI 로그에 맞게 평문만 쓴다.
+    pub fn decorated(&self) -> bool {
+        self.verbosity > Verbosity::Quiet
+    }
+
+    /// 진행 상황과 경고 (normal 이상)
+    pub fn info(&mut self, line: impl AsRef<str>) {
+        if self.verbosity >= Verbosity::Normal {
+            self.emit(line.as_ref());
+        }
+    }
+
+    /// 자세한 정보 (verbose만)
+    pub fn detail(&mut self, line: impl AsRef<str>) {
+        if self.verbosity >= Verbosity::Verbose {
+            self.emit(line.as_ref());
+        }
+    }
+
+    /// 명령의 결과. 수준과 무관하게 항상 출력한다.
+    pub fn result(&mut self, line: impl AsRef<str>) {
+        self.emit(line.as_ref());
+    }
+
+    /// 마지막 요약 한 줄. 항상 출력하며 quiet에서는 장식을 뺀다.
+    pub fn summary(&mut self, line: impl AsRef<str>) {
+        let line = line.as_ref();
+        if self.decorated() {
+            self.emit(&format!("\n{} {line}", style("✓").green().bold()));
+        } else {
+            self.emit(line);
+        }
+    }
+
+    fn emit(&mut self, line: &str) {
+        match &mut self.captured {
+            Some(buffer) => {
+                buffer.push_str(line);
+                buffer.push('\n');
+            }
+            None => println!("{line}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_filter_lines() {
+        for (verbosity, expected) in [
+            (Verbosity::Quiet, "done\n"),
+            (Verbosity::Normal, "step\n\n✓ done\n"),
+            (Verbosity::Verbose, "step\npath\n\n✓ done\n"),
+        ] {
+            let mut ctx = Ctx::capturing(verbosity);
+            ctx.info("step");
+            ctx.detail("path");
+            ctx.summary("done");
+            assert_eq!(ctx.captured(), expected, "{verbosity:?}");
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::path::{Path, PathBuf};
+
+use console::style;
+
+use crate::cli::ctx::Ctx;
+use crate::cli::init::{resolve_prd_path, use_prd_registry};
+use crate::core::diff::{self, FileChange};
+use crate::core::{generator, prd};
+use crate::error::Result;
+
+/// `kael diff`: 메모리에서 재생성한 결과와 설치된 설정을 비교한다. 파일은 쓰지 않는다.
+pub fn run(ctx: &mut Ctx, from: Option<PathBuf>) -> Result<()> {
+    let prd_path = resolve_prd_path(from)?;
+    let cwd = std::env::current_dir()?;
+    let parsed = prd::parse_prd_file(&prd_path)?;
+    use_prd_registry(ctx, &parsed.frontmatter, &prd_path)?;
+    let output = generator::generate_parsed(&parsed)?;
+
+    print!("{}", render(&diff::compare_to_disk(&cwd, &output)));
+    Ok(())
+}
+
+/// 바뀌는 파일의 unified diff를 먼저, 파일별 요약과 개수를 마지막에 출력한다.
+fn render(changes: &[(PathBuf, FileChange)]) -> String {
+    if changes.is_empty() {
+        return format!("{} No changes.\n", style("✓").green().bold());
+    }
+    let mut out = String::new();
+    for (_, change) in changes {
+        if let FileChange::Changed(unified) = change {
+            out.push_str(unified);
+            out.push('\n');
+        }
+    }
+
+    let (mut added, mut removed, mut changed) = (0, 0, 0);
+    for (path, change) in changes {
+        let path = display(path);
+        let line = match change {
+            FileChange::Added => {
+                added += 1;
+                format!("{} {path}", style("+").green())
+            }
+            FileChange::Removed => {
+                removed += 1;
+                format!("{} {path}", style("-").red())
+            }
+            FileChange::Changed(_) => {
+                changed += 1;
+                format!("{} {path}", style("~").yellow())
+            }
+        };
+        out.push_str(&format!("  {line}\n"));
+    }
+    out.push_str(&format!(
+        "\n{changed} changed, {added} added, {removed} removed\n"
+    ));
+    out
+}
+
+fn display(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::project;
+
+    fn output() -> generator::GeneratedOutput {
+        let parsed =
+            prd::parse_prd("---\nname: d\nstack:\n  language: rust\ntype: cli\n---\n").unwrap();
+        generator::generate_parsed(&parsed).unwrap()
+    }
+
+    #[test]
+    fn edited_claude_md_is_reported_as_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = output();
+        project::write_output(dir.path(), &output, false).unwrap();
+        let claude_md = dir.path().join("CLAUDE.md");
+        let edited = std::fs::read_to_string(&claude_md).unwrap() + "local note\n";
+        std::fs::write(&claude_md, edited).unwrap();
+
+        let changes = diff::compare_to_disk(dir.path(), &output);
+        assert_eq!(changes.len(), 1);
+        let report = render(&changes);
+        assert!(report.contains("--- a/CLAUDE.md"));
+        assert!(report.contains("-local note"));
+        assert!(report.contains("~ CLAUDE.md"));
+        assert!(report.contains("1 changed, 0 added, 0 removed"));
+    }
+
+    #[test]
+    fn missing_claude_dir_is_all_additions() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = output();
+        let changes = diff::compare_to_disk(dir.path(), &output);
+
+        assert_eq!(changes.len(), output.files().len());
+        assert!(changes.iter().all(|(_, c)| *c == FileChange::Added));
+        assert!(!dir.path().join(".claude").exists());
+    }
+}
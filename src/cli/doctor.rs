@@ -1,5 +1,536 @@
-use crate::error::Result;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 
-pub fn run() -> Result<()> {
-    todo!("kael doctor")
+use console::style;
+
+use crate::cli::add;
+use crate::cli::ctx::Ctx;
+use crate::cli::init::use_prd_registry;
+use crate::cli::remove::remove_empty_dirs;
+use crate::core::manifest::{self, InstallState};
+use crate::core::prd::{self, ParsedPrd, Prd};
+use crate::core::project::LineEnding;
+use crate::core::registry::{self, ComponentKind};
+use crate::core::timings::{Phase, Timings};
+use crate::core::{generator, matcher, project};
+use crate::error::{KaelError, Result};
+
+/// `kael doctor`가 찾은 문제
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Diagnosis {
+    /// 레지스트리 내용과 SHA-256이 같은 컴포넌트
+    pub unchanged: Vec<(ComponentKind, String)>,
+    /// 레지스트리 내용과 SHA-256이 다른 컴포넌트 (직접 수정, 손상, 오래된 설치)
+    pub modified: Vec<(ComponentKind, String)>,
+    /// 설치되어 있지만 레지스트리에 더 이상 없는 컴포넌트
+    pub orphaned: Vec<(ComponentKind, String)>,
+    /// PRD 매칭 결과에 있지만 설치되지 않은 컴포넌트
+    pub not_installed: Vec<(ComponentKind, String)>,
+    /// 설치가 임베디드보다 오래된 레지스트리로 생성되었으면 manifest에 기록된 버전
+    pub older_registry: Option<String>,
+}
+
+/// `kael doctor --fix` 옵션
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FixOptions {
+    /// 확인 없이 바로 고친다
+    pub yes: bool,
+    /// CLAUDE.md와 settings.json도 PRD.md로 다시 생성한다
+    pub regenerate: bool,
+}
+
+/// `fix` 결과 (`base` 기준 절대 경로)
+#[derive(Debug, Default)]
+pub struct FixReport {
+    /// 레지스트리 내용으로 되돌리거나 새로 설치한 파일
+    pub restored: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+    /// `regenerate`로 다시 쓴 CLAUDE.md, settings.json
+    pub regenerated: Vec<PathBuf>,
+}
+
+impl Diagnosis {
+    /// `fix`가 바꿀 컴포넌트 수
+    fn fixable(&self) -> usize {
+        self.modified.len() + self.not_installed.len() + self.orphaned.len()
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.modified.is_empty()
+            && self.orphaned.is_empty()
+            && self.not_installed.is_empty()
+            && self.older_registry.is_none()
+    }
+}
+
+/// `timings`면 끝에 단계별 소요 시간을 출력한다 (`--fix`면 write 단계 포함).
+pub fn run(ctx: &mut Ctx, fix_options: Option<FixOptions>, timings: bool) -> Result<()> {
+    let mut recorded = Timings::default();
+    let result = execute(ctx, fix_options, &mut recorded);
+    if timings {
+        print!("\n{}", recorded.render());
+    }
+    result
+}
+
+fn execute(ctx: &mut Ctx, fix_options: Option<FixOptions>, timings: &mut Timings) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    if !cwd.join(".claude").is_dir() {
+        println!(
+            "{} No .claude/ directory found. Run `kael init` first.",
+            style("!").yellow()
+        );
+        return Ok(());
+    }
+
+    // PRD.md가 있으면 권장 구성과도 비교한다
+    let prd_path = cwd.join("PRD.md");
+    let prd = if prd_path.is_file() {
+        let parsed = timings.time(Phase::Parse, || prd::parse_prd_file(&prd_path))?;
+        use_prd_registry(ctx, &parsed.frontmatter, &prd_path)?;
+        Some(parsed)
+    } else {
+        None
+    };
+
+    let diagnosis = diagnose_timed(
+        &cwd,
+        prd.as_ref().map(|parsed| &parsed.frontmatter),
+        timings,
+    );
+    println!("{} {}\n", style("Registry").bold(), registry::version());
+    if let Some(installed) = &diagnosis.older_registry {
+        println!(
+            "  {} Installed configuration was generated by an older registry ({}) {}\n",
+            style("!").yellow(),
+            installed,
+            style("— `kael generate --force` updates it").dim()
+        );
+    }
+    if !diagnosis.unchanged.is_empty() {
+        // 문제 없는 항목은 목록 대신 개수만 보여준다
+        println!(
+            "{} ({}) — match the registry\n",
+            style("Unchanged").bold(),
+            diagnosis.unchanged.len()
+        );
+    }
+    print_group(
+        "Modified — differs from the registry",
+        style("~").yellow().to_string(),
+        &diagnosis.modified,
+        |_, _| "`kael generate --force` restores it".into(),
+    );
+    print_group(
+        "Orphaned — consider removing",
+        style("✗").red().to_string(),
+        &diagnosis.orphaned,
+        |_, _| "`kael doctor --fix` removes it".into(),
+    );
+    print_group(
+        "Recommended but not installed",
+        style("!").yellow().to_string(),
+        &diagnosis.not_installed,
+        |kind, name| format!("`kael add {} {name}`", kind.label()),
+    );
+    if let Some(options) = fix_options {
+        return run_fix(&cwd, prd.as_ref(), &diagnosis, options, timings);
+    }
+    if diagnosis.is_healthy() {
+        println!("{} No issues found.", style("✓").green().bold());
+    } else if prd.is_none() {
+        println!(
+            "\n{} No PRD.md found; recommended components were not checked.",
+            style("!").yellow()
+        );
+    }
+    Ok(())
+}
+
+/// 진단 결과를 확인받은 뒤 `fix`로 고치고 결과를 출력한다.
+fn run_fix(
+    base: &Path,
+    prd: Option<&ParsedPrd>,
+    diagnosis: &Diagnosis,
+    options: FixOptions,
+    timings: &mut Timings,
+) -> Result<()> {
+    if diagnosis.fixable() == 0 && !options.regenerate {
+        println!("{} Nothing to fix.", style("✓").green().bold());
+        return Ok(());
+    }
+    if !options.yes && !confirm_fix(diagnosis)? {
+        println!("Aborted; nothing was changed.");
+        return Ok(());
+    }
+
+    let report = timings.time(Phase::Write, || {
+        fix(base, prd, diagnosis, options.regenerate)
+    })?;
+    let display = |path: &PathBuf| {
+        path.strip_prefix(base)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    };
+    for path in report.restored.iter().chain(&report.regenerated) {
+        println!("  {} {}", style("+").green(), display(path));
+    }
+    for path in &report.removed {
+        println!("  {} {}", style("-").red(), display(path));
+    }
+    println!(
+        "\n{} {} restored, {} removed{}.",
+        style("✓").green().bold(),
+        report.restored.len(),
+        report.removed.len(),
+        if report.regenerated.is_empty() {
+            String::new()
+        } else {
+            format!(", {} regenerated", report.regenerated.len())
+        }
+    );
+    Ok(())
+}
+
+fn confirm_fix(diagnosis: &Diagnosis) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        return Err(KaelError::Project {
+            message: "doctor --fix needs confirmation; pass --yes to run it without a terminal"
+                .into(),
+        });
+    }
+    let prompt = format!(
+        "Restore {} and remove {} component(s)?",
+        diagnosis.modified.len() + diagnosis.not_installed.len(),
+        diagnosis.orphaned.len()
+    );
+    dialoguer::Confirm::new()
+        .with_prompt(prompt)
+        .default(false)
+        .interact()
+        .map_err(|err| KaelError::Project {
+            message: format!("Confirmation prompt failed: {err}"),
+        })
+}
+
+/// 진단 결과대로 고친다. modified는 레지스트리 내용으로 되돌리고, not-installed는 설치하고,
+/// orphaned는 지운다.
+///
+/// CLAUDE.md와 settings.json은 `regenerate`일 때만 PRD로 다시 쓰며, 이때 `prd`가 필요하다.
+pub fn fix(
+    base: &Path,
+    prd: Option<&ParsedPrd>,
+    diagnosis: &Diagnosis,
+    regenerate: bool,
+) -> Result<FixReport> {
+    if regenerate && prd.is_none() {
+        return Err(KaelError::Project {
+            message: "--regenerate needs a PRD.md in the current directory".into(),
+        });
+    }
+
+    let mut report = FixReport::default();
+    for (kind, name) in &diagnosis.modified {
+        let content = project::normalize(
+            &registry::get_installable(*kind, name)?,
+            LineEnding::default(),
+        );
+        let relative = Path::new(".claude").join(kind.installed_path(name));
+        std::fs::write(base.join(&relative), &content)?;
+        manifest::record_installed_file(base, &relative, content.as_bytes())?;
+        report.restored.push(base.join(relative));
+    }
+    for kind in ComponentKind::ALL {
+        let names: Vec<String> = diagnosis
+            .not_installed
+            .iter()
+            .filter(|(k, _)| *k == kind)
+            .map(|(_, name)| name.clone())
+            .collect();
+        report
+            .restored
+            .extend(add::install(base, kind, &names)?.added);
+    }
+    for (kind, name) in &diagnosis.orphaned {
+        let relative = Path::new(".claude").join(kind.installed_path(name));
+        let path = base.join(&relative);
+        if path.is_file() {
+            std::fs::remove_file(&path)?;
+            manifest::forget(base, &relative)?;
+            report.removed.push(path);
+        }
+    }
+    remove_empty_dirs(&base.join(".claude"))?;
+
+    // 본문 섹션도 CLAUDE.md에 들어가도록 `kael generate`와 같은 경로로 생성한다
+    if let Some(parsed) = prd.filter(|_| regenerate) {
+        let output = generator::generate_parsed(parsed)?;
+        for target in ["CLAUDE.md", "settings.json"] {
+            let path = project::write_single(base, &output, target, true)?;
+            report.regenerated.push(path);
+        }
+    }
+    Ok(report)
+}
+
+/// `.claude/`의 컴포넌트를 레지스트리, PRD 매칭 결과와 비교한다.
+///
+/// 설치된 파일은 현재 레지스트리 내용과 SHA-256으로 비교해 unchanged/modified로 나눈다.
+/// 사용자 작성 파일(`add command --new` 등)은 레지스트리에 없어도 orphan이 아니다.
+/// `prd`가 없으면 권장 구성 비교는 건너뛴다.
+pub fn diagnose(base: &Path, prd: Option<&Prd>) -> Diagnosis {
+    diagnose_timed(base, prd, &mut Timings::default())
+}
+
+/// `diagnose`와 같지만 PRD 매칭은 match, 레지스트리 비교는 resolve 단계로 `timings`에 기록한다.
+pub fn diagnose_timed(base: &Path, prd: Option<&Prd>, timings: &mut Timings) -> Diagnosis {
+    let matched = prd.map(|prd| {
+        timings.time(Phase::Match, || {
+            matcher::match_components_cached(prd, matcher::MatchStages::default())
+        })
+    });
+    timings.time(Phase::Resolve, || compare(base, matched.as_ref()))
+}
+
+fn compare(base: &Path, matched: Option<&matcher::MatchResult>) -> Diagnosis {
+    let claude_dir = base.join(".claude");
+    let user_authored = manifest::user_authored_paths(base);
+
+    let mut diagnosis = Diagnosis {
+        older_registry: older_registry(base),
+        ..Diagnosis::default()
+    };
+    for drift in manifest::compare_install_to_registry(base) {
+        let entry = (drift.kind, drift.name);
+        match drift.state {
+            InstallState::Current => diagnosis.unchanged.push(entry),
+            InstallState::Outdated | InstallState::Modified => diagnosis.modified.push(entry),
+        }
+    }
+    for kind in ComponentKind::ALL {
+        let installed = registry::installed_components(kind, &claude_dir);
+        for name in &installed {
+            let path = claude_dir.join(kind.installed_path(name));
+            if !user_authored.contains(&path) && !registry::has_component(kind, name) {
+                diagnosis.orphaned.push((kind, name.clone()));
+            }
+        }
+
+        let Some(matched) = matched else {
+            continue;
+        };
+        let recommended = match kind {
+            ComponentKind::Skill => &matched.skills,
+            ComponentKind::Agent => &matched.agents,
+            ComponentKind::Command => &matched.commands,
+        };
+        // 레지스트리에 없는 매칭 항목은 `kael validate`가 알려준다
+        for name in recommended {
+            if !installed.contains(name) && registry::has_component(kind, name) {
+                diagnosis.not_installed.push((kind, name.clone()));
+            }
+        }
+    }
+    diagnosis
+}
+
+/// manifest에 기록된 레지스트리 버전이 임베디드보다 오래되었으면 그 버전
+///
+/// manifest가 없으면 비교할 수 없으므로 `None`이다. 버전 기록 전 manifest는 오래된 것으로 본다.
+fn older_registry(base: &Path) -> Option<String> {
+    match manifest::read(base).ok()??.registry_version {
+        Some(version) if registry::is_older_version(&version, registry::version()) => Some(version),
+        Some(_) => None,
+        None => Some("not recorded".into()),
+    }
+}
+
+fn print_group(
+    title: &str,
+    marker: String,
+    entries: &[(ComponentKind, String)],
+    fix: impl Fn(ComponentKind, &str) -> String,
+) {
+    if entries.is_empty() {
+        return;
+    }
+    println!("{} ({})", style(title).bold(), entries.len());
+    for (kind, name) in entries {
+        println!(
+            "  {marker} {} {name} {}",
+            kind.label(),
+            style(format!("— {}", fix(*kind, name))).dim()
+        );
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{generator, project};
+
+    #[test]
+    fn reports_orphaned_and_missing_components() {
+        let dir = tempfile::tempdir().unwrap();
+        let parsed =
+            prd::parse_prd("---\nname: d\nstack:\n  language: rust\ntype: cli\n---\n").unwrap();
+        let output = generator::generate_parsed(&parsed).unwrap();
+        project::write_output(dir.path(), &output, false).unwrap();
+
+        let claude = dir.path().join(".claude");
+        std::fs::remove_file(claude.join("agents/_base/debugger.md")).unwrap();
+        let retired = claude.join("skills/rust/retired-skill");
+        std::fs::create_dir_all(&retired).unwrap();
+        std::fs::write(retired.join("SKILL.md"), "# Retired").unwrap();
+        crate::cli::add::new_command(dir.path(), "deploy").unwrap();
+
+        let diagnosis = diagnose(dir.path(), Some(&parsed.frontmatter));
+        assert_eq!(
+            diagnosis.orphaned,
+            vec![(ComponentKind::Skill, "rust/retired-skill".to_string())]
+        );
+        assert_eq!(
+            diagnosis.not_installed,
+            vec![(ComponentKind::Agent, "_base/debugger".to_string())]
+        );
+
+        // PRD가 없으면 orphan만 본다
+        let without_prd = diagnose(dir.path(), None);
+        assert_eq!(without_prd.orphaned, diagnosis.orphaned);
+        assert!(without_prd.not_installed.is_empty());
+    }
+
+    #[test]
+    fn rewritten_component_is_modified() {
+        let dir = tempfile::tempdir().unwrap();
+        let parsed =
+            prd::parse_prd("---\nname: d\nstack:\n  language: rust\ntype: cli\n---\n").unwrap();
+        let output = generator::generate_parsed(&parsed).unwrap();
+        project::write_output(dir.path(), &output, false).unwrap();
+
+        let before = diagnose(dir.path(), None);
+        assert!(before.modified.is_empty());
+        let edited = (ComponentKind::Skill, "rust/error-handling".to_string());
+        assert!(before.unchanged.contains(&edited));
+
+        std::fs::write(
+            dir.path()
+                .join(".claude/skills/rust/error-handling/SKILL.md"),
+            "# Error Handling\n\nlocal notes\n",
+        )
+        .unwrap();
+
+        let after = diagnose(dir.path(), None);
+        assert_eq!(after.modified, vec![edited.clone()]);
+        assert!(!after.unchanged.contains(&edited));
+        assert_eq!(after.unchanged.len(), before.unchanged.len() - 1);
+        assert!(!after.is_healthy());
+    }
+
+    #[test]
+    fn fix_restores_modified_and_removes_orphaned() {
+        let dir = tempfile::tempdir().unwrap();
+        let parsed =
+            prd::parse_prd("---\nname: d\nstack:\n  language: rust\ntype: cli\n---\n").unwrap();
+        let output = generator::generate_parsed(&parsed).unwrap();
+        project::write_output(dir.path(), &output, false).unwrap();
+
+        let claude = dir.path().join(".claude");
+        let edited = claude.join("skills/rust/error-handling/SKILL.md");
+        std::fs::write(&edited, "# Error Handling\n\nlocal notes\n").unwrap();
+        let retired = claude.join("skills/rust/retired-skill");
+        std::fs::create_dir_all(&retired).unwrap();
+        std::fs::write(retired.join("SKILL.md"), "# Retired").unwrap();
+        std::fs::write(dir.path().join("CLAUDE.md"), "# hand-edited\n").unwrap();
+
+        let diagnosis = diagnose(dir.path(), None);
+        assert_eq!(diagnosis.modified.len(), 1);
+        assert_eq!(diagnosis.orphaned.len(), 1);
+        let report = fix(dir.path(), None, &diagnosis, false).unwrap();
+
+        assert_eq!(report.restored, vec![edited.clone()]);
+        assert_eq!(report.removed, vec![retired.join("SKILL.md")]);
+        assert_eq!(
+            std::fs::read_to_string(&edited).unwrap(),
+            project::normalize(
+                &registry::get_component(ComponentKind::Skill, "rust/error-handling").unwrap(),
+                LineEnding::Lf
+            )
+        );
+        assert!(!retired.exists());
+        // --regenerate 없이는 CLAUDE.md를 건드리지 않는다
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("CLAUDE.md")).unwrap(),
+            "# hand-edited\n"
+        );
+        assert!(diagnose(dir.path(), None).is_healthy());
+    }
+
+    #[test]
+    fn regenerate_keeps_prd_body_sections() {
+        let dir = tempfile::tempdir().unwrap();
+        let parsed = prd::parse_prd(
+            "---\nname: d\nstack:\n  language: rust\ntype: cli\n---\n\n## Architecture\n\nLayered design.\n",
+        )
+        .unwrap();
+        let output = generator::generate_parsed(&parsed).unwrap();
+        project::write_output(dir.path(), &output, false).unwrap();
+        std::fs::write(dir.path().join("CLAUDE.md"), "# hand-edited\n").unwrap();
+
+        let diagnosis = diagnose(dir.path(), Some(&parsed.frontmatter));
+        let report = fix(dir.path(), Some(&parsed), &diagnosis, true).unwrap();
+
+        assert!(report.regenerated.contains(&dir.path().join("CLAUDE.md")));
+        let claude_md = std::fs::read_to_string(dir.path().join("CLAUDE.md")).unwrap();
+        assert!(
+            claude_md.contains("## Architecture\n\nLayered design.\n"),
+            "{claude_md}"
+        );
+    }
+
+    #[test]
+    fn diagnose_records_match_and_resolve_timings() {
+        let dir = tempfile::tempdir().unwrap();
+        let parsed =
+            prd::parse_prd("---\nname: d\nstack:\n  language: rust\ntype: cli\n---\n").unwrap();
+        let output = generator::generate_parsed(&parsed).unwrap();
+        project::write_output(dir.path(), &output, false).unwrap();
+
+        let mut timings = Timings::default();
+        let diagnosis = diagnose_timed(dir.path(), Some(&parsed.frontmatter), &mut timings);
+        assert!(diagnosis.is_healthy());
+        assert!(timings.get(Phase::Match).is_some());
+        assert!(timings.get(Phase::Resolve).is_some());
+        assert!(timings.get(Phase::Write).is_none());
+
+        // PRD가 없으면 match 단계는 실행되지 않는다
+        let mut timings = Timings::default();
+        diagnose_timed(dir.path(), None, &mut timings);
+        assert!(timings.get(Phase::Match).is_none());
+        assert!(timings.get(Phase::Resolve).is_some());
+    }
+
+    #[test]
+    fn older_registry_in_manifest_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let parsed =
+            prd::parse_prd("---\nname: d\nstack:\n  language: rust\ntype: cli\n---\n").unwrap();
+        let output = generator::generate_parsed(&parsed).unwrap();
+        project::write_output(dir.path(), &output, false).unwrap();
+        assert_eq!(diagnose(dir.path(), None).older_registry, None);
+
+        let mut recorded = manifest::read(dir.path()).unwrap().unwrap();
+        assert_eq!(
+            recorded.registry_version.as_deref(),
+            Some(registry::version())
+        );
+        recorded.registry_version = Some("2020.1.1".into());
+        manifest::write(dir.path(), &recorded).unwrap();
+
+        let diagnosis = diagnose(dir.path(), None);
+        assert_eq!(diagnosis.older_registry.as_deref(), Some("2020.1.1"));
+        assert!(!diagnosis.is_healthy());
+    }
 }
@@ -0,0 +1,172 @@
+use console::style;
+
+use crate::core::matcher;
+use crate::core::registry::{self, ComponentKind};
+use crate::error::{KaelError, Result};
+
+/// 찾지 못했을 때 보여줄 비슷한 이름 최대 개수
+const SUGGESTION_LIMIT: usize = 5;
+
+/// `kael explain <name>`: 설치 전에 컴포넌트 내용을 요약(또는 `--full`로 전체) 출력한다.
+pub fn run(name: &str, kind: Option<ComponentKind>, full: bool) -> Result<()> {
+    let (kind, name) = match registry::resolve_component(name, kind) {
+        Err(KaelError::RegistryNotFound { .. }) => return Err(not_found(name)),
+        other => other?,
+    };
+    let content = registry::get_component(kind, &name)?;
+
+    println!(
+        "{} {} {}",
+        style(kind.label()).dim(),
+        style(&name).bold(),
+        style(format!("— add with `kael add {} {name}`", kind.label())).dim()
+    );
+    println!();
+    if full {
+        print!("{content}");
+    } else {
+        println!("{}", summary(&content));
+    }
+    Ok(())
+}
+
+/// 모든 종류에서 이름을 찾지 못했을 때, 가까운 이름을 담은 에러
+fn not_found(name: &str) -> KaelError {
+    let suggestions = suggestions(name);
+    let hint = if suggestions.is_empty() {
+        " Run `kael list all` to see every component.".to_string()
+    } else {
+        let names: Vec<String> = suggestions
+            .iter()
+            .map(|(kind, name)| format!("{} {name}", kind.label()))
+            .collect();
+        format!(" Closest matches: {}", names.join(", "))
+    };
+    KaelError::Project {
+        message: format!("No skill, agent, or command named '{name}'.{hint}"),
+    }
+}
+
+/// 부분 문자열이 겹치거나 편집 거리가 가까운 컴포넌트 이름 (가까운 순)
+pub(crate) fn suggestions(query: &str) -> Vec<(ComponentKind, String)> {
+    let query = query.to_ascii_lowercase();
+    let threshold = 2.max(query.len() / 3);
+    let mut scored = Vec::new();
+    for kind in ComponentKind::ALL {
+        for candidate in registry::list_components(kind) {
+            let leaf = candidate.rsplit('/').next().unwrap_or(&candidate);
+            let distance = if candidate.contains(&query) || query.contains(leaf) {
+                0
+            } else {
+                matcher::edit_distance(&query, &candidate).min(matcher::edit_distance(&query, leaf))
+            };
+            if distance <= threshold {
+                scored.push((distance, kind, candidate));
+            }
+        }
+    }
+    scored.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.2.cmp(&b.2)));
+    scored
+        .into_iter()
+        .take(SUGGESTION_LIMIT)
+        .map(|(_, kind, name)| (kind, name))
+        .collect()
+}
+
+/// 첫 `#` 제목과 그 뒤 첫 문단. 표와 코드 블록, 하위 제목은 문단으로 치지 않는다.
+///
+/// 문단이 없으면 frontmatter의 `description`을 대신 쓴다.
+pub(crate) fn summary(content: &str) -> String {
+    let (description, body) = split_frontmatter(content);
+    let mut heading = None;
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut in_code = false;
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        if heading.is_none() {
+            if let Some(title) = trimmed.strip_prefix("# ") {
+                heading = Some(title.trim());
+            }
+            continue;
+        }
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('|') {
+            if !paragraph.is_empty() {
+                break;
+            }
+            continue;
+        }
+        paragraph.push(trimmed);
+    }
+
+    let paragraph = if paragraph.is_empty() {
+        description.unwrap_or_default()
+    } else {
+        paragraph.join(" ")
+    };
+    match heading {
+        Some(heading) => format!("{heading}\n\n{paragraph}"),
+        None => paragraph,
+    }
+}
+
+/// `---` frontmatter의 `description`과 그 뒤 본문
+fn split_frontmatter(content: &str) -> (Option<String>, &str) {
+    let Some(rest) = content.strip_prefix("---") else {
+        return (None, content);
+    };
+    let Some(end) = rest.find("\n---") else {
+        return (None, content);
+    };
+    let description = serde_yaml_ng::from_str::<serde_yaml_ng::Value>(&rest[..end])
+        .ok()
+        .and_then(|v| v.get("description")?.as_str().map(str::to_string));
+    let body = &rest[end + "\n---".len()..];
+    (description, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn existing_skill_summarizes_heading_and_paragraph() {
+        let (kind, name) = registry::resolve_component("rust/async-patterns", None).unwrap();
+        assert_eq!(kind, ComponentKind::Skill);
+
+        let content = registry::get_component(kind, &name).unwrap();
+        let summary = summary(&content);
+        let heading = summary.lines().next().unwrap();
+        assert!(content.contains(&format!("# {heading}\n")), "{summary}");
+        assert!(summary.lines().count() >= 3, "{summary}");
+        assert!(!summary.contains("---"));
+    }
+
+    #[test]
+    fn summary_skips_tables_and_code() {
+        let content = "---\nname: x\ndescription: Fallback text.\n---\n\n# Title\n\n## Quick Reference\n\n| a | b |\n\n```rust\nfn main() {}\n```\n\nFirst paragraph\ncontinues here.\n\nSecond.\n";
+        assert_eq!(summary(content), "Title\n\nFirst paragraph continues here.");
+        assert_eq!(
+            summary("---\ndescription: Fallback text.\n---\n# Title\n\n| only | table |\n"),
+            "Title\n\nFallback text."
+        );
+    }
+
+    #[test]
+    fn unknown_name_suggests_closest_components() {
+        let err = run("async-patern", None, false).unwrap_err().to_string();
+        assert!(
+            err.contains("No skill, agent, or command named 'async-patern'"),
+            "{err}"
+        );
+        assert!(err.contains("skill rust/async-patterns"), "{err}");
+
+        assert!(suggestions("zzzzqqqq").is_empty());
+    }
+}
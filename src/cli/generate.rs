@@ -1,7 +1,382 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::error::Result;
+use console::style;
 
-pub fn run(_from: Option<PathBuf>, _dry_run: bool) -> Result<()> {
-    todo!("kael generate")
+use crate::cli::ctx::Ctx;
+use crate::cli::init::{
+    print_missing, print_prd_warnings, resolve_output_dir, resolve_prd_paths, use_prd_registries,
+};
+use crate::core::generator::GeneratedOutput;
+use crate::core::matcher::MatchStages;
+use crate::core::project::{ApplyMode, ApplyOptions, ApplyReport, LineEnding, WriteStatus};
+use crate::core::timings::{Phase, Timings};
+use crate::core::{diff, generator, manifest, prd, project};
+use crate::error::{KaelError, Result};
+use crate::template::engine::SettingsFormat;
+
+/// `kael generate` 옵션
+#[derive(Debug, Default)]
+pub struct Options {
+    pub dry_run: bool,
+    pub force: bool,
+    pub merge: bool,
+    /// 없는 파일만 생성 (`--force`보다 우선)
+    pub only_new: bool,
+    pub touch: bool,
+    pub emit_only: Option<String>,
+    pub write_manifest_only: bool,
+    /// 단계별 소요 시간 출력
+    pub timings: bool,
+    /// 켜져 있는 자동 매칭 단계
+    pub stages: MatchStages,
+    /// 함께 생성할 `settings.<env>.json`의 환경 이름
+    pub env: Option<String>,
+    /// 경고가 있으면 파일을 기록한 뒤 실패로 끝낸다
+    pub fail_on_warning: bool,
+    /// 현재 디렉토리 대신 파일을 쓸 디렉토리
+    pub output: Option<PathBuf>,
+    /// settings.json 출력 형식
+    pub settings_format: SettingsFormat,
+}
+
+/// `from`에 PRD가 여럿이면 매칭 결과를 합쳐 하나의 구성을 만든다. 첫 PRD가 기준이다.
+pub fn run(ctx: &mut Ctx, from: Vec<PathBuf>, options: Options) -> Result<()> {
+    let show_timings = options.timings;
+    let fail_on_warning = options.fail_on_warning;
+    let mut timings = Timings::default();
+    let output = execute(ctx, from, options, &mut timings)?;
+    if show_timings {
+        print!("\n{}", timings.render());
+    }
+    check_warnings(&output, fail_on_warning)
+}
+
+/// `--fail-on-warning`이면 경고가 하나라도 있을 때 에러로 끝낸다.
+///
+/// 파일은 이미 기록된(또는 `--dry-run`으로 미리 본) 뒤에 확인한다.
+fn check_warnings(output: &GeneratedOutput, fail_on_warning: bool) -> Result<()> {
+    let warnings = output.warnings();
+    if !fail_on_warning || warnings.is_empty() {
+        return Ok(());
+    }
+    Err(KaelError::Project {
+        message: format!(
+            "{} warning(s) with --fail-on-warning: {}",
+            warnings.len(),
+            warnings.join("; ")
+        ),
+    })
+}
+
+fn execute(
+    ctx: &mut Ctx,
+    from: Vec<PathBuf>,
+    options: Options,
+    timings: &mut Timings,
+) -> Result<GeneratedOutput> {
+    let Options {
+        dry_run,
+        force,
+        merge,
+        only_new,
+        touch,
+        emit_only,
+        write_manifest_only,
+        timings: _,
+        fail_on_warning: _,
+        stages,
+        env,
+        output: output_dir,
+        settings_format,
+    } = options;
+    let prd_paths = resolve_prd_paths(from)?;
+    // --dry-run은 --output 디렉토리도 만들지 않는다
+    let cwd = resolve_output_dir(&std::env::current_dir()?, output_dir, !dry_run)?;
+
+    let parsed = timings.time(Phase::Parse, || {
+        prd_paths
+            .iter()
+            .map(|path| prd::parse_prd_file(path))
+            .collect::<Result<Vec<_>>>()
+    })?;
+    use_prd_registries(
+        ctx,
+        parsed
+            .iter()
+            .zip(&prd_paths)
+            .map(|(p, path)| (&p.frontmatter, path.as_path())),
+    )?;
+    let mut output = generator::generate_merged(&parsed, stages, settings_format, timings)?;
+    if let Some(env) = &env {
+        generator::add_env_settings(&mut output, &parsed[0].frontmatter, stages, env)?;
+    }
+
+    // --write-manifest-only: 파일 내용은 건드리지 않고 추적 정보만 복구
+    if write_manifest_only {
+        let rebuilt = manifest::from_disk(&cwd, &output)?;
+        if dry_run {
+            println!("  {} {}", style("~").yellow(), manifest::MANIFEST_PATH);
+            return Ok(output);
+        }
+        let path = timings.time(Phase::Write, || manifest::record(&cwd, &output))?;
+        println!("  {} {}", style("+").green(), display_path(&cwd, &path));
+        println!(
+            "\n{} Manifest rebuilt for {} files.",
+            style("✓").green().bold(),
+            rebuilt.files.len()
+        );
+        return Ok(output);
+    }
+
+    // --emit-only: 지정한 파일 하나만 기록
+    if let Some(target) = emit_only {
+        let (relative, _) = project::select_file(&output, &target)?;
+        if dry_run {
+            println!("  {} {}", style("~").yellow(), relative.display());
+            return Ok(output);
+        }
+        let path = timings.time(Phase::Write, || {
+            project::write_single(&cwd, &output, &target, force)
+        })?;
+        println!("  {} {}", style("+").green(), display_path(&cwd, &path));
+        return Ok(output);
+    }
+
+    print_missing(&output);
+    print_prd_warnings(&output);
+    if dry_run {
+        print!("{}", preview_tree(&output));
+        println!("\n{}", style("── CLAUDE.md ──").dim());
+        print!("{}", output.claude_md);
+        println!(
+            "\n{} {} files would be written (nothing was written).",
+            style("✓").green().bold(),
+            output.files().len()
+        );
+        return Ok(output);
+    }
+
+    // 설정이 이미 있고 같으면 no-op, 다르면 --force/--merge에 따라 갱신, 없으면 생성
+    let mode = if only_new {
+        ApplyMode::OnlyNew
+    } else if force {
+        ApplyMode::Force
+    } else if merge {
+        ApplyMode::Merge
+    } else {
+        ApplyMode::Update
+    };
+    let report = timings.time(Phase::Write, || {
+        let options = ApplyOptions {
+            mode,
+            touch,
+            ..ApplyOptions::default()
+        };
+        project::apply(&cwd, &output, options)
+    })?;
+    print_report(&cwd, &report);
+    if only_new {
+        println!(
+            "\n{} {} files added, {} existing skipped.",
+            style("✓").green().bold(),
+            report.written(),
+            report.skipped()
+        );
+    } else {
+        println!(
+            "\n{} {} files written, {} unchanged.",
+            style("✓").green().bold(),
+            report.written(),
+            report.skipped()
+        );
+    }
+
+    Ok(output)
+}
+
+/// `--dry-run`에서 기록될 파일을 경로순 디렉토리 트리로 보여준다. 파일마다 바이트 크기를 붙인다.
+pub(crate) fn preview_tree(output: &GeneratedOutput) -> String {
+    let mut files = output.files();
+    files.sort();
+    let mut out = String::new();
+    let mut previous: Vec<String> = Vec::new();
+    for (relative, content) in files {
+        let parts: Vec<String> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        let (file, dirs) = parts.split_last().expect("generated path is never empty");
+        // 직전 파일과 갈라지는 지점부터 디렉토리 줄을 찍는다
+        let shared = dirs
+            .iter()
+            .zip(&previous)
+            .take_while(|(a, b)| a == b)
+            .count();
+        for (depth, dir) in dirs.iter().enumerate().skip(shared) {
+            out.push_str(&format!("{}{dir}/\n", "  ".repeat(depth + 1)));
+        }
+        out.push_str(&format!(
+            "{}{file} ({} bytes)\n",
+            "  ".repeat(dirs.len() + 1),
+            project::normalize(content, LineEnding::default()).len()
+        ));
+        previous = dirs.to_vec();
+    }
+    out
+}
+
+/// 기록 결과를 파일별로 출력한다. 변경 없는 파일은 생략한다.
+pub(crate) fn print_report(base: &Path, report: &ApplyReport) {
+    for line in report_lines(base, report) {
+        println!("{line}");
+    }
+}
+
+/// `print_report`가 출력할 줄들
+pub(crate) fn report_lines(base: &Path, report: &ApplyReport) -> Vec<String> {
+    report
+        .entries
+        .iter()
+        .filter(|(_, status)| *status != WriteStatus::Unchanged)
+        .map(|(path, status)| status_line(&display_path(base, path), *status))
+        .collect()
+}
+
+/// 기록 결과 한 줄을 출력한다. 덮어쓴 파일은 줄 변경량을 함께 보여준다.
+pub(crate) fn print_status(display: &str, status: WriteStatus) {
+    println!("{}", status_line(display, status));
+}
+
+fn status_line(display: &str, status: WriteStatus) -> String {
+    match status {
+        WriteStatus::Created => format!("  {} {}", style("+").green(), display),
+        WriteStatus::Updated(stats) | WriteStatus::Merged(stats) => {
+            let line = diff::summary_line(display, stats);
+            format!("  {}", style(line).yellow())
+        }
+        WriteStatus::Touched => format!("  {} {}", style("=").cyan(), display),
+        WriteStatus::Unchanged => format!("  {} {}", style("·").dim(), display),
+        WriteStatus::Kept => format!("  {} {} (kept)", style("·").dim(), display),
+        WriteStatus::Removed => format!("  {} {}", style("-").red(), display),
+    }
+}
+
+pub(crate) fn display_path(base: &Path, path: &Path) -> String {
+    path.strip_prefix(base)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regenerating_after_a_prd_change_updates_managed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let prd_path = dir.path().join("PRD.md");
+        let out = dir.path().join("out");
+        let generate = |name: &str| {
+            let prd = format!("---\nname: {name}\nstack:\n  language: rust\ntype: cli\n---\n");
+            std::fs::write(&prd_path, prd).unwrap();
+            let options = Options {
+                output: Some(out.clone()),
+                ..Options::default()
+            };
+            run(
+                &mut Ctx::capturing(Default::default()),
+                vec![prd_path.clone()],
+                options,
+            )
+        };
+        let claude_md = || std::fs::read_to_string(out.join("CLAUDE.md")).unwrap();
+
+        generate("first").unwrap();
+        generate("second").unwrap();
+        assert!(claude_md().contains("second"), "{}", claude_md());
+
+        // 사용자가 고친 파일은 덮어쓰지 않는다
+        std::fs::write(out.join("CLAUDE.md"), "# mine\n").unwrap();
+        let err = generate("third").unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{err}");
+        assert_eq!(claude_md(), "# mine\n");
+    }
+
+    #[test]
+    fn dry_run_writes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let prd_path = dir.path().join("PRD.md");
+        std::fs::write(
+            &prd_path,
+            "---\nname: dry\nstack:\n  language: rust\ntype: cli\n---\n",
+        )
+        .unwrap();
+        let out = dir.path().join("out");
+
+        let options = Options {
+            dry_run: true,
+            output: Some(out.clone()),
+            ..Options::default()
+        };
+        run(
+            &mut Ctx::capturing(Default::default()),
+            vec![prd_path],
+            options,
+        )
+        .unwrap();
+
+        assert!(!out.exists());
+        let entries: Vec<_> = std::fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[test]
+    fn preview_tree_groups_files_by_directory() {
+        let parsed =
+            prd::parse_prd("---\nname: t\nstack:\n  language: rust\ntype: cli\n---\n").unwrap();
+        let output = generator::generate_parsed(&parsed).unwrap();
+        let tree = preview_tree(&output);
+
+        assert!(tree.starts_with("  .claude/\n    agents/\n      _base/\n"));
+        // 크기는 줄바꿈을 정규화해 실제로 기록될 바이트 수다
+        let written = project::normalize(&output.claude_md, LineEnding::Lf).len();
+        assert!(tree.ends_with(&format!("  CLAUDE.md ({written} bytes)\n")));
+        assert_eq!(tree.matches("      _base/\n").count(), 1);
+        assert!(tree.contains("\n      rust/\n        async-patterns/\n          SKILL.md ("));
+    }
+
+    #[test]
+    fn warnings_fail_only_when_requested() {
+        let parsed = prd::parse_prd(
+            "---\nname: w\nstack:\n  language: rust\ntype: cli\nskills:\n  - rust/nope\n---\n",
+        )
+        .unwrap();
+        let output = generator::generate_parsed(&parsed).unwrap();
+        let warnings = output.warnings();
+        assert_eq!(warnings[0], "'rust/nope' not found in registry, skipped");
+        // 명시적 skills가 뺀 자동 매칭 skill도 경고에 들어간다
+        assert!(warnings.len() > 1);
+        assert!(
+            warnings[1..].iter().all(|w| w.contains("explicit")),
+            "{warnings:?}"
+        );
+
+        assert!(check_warnings(&output, false).is_ok());
+        let err = check_warnings(&output, true).unwrap_err().to_string();
+        assert!(err.contains("rust/nope"), "{err}");
+
+        let typo = prd::parse_prd(
+            "---\nname: f\nstack:\n  language: rust\n  framework: axium\ntype: cli\n---\n",
+        )
+        .unwrap();
+        let output = generator::generate_parsed(&typo).unwrap();
+        let err = check_warnings(&output, true).unwrap_err().to_string();
+        assert!(err.contains("did you mean 'axum'"), "{err}");
+
+        let clean =
+            prd::parse_prd("---\nname: c\nstack:\n  language: rust\ntype: cli\n---\n").unwrap();
+        let output = generator::generate_parsed(&clean).unwrap();
+        assert!(check_warnings(&output, true).is_ok());
+    }
 }
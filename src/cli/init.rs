@@ -1,81 +1,681 @@
-use std::path::PathBuf;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 
 use console::style;
 
-use crate::core::{generator, prd, project};
+use crate::cli::ctx::Ctx;
+use crate::cli::generate::{preview_tree, report_lines};
+use crate::cli::list;
+use crate::core::generator::GeneratedOutput;
+use crate::core::matcher::{self, ComponentOverrides, MatchStages};
+use crate::core::prd::{Language, Languages, ParsedPrd, Prd, PrdBody, ProjectType, Stack};
+use crate::core::project::{ApplyMode, ApplyOptions};
+use crate::core::registry::{ComponentKind, RegistrySource};
+use crate::core::timings::Timings;
+use crate::core::{detect, generator, prd, project, registry, remote};
 use crate::error::{KaelError, Result};
+use crate::template::engine::SettingsFormat;
 
-pub fn run(from: Option<PathBuf>, _minimal: bool, force: bool) -> Result<()> {
-    let prd_path = resolve_prd_path(from)?;
+/// `kael init` 옵션
+#[derive(Debug, Default)]
+pub struct Options {
+    /// CLAUDE.md와 기본 commands만 생성
+    pub minimal: bool,
+    pub force: bool,
+    /// 파일을 쓰지 않고 기록될 파일 목록만 출력
+    pub dry_run: bool,
+    /// `--force`일 때 새 결과에 없는 레지스트리 컴포넌트를 지움 (`kael add`로 설치한 것 포함)
+    pub prune: bool,
+    /// 매칭되지 않은 관련 컴포넌트 추천 출력
+    pub suggest: bool,
+    /// PRD.md가 없으면 질문으로 PRD를 만듦
+    pub interactive: bool,
+    /// `--interactive`의 언어 질문 기본값. 질문하지 않으면 첫 PRD의 `stack.language`를 대신한다.
+    pub language: Option<Language>,
+    /// PRD.md 대신 씀: 프리셋 이름 (`PROFILES`)
+    pub profile: Option<String>,
+    /// PRD.md 대신 씀: HTTPS로 받아 올 PRD 주소 (`remote` feature)
+    pub from_url: Option<String>,
+    /// PRD에 `stack.framework`가 없어도 프로젝트 파일에서 추측하지 않음
+    pub no_detect: bool,
+    /// `settings.local.json`도 생성
+    pub with_local: bool,
+    /// 현재 디렉토리 대신 파일을 쓸 디렉토리
+    pub output: Option<PathBuf>,
+    /// `.gitignore`에 kael 관리 블록 추가
+    pub gitignore: bool,
+    /// settings.json 출력 형식
+    pub settings_format: SettingsFormat,
+    /// 켜져 있는 자동 매칭 단계
+    pub stages: MatchStages,
+    /// 매칭 결과에 더하고 뺄 컴포넌트 (`--add-skill`, `--remove-agent` 등)
+    pub overrides: ComponentOverrides,
+}
+
+/// `from`에 PRD가 여럿이면 매칭 결과를 합쳐 하나의 구성을 만든다. 첫 PRD가 기준이다.
+pub fn run(ctx: &mut Ctx, from: Vec<PathBuf>, options: Options) -> Result<()> {
+    let Options {
+        minimal,
+        force,
+        dry_run,
+        prune,
+        suggest,
+        interactive,
+        mut language,
+        profile,
+        from_url,
+        no_detect,
+        with_local,
+        output,
+        gitignore,
+        settings_format,
+        stages,
+        overrides,
+    } = options;
     let cwd = std::env::current_dir()?;
+    // --dry-run은 --output 디렉토리도 만들지 않는다
+    let base = resolve_output_dir(&cwd, output, !dry_run)?;
 
-    println!(
-        "{} {}",
-        style("→").cyan().bold(),
-        style(format!("Parsing {}", prd_path.display())).bold()
-    );
+    // --interactive는 PRD.md가 없을 때만 질문으로 대신한다
+    let (mut parsed, prd_paths) = if let Some(profile) = profile {
+        let parsed = ParsedPrd {
+            frontmatter: profile_prd(&profile, &default_project_name(&cwd))?,
+            body: PrdBody::default(),
+        };
+        (vec![parsed], vec![cwd.join("PRD.md")])
+    } else if let Some(url) = from_url {
+        // 원격 PRD의 상대 경로(registry_paths)는 현재 디렉토리 기준이다
+        ctx.info(step(&format!("Fetching {url}")));
+        let parsed = prd::parse_prd(&remote::fetch_prd(&url)?)?;
+        (vec![parsed], vec![cwd.join("PRD.md")])
+    } else if interactive && from.is_empty() && !cwd.join("PRD.md").exists() {
+        (
+            vec![interactive_prd(ctx, &cwd, !dry_run, language.take())?],
+            vec![cwd.join("PRD.md")],
+        )
+    } else {
+        let prd_paths = resolve_prd_paths(from)?;
+        let mut parsed = Vec::new();
+        for prd_path in &prd_paths {
+            ctx.info(step(&format!("Parsing {}", prd_path.display())));
+            let resolved = std::fs::canonicalize(prd_path).unwrap_or_else(|_| prd_path.clone());
+            ctx.detail(format!("  PRD path: {}", resolved.display()));
+            parsed.push(prd::parse_prd_file(prd_path)?);
+        }
+        (parsed, prd_paths)
+    };
+    use_prd_registries(
+        ctx,
+        parsed
+            .iter()
+            .zip(&prd_paths)
+            .map(|(p, path)| (&p.frontmatter, path.as_path())),
+    )?;
 
-    let parsed = prd::parse_prd_file(&prd_path)?;
-    let fm = &parsed.frontmatter;
+    // 질문하지 않았으면 --language가 첫 PRD의 언어를 대신한다
+    if let Some(language) = language {
+        let stack = &mut parsed[0].frontmatter.stack;
+        if stack.language != language {
+            ctx.info(format!(
+                "  {} --language {} replaces PRD stack.language {:?}",
+                style("!").yellow(),
+                language.as_str(),
+                stack.language
+            ));
+        }
+        let detected = detect::detect_languages(&base);
+        if detected.len() > 1 {
+            ctx.info(format!(
+                "  {} Project files suggest {}; using --language {}",
+                style("!").yellow(),
+                detect::describe(&detected),
+                language.as_str()
+            ));
+        }
+        stack.language = language.into();
+    }
 
-    println!(
-        "  {} {} ({:?} / {:?})",
-        style("✓").green(),
-        fm.name,
-        fm.stack.language,
-        fm.project_type
-    );
+    // 프레임워크를 적지 않았으면 프로젝트 매니페스트에서 추측한다 (실패해도 그냥 넘어간다)
+    if !no_detect {
+        for p in &mut parsed {
+            let stack = &mut p.frontmatter.stack;
+            if stack.framework.is_some() {
+                continue;
+            }
+            let sniffed = stack
+                .language
+                .iter()
+                .find_map(|language| detect::sniff_framework(&base, language));
+            if let Some(framework) = sniffed {
+                ctx.detail(format!(
+                    "  Detected stack.framework: {framework} (--no-detect to skip)"
+                ));
+                stack.framework = Some(framework);
+            }
+        }
+    }
 
-    // 기존 설정 감지
-    if !force && project::has_existing_config(&cwd) {
+    for p in &parsed {
+        let fm = &p.frontmatter;
+        ctx.info(format!(
+            "  {} {} ({:?} / {:?})",
+            style("✓").green(),
+            fm.name,
+            fm.stack.language,
+            fm.project_type
+        ));
+    }
+    let fm = &parsed[0].frontmatter;
+
+    // 프로젝트 파일에서 감지한 언어와 PRD 선언이 어긋나면 알려준다
+    let detected = detect::detect_languages(&base);
+    if !detected.is_empty() && !detected.iter().any(|(l, _)| fm.stack.language.contains(l)) {
+        ctx.info(format!(
+            "  {} PRD declares {:?}, but project files suggest {}",
+            style("!").yellow(),
+            fm.stack.language,
+            detect::describe(&detected)
+        ));
+    }
+
+    // 기존 설정 감지. --dry-run이면 멈추지 않고 충돌로 보고한다.
+    let conflict = !force && project::has_existing_config(&base);
+    if conflict && !dry_run {
         return Err(KaelError::Project {
             message: ".claude/ or CLAUDE.md already exists. Use --force to overwrite.".into(),
         });
     }
 
-    println!(
-        "{} {}",
-        style("→").cyan().bold(),
-        style("Generating configuration").bold()
-    );
+    ctx.info(step("Generating configuration"));
 
-    let output = generator::generate(fm)?;
+    let mut output = if minimal {
+        generator::generate_minimal(&parsed[0])?
+    } else {
+        generator::generate_with_overrides(
+            &parsed,
+            stages,
+            settings_format,
+            &overrides,
+            &mut Timings::default(),
+        )?
+    };
+    if with_local {
+        generator::add_local_settings(&mut output, fm, stages)?;
+    }
 
-    println!(
+    ctx.info(format!(
         "  {} {} skills, {} agents, {} commands",
         style("✓").green(),
         output.skills.len(),
         output.agents.len(),
         output.commands.len()
-    );
+    ));
+    for file in output
+        .skills
+        .iter()
+        .chain(&output.agents)
+        .chain(&output.commands)
+    {
+        ctx.detail(format!("    {}", file.relative_path));
+    }
+    if let Some(warning) = missing_warning(&output) {
+        ctx.info(warning);
+    }
+    for warning in &output.prd_warnings {
+        ctx.info(warning_line(warning));
+    }
+
+    if dry_run {
+        for line in preview_tree(&output).lines() {
+            ctx.result(line);
+        }
+        if conflict {
+            ctx.result(format!(
+                "  {} Conflict: .claude/ or CLAUDE.md already exists; init would stop here without --force.",
+                style("!").yellow()
+            ));
+        }
+        ctx.summary(format!(
+            "{} files would be written (nothing was written).",
+            output.files().len()
+        ));
+        return Ok(());
+    }
+
+    ctx.info(step("Writing files"));
+
+    // --force는 덮어쓰기만 하고 아무것도 지우지 않는다.
+    // 새 결과에 없는 레지스트리 컴포넌트 정리는 --prune으로만 한다.
+    let options = if force {
+        ApplyOptions {
+            mode: ApplyMode::Force,
+            prune,
+            ..ApplyOptions::default()
+        }
+    } else {
+        ApplyMode::Create.into()
+    };
+    let report = project::apply(&base, &output, options)?;
+    for line in report_lines(&base, &report) {
+        ctx.info(line);
+    }
+    if gitignore && project::ensure_gitignore(&base, project::GITIGNORE_ENTRIES)? {
+        ctx.info(format!("  {} .gitignore (kael block)", style("~").yellow()));
+    }
+    if report.removed() > 0 {
+        ctx.info(format!(
+            "  {} {} stale components removed (--prune)",
+            style("-").red(),
+            report.removed()
+        ));
+    }
+
+    ctx.summary(format!(
+        "{} files written. Claude Code is ready.",
+        report.written()
+    ));
+
+    if suggest {
+        print_suggestions(ctx, &suggestions(&fm.stack.language, &output));
+    }
+
+    Ok(())
+}
+
+/// 진행 단계 제목 줄 (`→ Parsing ...`)
+fn step(title: &str) -> String {
+    format!("{} {}", style("→").cyan().bold(), style(title).bold())
+}
+
+/// `--output`으로 받은 디렉토리를 기록 기준 경로로 정한다. `create`면 없을 때 만든다.
+///
+/// 상대 경로는 `cwd` 기준이며, 지정하지 않으면 `cwd`를 그대로 쓴다.
+pub(crate) fn resolve_output_dir(
+    cwd: &Path,
+    output: Option<PathBuf>,
+    create: bool,
+) -> Result<PathBuf> {
+    let Some(output) = output else {
+        return Ok(cwd.to_path_buf());
+    };
+    let dir = cwd.join(output);
+    if create && !dir.is_dir() {
+        std::fs::create_dir_all(&dir)?;
+    }
+    Ok(dir)
+}
+
+/// 대화형 질문에 답을 받는 방법. 테스트에서는 미리 정한 답을 주입한다.
+pub trait Prompter {
+    fn input(&mut self, prompt: &str, default: Option<&str>) -> Result<String>;
+    fn select(&mut self, prompt: &str, items: &[String], default: usize) -> Result<usize>;
+    fn confirm(&mut self, prompt: &str, default: bool) -> Result<bool>;
+}
+
+/// 터미널에서 dialoguer로 묻는다.
+pub struct TerminalPrompter;
+
+impl Prompter for TerminalPrompter {
+    fn input(&mut self, prompt: &str, default: Option<&str>) -> Result<String> {
+        let mut input = dialoguer::Input::<String>::new()
+            .with_prompt(prompt)
+            .allow_empty(default.is_some());
+        if let Some(default) = default {
+            input = input
+                .default(default.to_string())
+                .show_default(!default.is_empty());
+        }
+        input.interact_text().map_err(prompt_error)
+    }
+
+    fn select(&mut self, prompt: &str, items: &[String], default: usize) -> Result<usize> {
+        dialoguer::Select::new()
+            .with_prompt(prompt)
+            .items(items)
+            .default(default)
+            .interact()
+            .map_err(prompt_error)
+    }
+
+    fn confirm(&mut self, prompt: &str, default: bool) -> Result<bool> {
+        dialoguer::Confirm::new()
+            .with_prompt(prompt)
+            .default(default)
+            .interact()
+            .map_err(prompt_error)
+    }
+}
+
+fn prompt_error(err: dialoguer::Error) -> KaelError {
+    KaelError::Project {
+        message: format!("Interactive prompt failed: {err}"),
+    }
+}
+
+/// PRD.md 없이 질문으로 PRD를 만들고, 원하면 `PRD.md`로 저장한다. (`init --interactive`)
+///
+/// 터미널이 아니면 질문하지 않고 바로 에러를 낸다.
+/// `allow_save`가 거짓이면(--dry-run) PRD.md로 저장할지 묻지 않는다.
+/// 언어 질문의 기본값은 `language`(`--language`), 없으면 감지한 언어다.
+fn interactive_prd(
+    ctx: &mut Ctx,
+    cwd: &Path,
+    allow_save: bool,
+    language: Option<Language>,
+) -> Result<ParsedPrd> {
+    if !std::io::stdin().is_terminal() {
+        return Err(KaelError::Prd {
+            message: "No PRD.md found and --interactive requires a terminal. Use --from <path> to specify.".into(),
+        });
+    }
+    let default_name = default_project_name(cwd);
+    // 여러 언어가 감지되고 --language도 없으면 임의로 고르지 않고 기본값 없이 묻는다
+    let detected = match detect::resolve_language(&detect::detect_languages(cwd), language) {
+        Ok(language) => language,
+        Err(err) => {
+            ctx.info(format!("  {} {err}", style("!").yellow()));
+            None
+        }
+    };
+
+    let mut prompter = TerminalPrompter;
+    let prd = prompt_prd(&mut prompter, &default_name, detected.as_ref())?;
+    if allow_save && prompter.confirm("Save these answers to PRD.md?", true)? {
+        std::fs::write(cwd.join("PRD.md"), prd_file(&prd)?)?;
+        ctx.info(format!("  {} PRD.md written", style("✓").green()));
+    }
+    Ok(ParsedPrd {
+        frontmatter: prd,
+        body: PrdBody::default(),
+    })
+}
+
+/// 프로젝트 이름, 언어, 타입, 프레임워크(선택)를 물어 PRD를 만든다.
+///
+/// `detected`는 언어 선택의 기본값이다. 빈 프레임워크는 지정하지 않은 것으로 본다.
+pub(crate) fn prompt_prd(
+    prompter: &mut dyn Prompter,
+    default_name: &str,
+    detected: Option<&Language>,
+) -> Result<Prd> {
+    let name = prompter.input("Project name", Some(default_name))?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(KaelError::Prd {
+            message: "Project name is required".into(),
+        });
+    }
+
+    let languages: Vec<String> = Language::ALL
+        .iter()
+        .map(|l| l.as_str().to_string())
+        .collect();
+    let default_language = detected
+        .and_then(|d| Language::ALL.iter().position(|l| l == d))
+        .unwrap_or(0);
+    let language = prompter.select("Language", &languages, default_language)?;
+    let types: Vec<String> = ProjectType::ALL
+        .iter()
+        .map(|t| t.as_str().to_string())
+        .collect();
+    let project_type = prompter.select("Project type", &types, 0)?;
+    let framework = prompter.input("Framework (optional)", Some(""))?;
+    let framework = framework.trim();
+
+    Ok(stack_prd(
+        name,
+        Language::ALL[language].clone(),
+        ProjectType::ALL[project_type].clone(),
+        (!framework.is_empty()).then_some(framework),
+    ))
+}
+
+/// `init --profile` 프리셋: 이름 → (언어, 타입, 프레임워크)
+const PROFILES: &[(&str, Language, ProjectType, Option<&str>)] = &[
+    ("rust-cli", Language::Rust, ProjectType::Cli, None),
+    (
+        "python-api",
+        Language::Python,
+        ProjectType::Api,
+        Some("fastapi"),
+    ),
+    (
+        "ts-nextjs",
+        Language::Typescript,
+        ProjectType::Web,
+        Some("nextjs"),
+    ),
+];
+
+/// 프리셋으로 PRD.md 없이 메모리에서 PRD를 만든다. 모르는 이름이면 가능한 프리셋을 알려준다.
+pub(crate) fn profile_prd(profile: &str, name: &str) -> Result<Prd> {
+    let Some((_, language, project_type, framework)) =
+        PROFILES.iter().find(|(p, ..)| *p == profile)
+    else {
+        let available: Vec<&str> = PROFILES.iter().map(|(p, ..)| *p).collect();
+        return Err(KaelError::Prd {
+            message: format!(
+                "Unknown profile '{profile}'. Available: {}",
+                available.join(", ")
+            ),
+        });
+    };
+    Ok(stack_prd(
+        name,
+        language.clone(),
+        project_type.clone(),
+        *framework,
+    ))
+}
+
+/// 이름과 스택만 정한 PRD. 나머지 필드는 비워 자동 매칭에 맡긴다.
+fn stack_prd(
+    name: &str,
+    language: Language,
+    project_type: ProjectType,
+    framework: Option<&str>,
+) -> Prd {
+    Prd {
+        name: name.to_string(),
+        description: None,
+        stack: Stack {
+            language: language.into(),
+            framework: framework.map(str::to_string),
+            database: None,
+            infra: None,
+        },
+        project_type,
+        features: None,
+        constraints: None,
+        agents: None,
+        skills: None,
+        mcp: None,
+        sections: None,
+        registry_paths: None,
+        envs: None,
+        team: None,
+    }
+}
+
+/// 디렉토리 이름을 프로젝트 이름 기본값으로 쓴다.
+fn default_project_name(cwd: &Path) -> String {
+    cwd.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// frontmatter만 있는 PRD.md 내용
+fn prd_file(prd: &Prd) -> Result<String> {
+    Ok(format!("---\n{}---\n", prd.to_yaml()?))
+}
+
+/// 레지스트리에서 찾지 못해 빠진 컴포넌트를 경고한다.
+pub(crate) fn print_missing(output: &GeneratedOutput) {
+    if let Some(warning) = missing_warning(output) {
+        println!("{warning}");
+    }
+}
+
+fn missing_warning(output: &GeneratedOutput) -> Option<String> {
+    if output.missing.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "  {} {}",
+        style("!").yellow(),
+        style(format!(
+            "not found in registry, skipped: {}",
+            output.missing.join(", ")
+        ))
+        .yellow()
+    ))
+}
+
+/// `stack.framework`가 언어에서 알려진 값이 아니면 경고한다.
+pub(crate) fn print_framework_warning(prd: &Prd) {
+    if let Some(warning) = matcher::framework_warning(prd) {
+        println!("{}", warning_line(&warning));
+    }
+}
 
-    println!(
-        "{} {}",
-        style("→").cyan().bold(),
-        style("Writing files").bold()
-    );
+/// 생성 결과의 `prd_warnings`를 출력한다.
+pub(crate) fn print_prd_warnings(output: &GeneratedOutput) {
+    for warning in &output.prd_warnings {
+        println!("{}", warning_line(warning));
+    }
+}
+
+/// 들여쓴 노란색 경고 한 줄
+fn warning_line(warning: &str) -> String {
+    format!("  {} {}", style("!").yellow(), style(warning).yellow())
+}
+
+/// 종류별로 보여줄 추천 컴포넌트 최대 개수
+const SUGGESTION_LIMIT: usize = 5;
 
-    let written = project::write_output(&cwd, &output, force)?;
+/// 스택과 관련 있지만 매칭되지 않은 레지스트리 컴포넌트를 고른다.
+///
+/// 관련 있음 = 나열된 언어의 카테고리(`rust/` 등) 또는 기본 카테고리(`_base/`, `_common/`).
+/// commands는 카테고리가 없으므로 매칭되지 않은 것을 모두 후보로 본다.
+pub(crate) fn suggestions(
+    languages: &Languages,
+    output: &GeneratedOutput,
+) -> Vec<(ComponentKind, String)> {
+    let prefixes: Vec<String> = languages
+        .iter()
+        .map(|language| format!("{}/", language.as_str()))
+        .collect();
+    let installed: Vec<String> = output
+        .files()
+        .into_iter()
+        .map(|(relative, _)| relative.to_string_lossy().replace('\\', "/"))
+        .collect();
 
-    for path in &written {
-        let display = path
-            .strip_prefix(&cwd)
-            .unwrap_or(path)
-            .display()
-            .to_string();
-        println!("  {} {}", style("+").green(), display);
+    let mut out = Vec::new();
+    for kind in [
+        ComponentKind::Skill,
+        ComponentKind::Agent,
+        ComponentKind::Command,
+    ] {
+        let (base, rest) = list::partition_base(registry::list_components(kind));
+        // 언어별 컴포넌트를 기본 카테고리보다 먼저 보여준다
+        let candidates = rest
+            .into_iter()
+            .filter(|name| {
+                kind == ComponentKind::Command
+                    || prefixes.iter().any(|prefix| name.starts_with(prefix))
+            })
+            .chain(base)
+            .filter(|name| {
+                let path = format!(".claude/{}", kind.installed_path(name));
+                !installed.contains(&path)
+            });
+        out.extend(candidates.take(SUGGESTION_LIMIT).map(|name| (kind, name)));
     }
+    out
+}
 
-    println!(
-        "\n{} {} files written. Claude Code is ready.",
-        style("✓").green().bold(),
-        written.len()
-    );
+fn print_suggestions(ctx: &mut Ctx, suggestions: &[(ComponentKind, String)]) {
+    if suggestions.is_empty() {
+        return;
+    }
+    ctx.info(format!("\n{}", style("Available but not added:").bold()));
+    for (kind, name) in suggestions {
+        ctx.info(format!(
+            "  {} {name} — add with `kael add {} {name}`",
+            style("•").dim(),
+            kind.label()
+        ));
+    }
+}
+
+/// PRD의 `registry_paths`를 레지스트리 overlay로 설정한다.
+///
+/// 존재하지 않는 경로는 경고만 출력하고 건너뛴다.
+/// `--registry-source overlay`인데 쓸 수 있는 경로가 하나도 없으면 에러를 낸다.
+pub(crate) fn use_prd_registry(ctx: &mut Ctx, prd: &Prd, prd_path: &Path) -> Result<()> {
+    use_prd_registries(ctx, [(prd, prd_path)])
+}
 
+/// 여러 PRD의 `registry_paths`를 PRD 순서대로 모두 overlay로 설정한다.
+pub(crate) fn use_prd_registries<'a>(
+    ctx: &mut Ctx,
+    prds: impl IntoIterator<Item = (&'a Prd, &'a Path)>,
+) -> Result<()> {
+    let (found, missing): (Vec<_>, Vec<_>) = prds
+        .into_iter()
+        .flat_map(|(prd, prd_path)| prd.resolved_registry_paths(prd_path))
+        .partition(|p| p.is_dir());
+    for path in &missing {
+        ctx.info(format!(
+            "  {} PRD registry_paths entry not found, skipping: {}",
+            style("!").yellow(),
+            path.display()
+        ));
+    }
+    registry::set_overlays(found);
+    if registry::overlays().is_empty() && registry::source() == RegistrySource::Overlay {
+        return Err(KaelError::Project {
+            message: format!(
+                "--registry-source overlay requires at least one existing registry path ({})",
+                registry::OVERLAY_SOURCES
+            ),
+        });
+    }
     Ok(())
 }
 
-fn resolve_prd_path(from: Option<PathBuf>) -> Result<PathBuf> {
+/// `--from`/`--prd` 플래그(여러 번 가능)와 위치 인자 중 사용할 PRD 경로를 고른다.
+///
+/// 플래그가 우선하며, 위치 인자가 플래그 하나와 같지 않으면 에러를 낸다.
+pub fn resolve_prd_arg(flags: Vec<PathBuf>, positional: Option<PathBuf>) -> Result<Vec<PathBuf>> {
+    match positional {
+        Some(positional) if !flags.is_empty() && flags != [positional.clone()] => {
+            let flags: Vec<String> = flags.iter().map(|f| f.display().to_string()).collect();
+            Err(KaelError::Prd {
+                message: format!(
+                    "Conflicting PRD paths: --from {} and positional {}",
+                    flags.join(", "),
+                    positional.display()
+                ),
+            })
+        }
+        Some(positional) => Ok(vec![positional]),
+        None => Ok(flags),
+    }
+}
+
+/// 주어진 PRD 경로들이 있는지 확인한다. 비어 있으면 현재 디렉토리의 PRD.md 하나를 쓴다.
+pub(crate) fn resolve_prd_paths(from: Vec<PathBuf>) -> Result<Vec<PathBuf>> {
+    if from.is_empty() {
+        return Ok(vec![resolve_prd_path(None)?]);
+    }
+    from.into_iter()
+        .map(|path| resolve_prd_path(Some(path)))
+        .collect()
+}
+
+pub(crate) fn resolve_prd_path(from: Option<PathBuf>) -> Result<PathBuf> {
     match from {
         Some(path) => {
             if path.exists() {
@@ -99,3 +699,357 @@ fn resolve_prd_path(from: Option<PathBuf>) -> Result<PathBuf> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::ctx::Verbosity;
+    use crate::core::matcher::MatchResult;
+
+    #[test]
+    fn rust_cli_suggests_unmatched_rust_components() {
+        let parsed =
+            prd::parse_prd("---\nname: s\nstack:\n  language: rust\ntype: cli\n---\n").unwrap();
+        let output = generator::generate_parsed(&parsed).unwrap();
+        let suggested = suggestions(&Language::Rust.into(), &output);
+
+        assert!(suggested.contains(&(ComponentKind::Skill, "rust/ffi".to_string())));
+        // 이미 매칭된 것과 다른 언어 컴포넌트는 제외
+        assert!(!suggested.iter().any(|(_, n)| n == "rust/async-patterns"));
+        assert!(!suggested.iter().any(|(_, n)| n.starts_with("go/")));
+    }
+
+    /// 미리 정한 답을 순서대로 돌려주는 Prompter
+    struct Scripted(Vec<&'static str>);
+
+    impl Prompter for Scripted {
+        fn input(&mut self, _: &str, _: Option<&str>) -> Result<String> {
+            Ok(self.0.remove(0).to_string())
+        }
+
+        fn select(&mut self, _: &str, items: &[String], _: usize) -> Result<usize> {
+            let answer = self.0.remove(0);
+            Ok(items.iter().position(|item| item == answer).unwrap())
+        }
+
+        fn confirm(&mut self, _: &str, _: bool) -> Result<bool> {
+            Ok(self.0.remove(0) == "y")
+        }
+    }
+
+    #[test]
+    fn prompt_answers_become_prd() {
+        let mut answers = Scripted(vec!["my-api", "python", "api", " fastapi "]);
+        let prd = prompt_prd(&mut answers, "dir", None).unwrap();
+
+        assert_eq!(prd.name, "my-api");
+        assert_eq!(prd.stack.language, Language::Python);
+        assert_eq!(prd.project_type, ProjectType::Api);
+        assert_eq!(prd.stack.framework.as_deref(), Some("fastapi"));
+        assert!(answers.0.is_empty());
+
+        // 저장한 PRD.md는 다시 파싱해도 같은 내용이다
+        let reparsed = prd::parse_prd(&prd_file(&prd).unwrap()).unwrap();
+        assert_eq!(reparsed.frontmatter, prd);
+    }
+
+    #[test]
+    fn prompt_rejects_empty_name_and_skips_empty_framework() {
+        let err = prompt_prd(&mut Scripted(vec!["  "]), "", None).unwrap_err();
+        assert!(err.to_string().contains("Project name is required"));
+
+        let mut answers = Scripted(vec!["tool", "rust", "cli", ""]);
+        let prd = prompt_prd(&mut answers, "tool", Some(&Language::Rust)).unwrap();
+        assert_eq!(prd.stack.framework, None);
+    }
+
+    #[test]
+    fn rust_cli_profile_matches_hand_written_prd() {
+        let from_profile = profile_prd("rust-cli", "tool").unwrap();
+        let written =
+            prd::parse_prd("---\nname: tool\nstack:\n  language: rust\ntype: cli\n---\n").unwrap();
+
+        assert_eq!(from_profile, written.frontmatter);
+        assert_eq!(
+            matcher::match_components_cached(&from_profile, MatchStages::default()),
+            matcher::match_components_cached(&written.frontmatter, MatchStages::default())
+        );
+    }
+
+    #[test]
+    fn unknown_profile_lists_available_ones() {
+        let err = profile_prd("rust-web", "x").unwrap_err().to_string();
+        assert!(err.contains("Unknown profile 'rust-web'"), "{err}");
+        assert!(err.contains("rust-cli, python-api, ts-nextjs"), "{err}");
+
+        let nextjs = profile_prd("ts-nextjs", "site").unwrap();
+        assert_eq!(nextjs.stack.framework.as_deref(), Some("nextjs"));
+        assert_eq!(nextjs.project_type, ProjectType::Web);
+    }
+
+    #[test]
+    fn init_writes_into_nested_output_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let prd_path = dir.path().join("PRD.md");
+        std::fs::write(
+            &prd_path,
+            "---\nname: mono\nstack:\n  language: rust\ntype: cli\n---\n",
+        )
+        .unwrap();
+        let nested = dir.path().join("packages/tool");
+
+        let options = Options {
+            output: Some(nested.clone()),
+            ..Options::default()
+        };
+        run(
+            &mut Ctx::capturing(Default::default()),
+            vec![prd_path],
+            options,
+        )
+        .unwrap();
+
+        assert!(nested.join("CLAUDE.md").is_file());
+        assert!(nested.join(".claude/settings.json").is_file());
+        assert!(!dir.path().join("CLAUDE.md").exists());
+    }
+
+    /// 출력 수준별로 `init`을 실행해 모은 출력
+    fn init_output(verbosity: Verbosity) -> (String, GeneratedOutput) {
+        let dir = tempfile::tempdir().unwrap();
+        let prd_path = dir.path().join("PRD.md");
+        let prd = "---\nname: quiet\nstack:\n  language: rust\ntype: cli\n---\n";
+        std::fs::write(&prd_path, prd).unwrap();
+        let options = Options {
+            output: Some(dir.path().join("out")),
+            ..Options::default()
+        };
+        let mut ctx = Ctx::capturing(verbosity);
+        run(&mut ctx, vec![prd_path], options).unwrap();
+        let output = generator::generate_parsed(&prd::parse_prd(prd).unwrap()).unwrap();
+        (ctx.captured().to_string(), output)
+    }
+
+    #[test]
+    fn missing_registry_path_warning_is_quiet_with_quiet() {
+        let dir = tempfile::tempdir().unwrap();
+        let prd_path = dir.path().join("PRD.md");
+        let prd = prd::parse_prd(
+            "---\nname: r\nstack:\n  language: rust\ntype: cli\nregistry_paths: [gone]\n---\n",
+        )
+        .unwrap()
+        .frontmatter;
+
+        let mut quiet = Ctx::capturing(Verbosity::Quiet);
+        use_prd_registry(&mut quiet, &prd, &prd_path).unwrap();
+        assert_eq!(quiet.captured(), "");
+
+        let mut normal = Ctx::capturing(Verbosity::Normal);
+        use_prd_registry(&mut normal, &prd, &prd_path).unwrap();
+        assert!(normal
+            .captured()
+            .contains("PRD registry_paths entry not found, skipping"));
+    }
+
+    #[test]
+    fn language_overrides_prd_without_interactive() {
+        let dir = tempfile::tempdir().unwrap();
+        let prd_path = dir.path().join("PRD.md");
+        std::fs::write(
+            &prd_path,
+            "---\nname: poly\nstack:\n  language: python\ntype: cli\n---\n",
+        )
+        .unwrap();
+        let out = dir.path().join("out");
+        std::fs::create_dir(&out).unwrap();
+        std::fs::write(out.join("Cargo.toml"), "[package]\nname = \"poly\"\n").unwrap();
+        std::fs::write(out.join("package.json"), "{}").unwrap();
+        let options = Options {
+            language: Some(Language::Rust),
+            output: Some(out.clone()),
+            ..Options::default()
+        };
+        let mut ctx = Ctx::capturing(Verbosity::Normal);
+        run(&mut ctx, vec![prd_path], options).unwrap();
+
+        let captured = ctx.captured();
+        assert!(
+            captured.contains("--language rust replaces PRD stack.language Python"),
+            "{captured}"
+        );
+        assert!(
+            captured.contains("Project files suggest rust"),
+            "{captured}"
+        );
+        assert!(captured.contains("✓ poly (Rust / Cli)"), "{captured}");
+        assert!(out.join(".claude/skills/rust").is_dir());
+    }
+
+    #[test]
+    fn verbosity_controls_init_output() {
+        let (quiet, _) = init_output(Verbosity::Quiet);
+        let (normal, output) = init_output(Verbosity::Normal);
+        let (verbose, _) = init_output(Verbosity::Verbose);
+
+        // quiet: 장식 없는 최종 요약 한 줄
+        assert_eq!(quiet.lines().count(), 1, "{quiet}");
+        assert!(
+            quiet.ends_with(" files written. Claude Code is ready.\n"),
+            "{quiet}"
+        );
+        assert!(!quiet.contains('✓'));
+
+        // 단계 3개 + PRD 1줄 + 개수 1줄 + 파일별 줄 + 빈 줄 + 요약
+        assert_eq!(
+            normal.lines().count(),
+            3 + 1 + 1 + output.files().len() + 1 + 1,
+            "{normal}"
+        );
+        assert!(normal.contains("✓ quiet (Rust / Cli)"));
+
+        // verbose: PRD 경로 1줄과 컴포넌트마다 1줄이 더 붙는다
+        let components = output.skills.len() + output.agents.len() + output.commands.len();
+        assert_eq!(
+            verbose.lines().count(),
+            normal.lines().count() + 1 + components,
+            "{verbose}"
+        );
+        assert!(verbose.contains("PRD path: "));
+        assert!(verbose.contains("    agents/_base/architect.md"));
+    }
+
+    #[test]
+    fn overrides_change_the_generated_file_set() {
+        let dir = tempfile::tempdir().unwrap();
+        let prd_path = dir.path().join("PRD.md");
+        std::fs::write(
+            &prd_path,
+            "---\nname: tweak\nstack:\n  language: rust\ntype: cli\n---\n",
+        )
+        .unwrap();
+        let out = dir.path().join("out");
+        let options = Options {
+            output: Some(out.clone()),
+            overrides: ComponentOverrides {
+                add: MatchResult {
+                    skills: vec!["rust/ffi".into(), "rust/tokio".into()],
+                    ..MatchResult::default()
+                },
+                remove: MatchResult {
+                    agents: vec!["_base/reviewer".into()],
+                    commands: vec!["not-matched".into()],
+                    ..MatchResult::default()
+                },
+            },
+            ..Options::default()
+        };
+
+        let mut ctx = Ctx::capturing(Verbosity::Normal);
+        run(&mut ctx, vec![prd_path], options).unwrap();
+        let claude = out.join(".claude");
+        assert!(claude.join("skills/rust/ffi/SKILL.md").is_file());
+        assert!(!claude.join("agents/_base/reviewer.md").exists());
+        assert!(claude.join("agents/_base/architect.md").is_file());
+        assert!(claude.join("commands/commit.md").is_file());
+        assert!(
+            ctx.captured()
+                .contains("not found in registry, skipped: rust/tokio"),
+            "{}",
+            ctx.captured()
+        );
+
+        let claude_md = std::fs::read_to_string(out.join("CLAUDE.md")).unwrap();
+        assert!(claude_md.contains("rust/ffi"));
+        assert!(!claude_md.contains("rust/tokio"));
+    }
+
+    #[test]
+    fn force_keeps_added_skills_unless_pruning() {
+        let dir = tempfile::tempdir().unwrap();
+        let prd_path = dir.path().join("PRD.md");
+        std::fs::write(
+            &prd_path,
+            "---\nname: keep\nstack:\n  language: rust\ntype: cli\n---\n",
+        )
+        .unwrap();
+        let out = dir.path().join("out");
+        let options = |prune| Options {
+            force: true,
+            prune,
+            output: Some(out.clone()),
+            ..Options::default()
+        };
+        let quiet = || Ctx::capturing(Verbosity::Quiet);
+
+        run(&mut quiet(), vec![prd_path.clone()], options(false)).unwrap();
+        crate::cli::add::install(&out, ComponentKind::Skill, &["rust/ffi".into()]).unwrap();
+        let added = out.join(".claude/skills/rust/ffi/SKILL.md");
+
+        run(&mut quiet(), vec![prd_path.clone()], options(false)).unwrap();
+        assert!(added.is_file());
+
+        run(&mut quiet(), vec![prd_path], options(true)).unwrap();
+        assert!(!added.exists());
+    }
+
+    #[test]
+    fn dry_run_creates_nothing_and_reports_conflict() {
+        let dir = tempfile::tempdir().unwrap();
+        let prd_path = dir.path().join("PRD.md");
+        std::fs::write(
+            &prd_path,
+            "---\nname: dry\nstack:\n  language: rust\ntype: cli\n---\n",
+        )
+        .unwrap();
+        let out = dir.path().join("out");
+        let options = || Options {
+            dry_run: true,
+            output: Some(out.clone()),
+            ..Options::default()
+        };
+
+        let mut ctx = Ctx::capturing(Verbosity::Normal);
+        run(&mut ctx, vec![prd_path.clone()], options()).unwrap();
+        assert!(!out.exists());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+        assert!(ctx.captured().contains("CLAUDE.md ("), "{}", ctx.captured());
+        assert!(ctx.captured().contains("(nothing was written)"));
+
+        // 이미 설정이 있으면 에러 대신 충돌로 보고하고, 기존 파일은 그대로 둔다
+        std::fs::create_dir_all(&out).unwrap();
+        std::fs::write(out.join("CLAUDE.md"), "mine").unwrap();
+        let mut ctx = Ctx::capturing(Verbosity::Normal);
+        run(&mut ctx, vec![prd_path], options()).unwrap();
+        assert!(ctx.captured().contains("Conflict: "), "{}", ctx.captured());
+        assert_eq!(
+            std::fs::read_to_string(out.join("CLAUDE.md")).unwrap(),
+            "mine"
+        );
+        assert!(!out.join(".claude").exists());
+    }
+
+    #[test]
+    fn prd_arg_positional_only() {
+        let resolved = resolve_prd_arg(vec![], Some("docs/PRD.md".into())).unwrap();
+        assert_eq!(resolved, vec![PathBuf::from("docs/PRD.md")]);
+    }
+
+    #[test]
+    fn prd_arg_flag_only() {
+        let resolved = resolve_prd_arg(vec!["PRD.md".into()], None).unwrap();
+        assert_eq!(resolved, vec![PathBuf::from("PRD.md")]);
+        assert!(resolve_prd_arg(vec![], None).unwrap().is_empty());
+
+        let many = resolve_prd_arg(vec!["a.md".into(), "b.md".into()], None).unwrap();
+        assert_eq!(many, vec![PathBuf::from("a.md"), PathBuf::from("b.md")]);
+    }
+
+    #[test]
+    fn prd_arg_both_provided() {
+        let same = resolve_prd_arg(vec!["PRD.md".into()], Some("PRD.md".into())).unwrap();
+        assert_eq!(same, vec![PathBuf::from("PRD.md")]);
+
+        let err = resolve_prd_arg(vec!["a.md".into()], Some("b.md".into())).unwrap_err();
+        assert!(err.to_string().contains("Conflicting PRD paths"));
+    }
+}
@@ -1,29 +1,762 @@
+use std::path::Path;
+
 use console::style;
 
+use crate::cli::ctx::Ctx;
+use crate::core::manifest::{self, InstallState};
+use crate::core::prd::Language;
 use crate::core::registry::{self, ComponentKind};
-use crate::error::Result;
-use crate::ListKind;
-
-pub fn run(kind: ListKind, _installed: bool, _stack: Option<String>) -> Result<()> {
-    match kind {
-        ListKind::Skills => print_components(ComponentKind::Skill, "Skills"),
-        ListKind::Agents => print_components(ComponentKind::Agent, "Agents"),
-        ListKind::Commands => print_components(ComponentKind::Command, "Commands"),
-        ListKind::All => {
-            print_components(ComponentKind::Skill, "Skills");
-            println!();
-            print_components(ComponentKind::Agent, "Agents");
-            println!();
-            print_components(ComponentKind::Command, "Commands");
+use crate::error::{KaelError, Result};
+
+#[derive(Clone, Debug, clap::ValueEnum)]
+pub enum ListKind {
+    Skills,
+    Agents,
+    Commands,
+    All,
+}
+
+/// `--format` 출력 형식
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// 사람이 읽는 목록
+    #[default]
+    Text,
+    /// 종류별 배열을 담은 JSON 객체 하나 (`{"skills": [...], ...}`)
+    Json,
+    /// 컴포넌트마다 JSON 객체 한 줄 (스트리밍용)
+    Ndjson,
+}
+
+/// `--tree`에서 `/`가 없는 이름(대부분의 command)을 모으는 그룹 이름
+const ROOT_GROUP: &str = "(root)";
+
+/// `--stack`/`--tag`/범위 필터 조합. 여러 개를 지정하면 AND로 적용된다.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Filter<'a> {
+    pub stack: Option<&'a str>,
+    pub tag: Option<&'a str>,
+    pub scope: Scope,
+}
+
+/// 기본 포함(`_base/`, `_common/`) 컴포넌트와 그 외를 구분하는 표시 범위
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Scope {
+    #[default]
+    All,
+    /// `_base/*`, `_common/*`만
+    BaseOnly,
+    /// 언어/타입별 컴포넌트만
+    LanguageOnly,
+}
+
+/// 목록에 표시할 컴포넌트 한 줄
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    name: String,
+    tags: Vec<String>,
+    /// `--stale-only`일 때 레지스트리와 비교한 상태
+    state: Option<InstallState>,
+}
+
+pub fn run(
+    ctx: &mut Ctx,
+    kind: ListKind,
+    installed: bool,
+    stale_only: bool,
+    filter: Filter,
+    format: OutputFormat,
+    tree: bool,
+) -> Result<()> {
+    if let Some(stack) = filter.stack {
+        parse_stack(stack)?;
+    }
+
+    // --installed는 임베디드 레지스트리 대신 현재 디렉토리의 .claude/를 읽는다
+    let claude_dir = std::env::current_dir()?.join(".claude");
+    if installed && !claude_dir.is_dir() {
+        ctx.info(format!(
+            "{} No .claude/ directory found. Run `kael init` first.",
+            style("!").yellow()
+        ));
+        return Ok(());
+    }
+    let source = installed.then_some(claude_dir.as_path());
+
+    let kinds: &[(ComponentKind, &str)] = match kind {
+        ListKind::Skills => &[(ComponentKind::Skill, "Skills")],
+        ListKind::Agents => &[(ComponentKind::Agent, "Agents")],
+        ListKind::Commands => &[(ComponentKind::Command, "Commands")],
+        ListKind::All => &[
+            (ComponentKind::Skill, "Skills"),
+            (ComponentKind::Agent, "Agents"),
+            (ComponentKind::Command, "Commands"),
+        ],
+    };
+    let sections: Vec<_> = kinds
+        .iter()
+        .map(|(kind, title)| (*kind, *title, collect_entries(*kind, filter, source)))
+        .collect();
+    let sections = if stale_only {
+        let base = std::env::current_dir()?;
+        stale_sections(sections, &manifest::compare_install_to_registry(&base))
+    } else {
+        sections
+    };
+
+    match format {
+        // quiet에서는 제목과 기호 없이 이름만 한 줄씩 쓴다
+        OutputFormat::Text if !ctx.decorated() => {
+            for (_, _, entries) in &sections {
+                for entry in entries {
+                    ctx.result(&entry.name);
+                }
+            }
+        }
+        OutputFormat::Text => {
+            for (i, (_, title, entries)) in sections.iter().enumerate() {
+                if i > 0 {
+                    ctx.result("");
+                }
+                let section = if tree {
+                    render_tree(title, entries)
+                } else {
+                    render_section(title, entries)
+                };
+                ctx.result(section.trim_end_matches('\n'));
+            }
+            match source {
+                Some(claude_dir) => ctx.detail(format!("\nSource: {}", claude_dir.display())),
+                None => ctx.detail(format!(
+                    "\nSource: registry ({})",
+                    format!("{:?}", registry::source()).to_lowercase()
+                )),
+            }
+        }
+        OutputFormat::Json => ctx.result(render_json(&sections)?),
+        OutputFormat::Ndjson => {
+            for line in render_ndjson(&sections)?.lines() {
+                ctx.result(line);
+            }
         }
     }
     Ok(())
 }
 
-fn print_components(kind: ComponentKind, title: &str) {
-    let components = registry::list_components(kind);
-    println!("{} ({})", style(title).bold(), components.len());
-    for name in &components {
-        println!("  {} {}", style("•").dim(), name);
+fn collect_entries(kind: ComponentKind, filter: Filter, installed: Option<&Path>) -> Vec<Entry> {
+    let entries = match installed {
+        Some(claude_dir) => installed_entries(kind, claude_dir),
+        None => registry_entries(kind),
+    };
+    apply_filters(kind, entries, filter)
+}
+
+/// 오래됐거나 수정된 컴포넌트만 남기고 각 항목에 상태를 붙인다. (`--stale-only`)
+fn stale_sections<'a>(
+    sections: Vec<(ComponentKind, &'a str, Vec<Entry>)>,
+    drift: &[manifest::ComponentDrift],
+) -> Vec<(ComponentKind, &'a str, Vec<Entry>)> {
+    sections
+        .into_iter()
+        .map(|(kind, title, entries)| {
+            let entries = entries
+                .into_iter()
+                .filter_map(|mut entry| {
+                    let state = drift
+                        .iter()
+                        .find(|d| d.kind == kind && d.name == entry.name)
+                        .map(|d| d.state)
+                        .filter(|state| *state != InstallState::Current)?;
+                    entry.state = Some(state);
+                    Some(entry)
+                })
+                .collect();
+            (kind, title, entries)
+        })
+        .collect()
+}
+
+/// 상태에 맞는 해결 명령. 수정된 파일은 로컬 변경을 덮어쓰므로 `--force`가 필요하다.
+fn remediation(state: InstallState) -> Option<String> {
+    match state {
+        InstallState::Current => None,
+        InstallState::Outdated => Some("kael doctor --fix".into()),
+        InstallState::Modified => Some("kael init --force".into()),
+    }
+}
+
+/// 기계 판독용 출력의 컴포넌트 객체: `{"kind", "name", "tags"}`
+///
+/// `--stale-only`이면 `state`와 `hint`가 추가된다.
+fn json_items(kind: ComponentKind, entries: &[Entry]) -> Vec<serde_json::Value> {
+    entries
+        .iter()
+        .map(|entry| {
+            let mut item = serde_json::json!({
+                "kind": kind.label(),
+                "name": entry.name,
+                "tags": entry.tags,
+            });
+            if let Some(state) = entry.state {
+                item["state"] = state.label().into();
+                item["hint"] = remediation(state).into();
+            }
+            item
+        })
+        .collect()
+}
+
+/// 종류별 이름(`skills`/`agents`/`commands`)을 키로 하는 JSON 객체를 만든다.
+fn render_json(sections: &[(ComponentKind, &str, Vec<Entry>)]) -> Result<String> {
+    let object: serde_json::Map<String, serde_json::Value> = sections
+        .iter()
+        .map(|(kind, title, entries)| {
+            let items = json_items(*kind, entries)
+                .into_iter()
+                .map(|mut item| {
+                    // 키가 종류를 나타내므로 객체 안의 kind는 뺀다
+                    if let Some(object) = item.as_object_mut() {
+                        object.remove("kind");
+                    }
+                    item
+                })
+                .collect();
+            (title.to_lowercase(), serde_json::Value::Array(items))
+        })
+        .collect();
+    Ok(serde_json::to_string_pretty(&object)?)
+}
+
+/// 컴포넌트마다 한 줄씩, 각 줄이 독립된 JSON 객체인 NDJSON을 만든다.
+fn render_ndjson(sections: &[(ComponentKind, &str, Vec<Entry>)]) -> Result<String> {
+    let mut out = String::new();
+    for (kind, _, entries) in sections {
+        for item in json_items(*kind, entries) {
+            out.push_str(&serde_json::to_string(&item)?);
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+fn registry_entries(kind: ComponentKind) -> Vec<Entry> {
+    registry::list_components(kind)
+        .into_iter()
+        .map(|name| {
+            let tags = registry::component_meta(kind, &name)
+                .map(|meta| meta.tags)
+                .unwrap_or_default();
+            Entry {
+                name,
+                tags,
+                state: None,
+            }
+        })
+        .collect()
+}
+
+/// `.claude/`에 설치된 컴포넌트. 태그는 설치된 파일의 frontmatter에서 읽는다.
+fn installed_entries(kind: ComponentKind, claude_dir: &Path) -> Vec<Entry> {
+    registry::installed_components(kind, claude_dir)
+        .into_iter()
+        .map(|name| {
+            let tags = std::fs::read_to_string(claude_dir.join(kind.installed_path(&name)))
+                .map(|content| registry::parse_meta(&content).tags)
+                .unwrap_or_default();
+            Entry {
+                name,
+                tags,
+                state: None,
+            }
+        })
+        .collect()
+}
+
+/// `--stack` 값을 알려진 언어로 검증한다.
+pub(crate) fn parse_stack(stack: &str) -> Result<Language> {
+    let names = || Language::ALL.map(|l| l.as_str());
+    Language::ALL
+        .into_iter()
+        .zip(names())
+        .find(|(_, name)| name.eq_ignore_ascii_case(stack))
+        .map(|(language, _)| language)
+        .ok_or_else(|| KaelError::Project {
+            message: format!(
+                "Unknown stack '{stack}'. Valid stacks: {}",
+                names().join(", ")
+            ),
+        })
+}
+
+/// 이름 목록을 기본 포함 컴포넌트(`_base/`, `_common/`)와 나머지로 나눈다.
+pub fn partition_base(names: Vec<String>) -> (Vec<String>, Vec<String>) {
+    names.into_iter().partition(|name| is_base(name))
+}
+
+fn is_base(name: &str) -> bool {
+    name.starts_with("_base/") || name.starts_with("_common/")
+}
+
+/// 범위 필터 → stack 필터 → tag 필터 순서로 적용한다.
+///
+/// stack 필터는 해당 스택 컴포넌트와 공용(`_base/`, `_common/`) 컴포넌트를 남긴다.
+/// commands는 스택별로 나뉘지 않으므로 stack 필터를 건너뛰고 tag 필터만 받는다.
+fn apply_filters(kind: ComponentKind, entries: Vec<Entry>, filter: Filter) -> Vec<Entry> {
+    entries
+        .into_iter()
+        .filter(|entry| match filter.scope {
+            Scope::All => true,
+            Scope::BaseOnly => is_base(&entry.name),
+            Scope::LanguageOnly => !is_base(&entry.name),
+        })
+        .filter(|entry| match filter.stack {
+            Some(stack) if kind != ComponentKind::Command => {
+                is_base(&entry.name)
+                    || entry
+                        .name
+                        .to_lowercase()
+                        .starts_with(&format!("{}/", stack.to_lowercase()))
+            }
+            _ => true,
+        })
+        .filter(|entry| match filter.tag {
+            Some(tag) => entry.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)),
+            None => true,
+        })
+        .collect()
+}
+
+fn render_section(title: &str, entries: &[Entry]) -> String {
+    let mut out = format!("{} ({})\n", style(title).bold(), entries.len());
+    if entries.is_empty() {
+        out.push_str(&format!("  {}\n", style("no matches").dim()));
+    }
+    for entry in entries {
+        out.push_str(&entry_line("  ", entry, &entry.name));
+    }
+    out
+}
+
+/// `render_section`과 같되, 첫 `/` 앞의 카테고리별로 묶어 들여쓴다.
+///
+/// 카테고리는 목록에 처음 나온 순서를 따르고, `/`가 없는 이름은 `(root)` 아래에 둔다.
+fn render_tree(title: &str, entries: &[Entry]) -> String {
+    let mut groups: Vec<(&str, Vec<(&Entry, &str)>)> = Vec::new();
+    for entry in entries {
+        let (group, leaf) = match entry.name.split_once('/') {
+            Some((category, leaf)) => (category, leaf),
+            None => (ROOT_GROUP, entry.name.as_str()),
+        };
+        match groups.iter_mut().find(|(name, _)| *name == group) {
+            Some((_, members)) => members.push((entry, leaf)),
+            None => groups.push((group, vec![(entry, leaf)])),
+        }
+    }
+
+    let mut out = format!("{} ({})\n", style(title).bold(), entries.len());
+    if entries.is_empty() {
+        out.push_str(&format!("  {}\n", style("no matches").dim()));
+    }
+    for (group, members) in groups {
+        let header = if group == ROOT_GROUP {
+            group.to_string()
+        } else {
+            format!("{group}/")
+        };
+        out.push_str(&format!("  {}\n", style(header).cyan()));
+        for (entry, leaf) in members {
+            out.push_str(&entry_line("    ", entry, leaf));
+        }
+    }
+    out
+}
+
+/// 목록 한 줄. `--stale-only`면 상태와 고치는 명령을 덧붙인다.
+fn entry_line(indent: &str, entry: &Entry, label: &str) -> String {
+    match entry
+        .state
+        .and_then(|state| Some((state, remediation(state)?)))
+    {
+        Some((state, hint)) => format!(
+            "{indent}{} {label} {} {} {hint}\n",
+            style("•").dim(),
+            style(format!("({})", state.label())).yellow(),
+            style("→").dim(),
+        ),
+        None => format!("{indent}{} {label}\n", style("•").dim()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::ctx::Verbosity;
+
+    fn entry(name: &str, tags: &[&str]) -> Entry {
+        Entry {
+            name: name.into(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            state: None,
+        }
+    }
+
+    fn fixture() -> Vec<Entry> {
+        vec![
+            entry("_common/testing", &["testing"]),
+            entry("go/testing", &["testing"]),
+            entry("rust/async-patterns", &["concurrency"]),
+            entry("rust/property-testing", &["testing"]),
+            entry("typescript/testing", &["testing"]),
+        ]
+    }
+
+    #[test]
+    fn stack_and_tag_intersect() {
+        let filter = Filter {
+            stack: Some("rust"),
+            tag: Some("testing"),
+            ..Filter::default()
+        };
+        let result = apply_filters(ComponentKind::Skill, fixture(), filter);
+        assert_eq!(
+            result,
+            vec![
+                entry("_common/testing", &["testing"]),
+                entry("rust/property-testing", &["testing"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn rust_stack_keeps_shared_entries() {
+        let filter = Filter {
+            stack: Some("rust"),
+            ..Filter::default()
+        };
+        let result = apply_filters(ComponentKind::Skill, fixture(), filter);
+        let names: Vec<_> = result.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "_common/testing",
+                "rust/async-patterns",
+                "rust/property-testing"
+            ]
+        );
+        assert_eq!(parse_stack("Rust").unwrap(), Language::Rust);
+    }
+
+    #[test]
+    fn unknown_stack_lists_valid_ones() {
+        let err = parse_stack("cobol").unwrap_err().to_string();
+        assert!(err.contains("Unknown stack 'cobol'"));
+        assert!(err.contains("rust, typescript, python, go, java, csharp, ruby"));
+
+        // All도 출력 전에 같은 검증을 거친다
+        let err = run(
+            &mut Ctx::capturing(Default::default()),
+            ListKind::All,
+            false,
+            false,
+            Filter {
+                stack: Some("cobol"),
+                ..Filter::default()
+            },
+            OutputFormat::Text,
+            false,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Valid stacks"));
+    }
+
+    #[test]
+    fn quiet_lists_bare_names() {
+        let list = |verbosity| {
+            let mut ctx = Ctx::capturing(verbosity);
+            let filter = Filter::default();
+            run(
+                &mut ctx,
+                ListKind::Commands,
+                false,
+                false,
+                filter,
+                OutputFormat::Text,
+                false,
+            )
+            .unwrap();
+            ctx.captured().to_string()
+        };
+        let commands = registry::list_components(ComponentKind::Command);
+
+        let quiet = list(Verbosity::Quiet);
+        assert_eq!(quiet.lines().collect::<Vec<_>>(), commands);
+
+        let normal = list(Verbosity::Normal);
+        assert_eq!(normal.lines().count(), commands.len() + 1);
+        assert!(normal.starts_with(&format!("Commands ({})", commands.len())));
+
+        let verbose = list(Verbosity::Verbose);
+        assert_eq!(verbose.lines().count(), normal.lines().count() + 2);
+        assert!(verbose.ends_with("Source: registry (both)\n"), "{verbose}");
+    }
+
+    #[test]
+    fn all_kinds_filter_by_stack() {
+        let filter = Filter {
+            stack: Some("rust"),
+            ..Filter::default()
+        };
+        for kind in [ComponentKind::Skill, ComponentKind::Agent] {
+            let result = apply_filters(kind, registry_entries(kind), filter);
+            assert!(!result.is_empty());
+            assert!(result
+                .iter()
+                .all(|e| is_base(&e.name) || e.name.starts_with("rust/")));
+        }
+        let commands = registry_entries(ComponentKind::Command);
+        let result = apply_filters(ComponentKind::Command, commands.clone(), filter);
+        assert_eq!(result, commands);
+    }
+
+    #[test]
+    fn tag_only_spans_stacks() {
+        let filter = Filter {
+            tag: Some("testing"),
+            ..Filter::default()
+        };
+        let result = apply_filters(ComponentKind::Skill, fixture(), filter);
+        assert_eq!(result.len(), 4);
+    }
+
+    #[test]
+    fn commands_ignore_stack_filter() {
+        let commands = vec![entry("test", &["testing"]), entry("commit", &[])];
+        let filter = Filter {
+            stack: Some("rust"),
+            tag: Some("testing"),
+            ..Filter::default()
+        };
+        let result = apply_filters(ComponentKind::Command, commands, filter);
+        assert_eq!(result, vec![entry("test", &["testing"])]);
+    }
+
+    #[test]
+    fn partition_separates_base_agents() {
+        let names = vec![
+            "_base/architect".to_string(),
+            "rust/perf-engineer".to_string(),
+            "_base/reviewer".to_string(),
+            "go/api-expert".to_string(),
+        ];
+        let (base, rest) = partition_base(names);
+        assert_eq!(base, vec!["_base/architect", "_base/reviewer"]);
+        assert_eq!(rest, vec!["rust/perf-engineer", "go/api-expert"]);
+    }
+
+    #[test]
+    fn scope_filters_entries() {
+        let base_only = Filter {
+            scope: Scope::BaseOnly,
+            ..Filter::default()
+        };
+        let result = apply_filters(ComponentKind::Skill, fixture(), base_only);
+        assert_eq!(result, vec![entry("_common/testing", &["testing"])]);
+
+        let language_only = Filter {
+            scope: Scope::LanguageOnly,
+            ..Filter::default()
+        };
+        let result = apply_filters(ComponentKind::Skill, fixture(), language_only);
+        assert_eq!(result.len(), 4);
+        assert!(result.iter().all(|e| !e.name.starts_with("_common/")));
+    }
+
+    #[test]
+    fn installed_reads_claude_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let claude = dir.path().join(".claude");
+        let skill = claude.join("skills/rust/async-patterns");
+        std::fs::create_dir_all(&skill).unwrap();
+        std::fs::write(skill.join("SKILL.md"), "---\ntags: [concurrency]\n---\n").unwrap();
+        std::fs::create_dir_all(claude.join("agents/_base")).unwrap();
+        std::fs::write(claude.join("agents/_base/reviewer.md"), "# Reviewer\n").unwrap();
+        std::fs::create_dir_all(claude.join("commands")).unwrap();
+        std::fs::write(claude.join("commands/commit.md"), "# Commit\n").unwrap();
+
+        let skills = installed_entries(ComponentKind::Skill, &claude);
+        assert_eq!(skills, vec![entry("rust/async-patterns", &["concurrency"])]);
+        let agents = installed_entries(ComponentKind::Agent, &claude);
+        assert_eq!(agents, vec![entry("_base/reviewer", &[])]);
+        let commands = installed_entries(ComponentKind::Command, &claude);
+        assert_eq!(commands, vec![entry("commit", &[])]);
+
+        let filter = Filter {
+            stack: Some("go"),
+            ..Filter::default()
+        };
+        assert!(apply_filters(ComponentKind::Skill, skills, filter).is_empty());
+    }
+
+    #[test]
+    fn stale_entries_carry_upgrade_hints() {
+        let dir = tempfile::tempdir().unwrap();
+        let claude = dir.path().join(".claude");
+        let skill = claude.join("skills/rust/ffi");
+        std::fs::create_dir_all(&skill).unwrap();
+        std::fs::write(skill.join("SKILL.md"), "# FFI (old release)\n").unwrap();
+        std::fs::create_dir_all(claude.join("agents/_base")).unwrap();
+        std::fs::write(claude.join("agents/_base/reviewer.md"), "# Edited\n").unwrap();
+        std::fs::create_dir_all(claude.join("commands")).unwrap();
+        std::fs::write(
+            claude.join("commands/commit.md"),
+            registry::get_component(ComponentKind::Command, "commit").unwrap(),
+        )
+        .unwrap();
+        // 스킬은 kael이 기록한 그대로, 에이전트는 기록 이후 수정됨
+        let recorded = |path: &str, bytes: &[u8]| manifest::ManifestEntry {
+            path: path.into(),
+            sha256: manifest::sha256_hex(bytes),
+            user_authored: false,
+        };
+        manifest::write(
+            dir.path(),
+            &manifest::Manifest {
+                version: "0.0.0".into(),
+                registry_version: None,
+                generated_at: 0,
+                files: vec![
+                    recorded(".claude/skills/rust/ffi/SKILL.md", b"# FFI (old release)\n"),
+                    recorded(".claude/agents/_base/reviewer.md", b"# Reviewer\n"),
+                ],
+            },
+        )
+        .unwrap();
+
+        let sections: Vec<_> = ComponentKind::ALL
+            .into_iter()
+            .map(|kind| (kind, "", installed_entries(kind, &claude)))
+            .collect();
+        let drift = manifest::compare_install_to_registry(dir.path());
+        let stale = stale_sections(sections, &drift);
+
+        let skill = &stale[0].2[0];
+        assert_eq!(skill.state, Some(InstallState::Outdated));
+        assert_eq!(
+            remediation(InstallState::Outdated).unwrap(),
+            "kael doctor --fix"
+        );
+        let agent = &stale[1].2[0];
+        assert_eq!(agent.state, Some(InstallState::Modified));
+        assert!(remediation(InstallState::Modified)
+            .unwrap()
+            .contains("--force"));
+        // 레지스트리와 같은 command는 빠진다
+        assert!(stale[2].2.is_empty());
+    }
+
+    #[test]
+    fn tree_groups_entries_by_category() {
+        let mut entries = fixture();
+        entries.push(entry("db/migrate", &[]));
+        entries.push(entry("commit", &[]));
+        assert_eq!(
+            render_tree("Skills", &entries),
+            "Skills (7)\n  _common/\n    • testing\n  go/\n    • testing\n  rust/\n    • async-patterns\n    • property-testing\n  typescript/\n    • testing\n  db/\n    • migrate\n  (root)\n    • commit\n"
+        );
+
+        let mut ctx = Ctx::capturing(Verbosity::Normal);
+        let filter = Filter {
+            stack: Some("rust"),
+            ..Filter::default()
+        };
+        run(
+            &mut ctx,
+            ListKind::Agents,
+            false,
+            false,
+            filter,
+            OutputFormat::Text,
+            true,
+        )
+        .unwrap();
+        let tree = ctx.captured();
+        assert!(tree.starts_with("Agents ("), "{tree}");
+        assert!(tree.contains("\n  _base/\n    • "), "{tree}");
+        assert!(tree.contains("\n    • architect\n"), "{tree}");
+        assert!(tree.contains("\n  rust/\n    • "), "{tree}");
+        assert!(!tree.contains("• rust/"), "{tree}");
+    }
+
+    #[test]
+    fn json_groups_entries_by_kind() {
+        let filter = Filter {
+            stack: Some("go"),
+            tag: Some("concurrency"),
+            ..Filter::default()
+        };
+        let sections = vec![
+            (ComponentKind::Skill, "Skills", fixture()),
+            (
+                ComponentKind::Agent,
+                "Agents",
+                apply_filters(ComponentKind::Agent, fixture(), filter),
+            ),
+            (
+                ComponentKind::Command,
+                "Commands",
+                vec![entry("commit", &[])],
+            ),
+        ];
+        let value: serde_json::Value =
+            serde_json::from_str(&render_json(&sections).unwrap()).unwrap();
+
+        let skills = value["skills"].as_array().unwrap();
+        assert_eq!(skills.len(), fixture().len());
+        assert_eq!(
+            skills[0],
+            serde_json::json!({"name": "_common/testing", "tags": ["testing"]})
+        );
+        assert_eq!(value["agents"], serde_json::json!([]));
+        assert_eq!(value["commands"][0]["name"], "commit");
+    }
+
+    #[test]
+    fn ndjson_has_one_object_per_line() {
+        let sections = vec![
+            (ComponentKind::Skill, "Skills", fixture()),
+            (
+                ComponentKind::Command,
+                "Commands",
+                vec![entry("commit", &[])],
+            ),
+        ];
+        let out = render_ndjson(&sections).unwrap();
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines.len(), fixture().len() + 1);
+
+        for line in &lines {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert!(value.is_object());
+        }
+        let last: serde_json::Value = serde_json::from_str(lines[5]).unwrap();
+        assert_eq!(
+            last,
+            serde_json::json!({"kind": "command", "name": "commit", "tags": []})
+        );
+    }
+
+    #[test]
+    fn empty_intersection_prints_no_matches() {
+        let filter = Filter {
+            stack: Some("go"),
+            tag: Some("concurrency"),
+            ..Filter::default()
+        };
+        let result = apply_filters(ComponentKind::Skill, fixture(), filter);
+        assert!(result.is_empty());
+
+        let rendered = render_section("Skills", &result);
+        assert!(rendered.contains("Skills (0)"));
+        assert!(rendered.contains("no matches"));
     }
 }
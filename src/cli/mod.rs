@@ -1,6 +1,15 @@
 pub mod add;
+pub mod color;
+pub mod ctx;
+pub mod diff;
 pub mod doctor;
+pub mod explain;
 pub mod generate;
 pub mod init;
 pub mod list;
+pub mod registry;
 pub mod remove;
+pub mod schema;
+pub mod upgrade_prd;
+pub mod validate;
+pub mod version;
@@ -0,0 +1,45 @@
+use std::path::Path;
+
+use console::style;
+
+use crate::core::migrate;
+use crate::error::Result;
+
+/// `kael registry migrate <path>`: 레이아웃 문제를 보고하고, `--apply`면 고친다.
+pub fn run_migrate(path: &Path, apply: bool) -> Result<()> {
+    let plan = migrate::plan_migration(path)?;
+    if plan.is_empty() {
+        println!(
+            "{} {} already matches the expected layout.",
+            style("✓").green().bold(),
+            path.display()
+        );
+        return Ok(());
+    }
+
+    for migration in &plan {
+        println!(
+            "  {} {} → {} {}",
+            style("~").yellow(),
+            migration.from.display(),
+            migration.to.display(),
+            style(format!("({})", migration.reason)).dim()
+        );
+    }
+
+    if apply {
+        migrate::apply_migration(path, &plan)?;
+        println!(
+            "\n{} {} files moved.",
+            style("✓").green().bold(),
+            plan.len()
+        );
+    } else {
+        println!(
+            "\n{} {} files would be moved. Re-run with --apply to migrate.",
+            style("!").yellow().bold(),
+            plan.len()
+        );
+    }
+    Ok(())
+}
@@ -1,4 +1,11 @@
-use crate::error::Result;
+use std::path::{Component, Path, PathBuf};
+
+use console::style;
+
+use crate::cli::generate::{display_path, print_status};
+use crate::core::manifest::{self, Manifest};
+use crate::core::project::WriteStatus;
+use crate::error::{KaelError, Result};
 
 pub fn run_skill(_name: &str) -> Result<()> {
     todo!("kael remove skill")
@@ -11,3 +18,150 @@ pub fn run_agent(_name: &str) -> Result<()> {
 pub fn run_command(_name: &str) -> Result<()> {
     todo!("kael remove command")
 }
+
+/// `kael remove --all`
+pub fn run_all() -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let report = remove_all(&cwd)?;
+    for path in &report.removed {
+        print_status(&display_path(&cwd, path), WriteStatus::Removed);
+    }
+    println!(
+        "\n{} {} kael files removed, {} user files kept.",
+        style("✓").green().bold(),
+        report.removed.len(),
+        report.kept.len()
+    );
+    Ok(())
+}
+
+/// `remove_all` 결과 (`base` 기준 절대 경로)
+#[derive(Debug, Default)]
+pub struct RemoveReport {
+    pub removed: Vec<PathBuf>,
+    /// manifest에 사용자 작성으로 기록되어 남겨둔 파일
+    pub kept: Vec<PathBuf>,
+}
+
+/// manifest에 기록된 kael 파일을 정확히 그만큼 지우고, 비게 된 `.claude/` 하위 디렉토리를 정리한다.
+///
+/// manifest가 없으면 무엇을 지울지 추측하지 않고 에러를 낸다.
+/// 사용자 작성 파일과 manifest에 없는 파일은 남긴다. 사용자 작성 파일이 남으면
+/// manifest도 그 항목만 남겨 다시 기록한다.
+pub fn remove_all(base: &Path) -> Result<RemoveReport> {
+    let Some(manifest) = manifest::read(base)? else {
+        return Err(KaelError::Project {
+            message: format!(
+                "No {} found, so kael cannot tell which files it installed. Remove them manually.",
+                manifest::MANIFEST_PATH
+            ),
+        });
+    };
+
+    // 손상된 manifest가 프로젝트 밖을 가리키면 하나도 지우기 전에 멈춘다
+    if let Some(entry) = manifest.files.iter().find(|e| !is_contained(&e.path)) {
+        return Err(KaelError::InvalidName {
+            name: entry.path.clone(),
+        });
+    }
+
+    let mut report = RemoveReport::default();
+    let mut user_files = Vec::new();
+    for entry in manifest.files {
+        let path = base.join(&entry.path);
+        if entry.user_authored {
+            report.kept.push(path);
+            user_files.push(entry);
+            continue;
+        }
+        if path.is_file() {
+            std::fs::remove_file(&path)?;
+            report.removed.push(path);
+        }
+    }
+
+    let manifest_path = base.join(manifest::MANIFEST_PATH);
+    if user_files.is_empty() {
+        std::fs::remove_file(&manifest_path)?;
+        report.removed.push(manifest_path);
+    } else {
+        manifest::write(
+            base,
+            &Manifest {
+                files: user_files,
+                ..manifest
+            },
+        )?;
+    }
+    remove_empty_dirs(&base.join(".claude"))?;
+    Ok(report)
+}
+
+/// 상대 경로이면서 `..`로 위로 올라가지 않는지
+fn is_contained(relative: &str) -> bool {
+    Path::new(relative)
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// `dir` 아래의 빈 디렉토리를 안쪽부터 지운다. `dir` 자체도 비면 지운다.
+pub(crate) fn remove_empty_dirs(dir: &Path) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            remove_empty_dirs(&path)?;
+        }
+    }
+    if std::fs::read_dir(dir)?.next().is_none() {
+        std::fs::remove_dir(dir)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{generator, prd, project};
+
+    #[test]
+    fn remove_all_deletes_only_kael_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let parsed =
+            prd::parse_prd("---\nname: r\nstack:\n  language: rust\ntype: cli\n---\n").unwrap();
+        let output = generator::generate_parsed(&parsed).unwrap();
+        project::write_output(dir.path(), &output, false).unwrap();
+        crate::cli::add::new_command(dir.path(), "deploy").unwrap();
+        let notes = dir.path().join(".claude/notes/todo.md");
+        std::fs::create_dir_all(notes.parent().unwrap()).unwrap();
+        std::fs::write(&notes, "mine").unwrap();
+
+        let report = remove_all(dir.path()).unwrap();
+
+        assert!(!dir.path().join("CLAUDE.md").exists());
+        assert!(!dir.path().join(".claude/skills").exists());
+        assert!(!dir.path().join(".claude/agents").exists());
+        assert!(!dir.path().join(".claude/settings.json").exists());
+        assert!(notes.is_file());
+        let deploy = dir.path().join(".claude/commands/deploy.md");
+        assert!(deploy.is_file());
+        assert_eq!(report.kept, vec![deploy]);
+        assert_eq!(report.removed.len(), output.files().len());
+
+        // 사용자 파일만 남은 manifest가 다시 기록된다
+        let manifest = manifest::read(dir.path()).unwrap().unwrap();
+        assert!(manifest.files.iter().all(|e| e.user_authored));
+    }
+
+    #[test]
+    fn remove_all_refuses_without_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".claude/skills")).unwrap();
+
+        let err = remove_all(dir.path()).unwrap_err().to_string();
+        assert!(err.contains("Remove them manually"), "{err}");
+        assert!(dir.path().join(".claude/skills").is_dir());
+    }
+}
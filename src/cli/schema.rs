@@ -0,0 +1,62 @@
+use console::style;
+
+use crate::core::prd::{Language, ProjectType};
+use crate::error::Result;
+
+/// `kael schema values`: frontmatter enum 필드가 받는 값을 출력한다.
+pub fn run_values() -> Result<()> {
+    print!("{}", render_values());
+    Ok(())
+}
+
+/// 파서와 같은 `as_str` 값에서 목록을 만든다.
+fn render_values() -> String {
+    let fields = [
+        ("stack.language", Language::ALL.map(|l| l.as_str()).to_vec()),
+        ("type", ProjectType::ALL.map(|t| t.as_str()).to_vec()),
+    ];
+    let mut out = String::new();
+    for (field, values) in fields {
+        out.push_str(&format!("{}\n", style(field).bold()));
+        for value in values {
+            out.push_str(&format!("  {} {value}\n", style("•").dim()));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_cover_languages_and_types() {
+        let out = render_values();
+        for value in [
+            "rust",
+            "typescript",
+            "python",
+            "go",
+            "java",
+            "csharp",
+            "ruby",
+        ] {
+            assert!(out.contains(&format!("• {value}\n")), "{value}");
+        }
+        for project_type in ProjectType::ALL {
+            assert!(out.contains(&format!("• {}\n", project_type.as_str())));
+        }
+    }
+
+    #[test]
+    fn as_str_matches_serde_names() {
+        for language in Language::ALL {
+            let yaml = serde_yaml_ng::to_string(&language).unwrap();
+            assert_eq!(yaml.trim(), language.as_str());
+        }
+        for project_type in ProjectType::ALL {
+            let yaml = serde_yaml_ng::to_string(&project_type).unwrap();
+            assert_eq!(yaml.trim(), project_type.as_str());
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use console::style;
+
+use crate::cli::init::resolve_prd_path;
+use crate::core::{diff, prd_upgrade};
+use crate::error::Result;
+
+/// `kael upgrade-prd`: 예전 키를 현재 스키마로 옮긴 diff를 보여주고, `--write`면 덮어쓴다.
+pub fn run(from: Option<PathBuf>, write: bool) -> Result<()> {
+    let prd_path = resolve_prd_path(from)?;
+    let content = std::fs::read_to_string(&prd_path)?;
+    let upgrade = prd_upgrade::upgrade_prd(&content)?;
+    if upgrade.changes.is_empty() {
+        println!(
+            "{} {} already uses the current schema.",
+            style("✓").green().bold(),
+            prd_path.display()
+        );
+        return Ok(());
+    }
+
+    let display = prd_path.display().to_string();
+    print!("{}", diff::unified(&display, &content, &upgrade.content));
+    println!();
+    for change in &upgrade.changes {
+        let note = if change.dropped {
+            format!(
+                " {}",
+                style(format!("(dropped, {} is already set)", change.to)).dim()
+            )
+        } else {
+            String::new()
+        };
+        println!(
+            "  {} {} → {}{note}",
+            style("~").yellow(),
+            change.from,
+            change.to
+        );
+    }
+
+    if write {
+        std::fs::write(&prd_path, &upgrade.content)?;
+        println!(
+            "\n{} {display} upgraded ({} keys).",
+            style("✓").green().bold(),
+            upgrade.changes.len()
+        );
+    } else {
+        println!(
+            "\n{} {} keys would change. Re-run with --write to upgrade {display}.",
+            style("!").yellow().bold(),
+            upgrade.changes.len()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,178 @@
+use std::path::{Path, PathBuf};
+
+use console::style;
+
+use crate::cli::ctx::Ctx;
+use crate::cli::init::{print_framework_warning, resolve_prd_path, use_prd_registry};
+use crate::core::matcher::{self, MatchResult};
+use crate::core::prd::{self, Prd};
+use crate::core::registry::{self, ComponentKind};
+use crate::error::{KaelError, Result};
+
+/// 매칭됐지만 레지스트리에 없는 컴포넌트
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Missing {
+    pub kind: ComponentKind,
+    pub name: String,
+    /// PRD의 `skills`/`agents`에 직접 적은 항목인지
+    pub explicit: bool,
+}
+
+/// 파일을 쓰지 않고 PRD 파싱과 매칭 결과만 확인한다.
+///
+/// 파싱에 실패하거나 명시한 skill/agent가 레지스트리에 없으면 에러를 낸다.
+pub fn run(ctx: &mut Ctx, from: Option<PathBuf>) -> Result<()> {
+    let prd_path = resolve_prd_path(from)?;
+    let parsed = prd::parse_prd_file(&prd_path)?;
+    let fm = &parsed.frontmatter;
+    use_prd_registry(ctx, fm, &prd_path)?;
+
+    println!(
+        "{} {} ({:?} / {:?})",
+        style("✓").green(),
+        fm.name,
+        fm.stack.language,
+        fm.project_type
+    );
+    print_framework_warning(fm);
+
+    let (matched, warnings) = matcher::match_components_verbose(fm);
+    for warning in &warnings {
+        println!("  {} {warning}", style("!").yellow());
+    }
+    println!(
+        "  {} skills, {} agents, {} commands matched",
+        matched.skills.len(),
+        matched.agents.len(),
+        matched.commands.len()
+    );
+
+    let missing = missing_components(fm, &matched);
+    for m in &missing {
+        println!(
+            "  {} {} not found in registry: {}{}",
+            style("✗").red(),
+            m.kind.label(),
+            m.name,
+            if m.explicit { " (listed in PRD)" } else { "" }
+        );
+    }
+
+    let explicit = missing.iter().filter(|m| m.explicit).count();
+    if explicit > 0 {
+        return Err(KaelError::Prd {
+            message: format!(
+                "{} missing from the registry",
+                plural(explicit, "explicit component")
+            ),
+        });
+    }
+    println!(
+        "\n{} {} is valid.",
+        style("✓").green().bold(),
+        prd_path.display()
+    );
+    Ok(())
+}
+
+/// PRD 파싱만 확인한다. 매칭과 레지스트리 조회는 하지 않는다. (`validate --prd-check-only`)
+///
+/// 에디터 연동용으로, 파싱 에러는 위치 정보가 담긴 그대로 돌려준다.
+pub fn check_only(from: Option<PathBuf>) -> Result<()> {
+    let prd_path = resolve_prd_path(from)?;
+    println!("{} {}", style("✓").green(), parse_check(&prd_path)?);
+    Ok(())
+}
+
+fn parse_check(path: &Path) -> Result<String> {
+    let fm = prd::parse_prd_file(path)?.frontmatter;
+    Ok(format!(
+        "{} parses: {} ({:?} / {:?})",
+        path.display(),
+        fm.name,
+        fm.stack.language,
+        fm.project_type
+    ))
+}
+
+/// 매칭 결과 중 레지스트리에서 찾을 수 없는 컴포넌트를 모은다.
+pub fn missing_components(prd: &Prd, matched: &MatchResult) -> Vec<Missing> {
+    let groups = [
+        (ComponentKind::Skill, &matched.skills, prd.skills.as_deref()),
+        (ComponentKind::Agent, &matched.agents, prd.agents.as_deref()),
+        (ComponentKind::Command, &matched.commands, None),
+    ];
+    let mut out = Vec::new();
+    for (kind, names, explicit) in groups {
+        for name in names {
+            if !registry::has_component(kind, name) {
+                out.push(Missing {
+                    kind,
+                    name: name.clone(),
+                    explicit: explicit.is_some_and(|list| list.contains(name)),
+                });
+            }
+        }
+    }
+    out
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {noun} is")
+    } else {
+        format!("{count} {noun}s are")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_unknown_skill_is_reported() {
+        let parsed = prd::parse_prd(
+            "---\nname: v\nstack:\n  language: rust\ntype: cli\nskills:\n  - rust/async-patterns\n  - rust/nope\n---\n",
+        )
+        .unwrap();
+        let matched = matcher::match_components_cached(&parsed.frontmatter, Default::default());
+        let missing = missing_components(&parsed.frontmatter, &matched);
+
+        assert_eq!(
+            missing,
+            vec![Missing {
+                kind: ComponentKind::Skill,
+                name: "rust/nope".into(),
+                explicit: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_check_reports_malformed_and_valid_prds() {
+        let dir = tempfile::tempdir().unwrap();
+        let broken = dir.path().join("broken.md");
+        std::fs::write(&broken, "---\nname: [unclosed\ntype: cli\n---\n").unwrap();
+        let err = parse_check(&broken).unwrap_err().to_string();
+        assert!(err.contains("line"), "{err}");
+
+        let valid = dir.path().join("PRD.md");
+        std::fs::write(
+            &valid,
+            "---\nname: ok\nstack:\n  language: go\ntype: api\n---\n",
+        )
+        .unwrap();
+        let started = std::time::Instant::now();
+        let summary = parse_check(&valid).unwrap();
+        assert!(summary.ends_with("parses: ok (Go / Api)"));
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+    }
+
+    #[test]
+    fn auto_matched_components_all_exist() {
+        let parsed =
+            prd::parse_prd("---\nname: v\nstack:\n  language: go\ntype: api\n---\n").unwrap();
+        let matched = matcher::match_components_cached(&parsed.frontmatter, Default::default());
+        assert!(missing_components(&parsed.frontmatter, &matched).is_empty());
+    }
+}
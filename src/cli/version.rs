@@ -0,0 +1,55 @@
+use std::sync::OnceLock;
+
+use crate::core::registry;
+use crate::error::Result;
+
+pub fn run(verbose: bool) -> Result<()> {
+    print!("{}", render(verbose));
+    Ok(())
+}
+
+/// `kael --version`에도 쓰는 한 줄 버전 (`0.1.0 (registry 2026.10.14)`)
+pub fn short() -> &'static str {
+    static SHORT: OnceLock<String> = OnceLock::new();
+    SHORT.get_or_init(|| {
+        format!(
+            "{} (registry {})",
+            env!("CARGO_PKG_VERSION"),
+            registry::version()
+        )
+    })
+}
+
+/// 버전 정보를 만든다. `verbose`면 레지스트리/빌드 출처도 함께 적는다.
+pub fn render(verbose: bool) -> String {
+    let mut out = format!("kael {}\n", short());
+    if !verbose {
+        return out;
+    }
+
+    let stats = registry::component_stats();
+    let hash = registry::registry_hash();
+    out.push_str(&format!("registry:   {}\n", &hash[..12]));
+    out.push_str(&format!(
+        "components: {} skills, {} agents, {} commands\n",
+        stats.skills, stats.agents, stats.commands
+    ));
+    out.push_str(&format!("commit:     {}\n", env!("KAEL_GIT_COMMIT")));
+    out.push_str(&format!("built:      {}\n", env!("KAEL_BUILD_DATE")));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verbose_includes_skill_count() {
+        let stats = registry::component_stats();
+        let out = render(true);
+        assert!(out.contains(&format!("{} skills", stats.skills)));
+        assert!(out.contains("commit:"));
+        assert_eq!(render(false).lines().count(), 1);
+        assert!(render(false).contains(&format!("(registry {})", registry::version())));
+    }
+}
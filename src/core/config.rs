@@ -0,0 +1,178 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::core::registry::RegistrySource;
+use crate::error::{KaelError, Result};
+
+/// kael 사용자 설정 (`kael.toml` / `config.toml`)
+///
+/// ```toml
+/// registry_source = "both"
+/// registry_paths = ["~/work/team-registry"]
+///
+/// [base]
+/// skills = ["_common/org-standards"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// `--registry-source`를 주지 않았을 때 쓸 레지스트리 출처
+    #[serde(default)]
+    pub registry_source: Option<RegistrySource>,
+    /// 모든 명령에서 PRD의 `registry_paths` 뒤에 덧붙일 레지스트리
+    /// (설정 파일 기준 상대 경로, 맨 앞의 `~`는 홈 디렉토리)
+    #[serde(default)]
+    pub registry_paths: Vec<PathBuf>,
+    /// 모든 PRD에 항상 들어가는 base 컴포넌트 조정
+    #[serde(default)]
+    pub base: BaseLists,
+}
+
+/// `[base]`: 지정한 목록을 기본 base 목록 뒤에 덧붙인다. `replace = true`면 대신 쓴다.
+///
+/// 지정하지 않은 목록은 `replace`여도 기본값을 유지한다.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BaseLists {
+    #[serde(default)]
+    pub replace: bool,
+    #[serde(default)]
+    pub skills: Option<Vec<String>>,
+    #[serde(default)]
+    pub agents: Option<Vec<String>>,
+    #[serde(default)]
+    pub commands: Option<Vec<String>>,
+}
+
+/// 프로젝트 루트의 설정 파일 이름
+pub const PROJECT_CONFIG: &str = "kael.toml";
+
+/// 기본 설정 파일 경로: `$XDG_CONFIG_HOME/kael/config.toml`, 없으면 `~/.config/kael/config.toml`
+pub fn default_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("kael").join("config.toml"))
+}
+
+/// 설정을 읽는다.
+///
+/// `explicit`(`--config`)로 지정한 파일은 반드시 있어야 한다. 지정하지 않으면
+/// `project_dir`의 `kael.toml`, 그다음 기본 경로 순으로 찾고, 둘 다 없으면 기본값을 쓴다.
+pub fn load(explicit: Option<&Path>, project_dir: &Path) -> Result<Config> {
+    match explicit {
+        Some(path) if !path.is_file() => Err(KaelError::Project {
+            message: format!("Config file not found: {}", path.display()),
+        }),
+        Some(path) => read(path),
+        None => {
+            let project = project_dir.join(PROJECT_CONFIG);
+            if project.is_file() {
+                return read(&project);
+            }
+            match default_path() {
+                Some(path) if path.is_file() => read(&path),
+                _ => Ok(Config::default()),
+            }
+        }
+    }
+}
+
+fn read(path: &Path) -> Result<Config> {
+    let content = std::fs::read_to_string(path)?;
+    let mut config: Config = toml::from_str(&content).map_err(|e| KaelError::Project {
+        message: format!("Invalid config file {}: {e}", path.display()),
+    })?;
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    config.registry_paths = config
+        .registry_paths
+        .into_iter()
+        .map(|p| dir.join(expand_home(&p, home.as_deref())))
+        .collect();
+    Ok(config)
+}
+
+/// `~`나 `~/...`를 `home` 아래 경로로 바꾼다. `home`을 모르거나 `~user` 형태면 그대로 둔다.
+fn expand_home(path: &Path, home: Option<&Path>) -> PathBuf {
+    let (Some(home), Ok(rest)) = (home, path.strip_prefix("~")) else {
+        return path.to_path_buf();
+    };
+    home.join(rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_config_values_take_effect() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("kael.toml");
+        std::fs::write(
+            &path,
+            "registry_source = \"embedded\"\nregistry_paths = [\"team-registry\"]\n",
+        )
+        .unwrap();
+
+        let config = load(Some(&path), dir.path()).unwrap();
+        assert_eq!(config.registry_source, Some(RegistrySource::Embedded));
+        assert_eq!(
+            config.registry_paths,
+            vec![dir.path().join("team-registry")]
+        );
+    }
+
+    #[test]
+    fn leading_tilde_expands_to_home() {
+        let home = Path::new("/home/dev");
+        assert_eq!(
+            expand_home(Path::new("~/work/team-registry"), Some(home)),
+            home.join("work/team-registry")
+        );
+        assert_eq!(expand_home(Path::new("~"), Some(home)), home);
+        // `~user`와 중간의 `~`는 건드리지 않는다
+        assert_eq!(
+            expand_home(Path::new("~other/reg"), Some(home)),
+            Path::new("~other/reg")
+        );
+        assert_eq!(
+            expand_home(Path::new("reg/~"), Some(home)),
+            Path::new("reg/~")
+        );
+        assert_eq!(expand_home(Path::new("~/reg"), None), Path::new("~/reg"));
+    }
+
+    #[test]
+    fn explicit_config_must_exist() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = load(Some(&dir.path().join("missing.toml")), dir.path()).unwrap_err();
+        assert!(err.to_string().contains("Config file not found"));
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("kael.toml");
+        std::fs::write(&path, "registy_source = \"embedded\"\n").unwrap();
+        let err = load(Some(&path), dir.path()).unwrap_err();
+        assert!(err.to_string().contains("Invalid config file"));
+    }
+
+    #[test]
+    fn project_kael_toml_is_found_without_flag() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(PROJECT_CONFIG),
+            "[base]\nreplace = true\nagents = [\"_base/reviewer\"]\n",
+        )
+        .unwrap();
+
+        let config = load(None, dir.path()).unwrap();
+        assert!(config.base.replace);
+        assert_eq!(config.base.agents, Some(vec!["_base/reviewer".to_string()]));
+        assert_eq!(config.base.skills, None);
+    }
+}
@@ -0,0 +1,317 @@
+use std::cmp::Reverse;
+use std::path::Path;
+
+use crate::core::prd::Language;
+use crate::error::{KaelError, Result};
+
+/// 감지 신호의 확신도
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confidence {
+    Low,
+    Medium,
+    High,
+}
+
+/// 언어별 감지 신호: 프로젝트 루트에 이 파일이 있으면 해당 언어로 본다.
+const SIGNALS: &[(&str, Language, Confidence)] = &[
+    ("Cargo.toml", Language::Rust, Confidence::High),
+    ("tsconfig.json", Language::Typescript, Confidence::High),
+    ("package.json", Language::Typescript, Confidence::Medium),
+    ("pyproject.toml", Language::Python, Confidence::High),
+    ("setup.py", Language::Python, Confidence::Medium),
+    ("requirements.txt", Language::Python, Confidence::Medium),
+    ("go.mod", Language::Go, Confidence::High),
+    ("pom.xml", Language::Java, Confidence::High),
+    ("build.gradle", Language::Java, Confidence::High),
+    ("build.gradle.kts", Language::Java, Confidence::Medium),
+    ("global.json", Language::Csharp, Confidence::Medium),
+    (
+        "Directory.Build.props",
+        Language::Csharp,
+        Confidence::Medium,
+    ),
+    ("Gemfile", Language::Ruby, Confidence::High),
+];
+
+/// 프로젝트 루트의 매니페스트 파일로 언어를 감지한다.
+///
+/// 신호가 여러 개인 언어는 가장 높은 확신도를 취하며, 결과는 확신도 내림차순이다.
+/// 하나를 고르지 않고 감지된 언어를 모두 돌려준다.
+pub fn detect_languages(base: &Path) -> Vec<(Language, Confidence)> {
+    let mut detected: Vec<(Language, Confidence)> = Vec::new();
+    for (file, language, confidence) in SIGNALS {
+        if !base.join(file).is_file() {
+            continue;
+        }
+        match detected.iter_mut().find(|(l, _)| l == language) {
+            Some((_, existing)) => *existing = (*existing).max(*confidence),
+            None => detected.push((language.clone(), *confidence)),
+        }
+    }
+    detected.sort_by_key(|(_, confidence)| Reverse(*confidence));
+    detected
+}
+
+/// 프레임워크 감지 신호: 언어의 매니페스트에 이 의존성이 있으면 `stack.framework`로 쓴다.
+///
+/// 의존성 이름은 정확히 같거나 `<이름>/`으로 시작하면(Go 모듈의 `/v4` 등) 일치로 본다.
+const FRAMEWORK_SIGNALS: &[(Language, &str, &str)] = &[
+    (Language::Rust, "axum", "axum"),
+    (Language::Rust, "actix-web", "actix"),
+    (Language::Typescript, "next", "nextjs"),
+    (Language::Typescript, "@remix-run/react", "remix"),
+    (Language::Python, "fastapi", "fastapi"),
+    (Language::Python, "django", "django"),
+    (Language::Go, "github.com/gin-gonic/gin", "gin"),
+    (Language::Go, "github.com/labstack/echo", "echo"),
+    (Language::Go, "github.com/gofiber/fiber", "fiber"),
+    (Language::Ruby, "rails", "rails"),
+    (Language::Ruby, "sinatra", "sinatra"),
+];
+
+/// `base`의 매니페스트 의존성으로 `language`의 프레임워크를 추측한다.
+///
+/// 최선 노력(best-effort)이라 파일이 없거나 파싱에 실패하면 `None`이다.
+/// 여러 개가 맞으면 `FRAMEWORK_SIGNALS` 순서에서 먼저 나온 것을 쓴다.
+pub fn sniff_framework(base: &Path, language: &Language) -> Option<String> {
+    let dependencies = dependency_names(base, language);
+    FRAMEWORK_SIGNALS
+        .iter()
+        .filter(|(l, _, _)| l == language)
+        .find(|(_, dependency, _)| {
+            dependencies.iter().any(|name| {
+                name == dependency
+                    || name
+                        .strip_prefix(dependency)
+                        .is_some_and(|rest| rest.starts_with('/'))
+            })
+        })
+        .map(|(_, _, framework)| framework.to_string())
+}
+
+/// 언어별 매니페스트에 선언된 의존성 이름 (소문자)
+fn dependency_names(base: &Path, language: &Language) -> Vec<String> {
+    let read = |file: &str| std::fs::read_to_string(base.join(file)).ok();
+    let mut names: Vec<String> = match language {
+        Language::Rust => read("Cargo.toml")
+            .and_then(|s| s.parse::<toml::Table>().ok())
+            .map(|manifest| table_keys(&manifest, &["dependencies"]))
+            .unwrap_or_default(),
+        Language::Typescript => read("package.json")
+            .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+            .map(|manifest| {
+                ["dependencies", "devDependencies"]
+                    .iter()
+                    .filter_map(|key| manifest.get(key)?.as_object())
+                    .flat_map(|deps| deps.keys().cloned())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        Language::Python => {
+            let mut names = Vec::new();
+            if let Some(manifest) =
+                read("pyproject.toml").and_then(|s| s.parse::<toml::Table>().ok())
+            {
+                // PEP 621 `project.dependencies`와 Poetry `tool.poetry.dependencies`
+                let specs = manifest
+                    .get("project")
+                    .and_then(|p| p.get("dependencies"))
+                    .and_then(|d| d.as_array());
+                names.extend(
+                    specs
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|spec| spec.as_str())
+                        .map(requirement_name),
+                );
+                names.extend(table_keys(&manifest, &["tool", "poetry", "dependencies"]));
+            }
+            names.extend(
+                read("requirements.txt")
+                    .unwrap_or_default()
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with(['#', '-']))
+                    .map(requirement_name),
+            );
+            names
+        }
+        Language::Go => read("go.mod")
+            .unwrap_or_default()
+            .lines()
+            .map(|line| line.trim().trim_start_matches("require").trim())
+            .filter_map(|line| line.split_whitespace().next())
+            .map(str::to_string)
+            .collect(),
+        Language::Ruby => read("Gemfile")
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| {
+                let rest = line.trim().strip_prefix("gem ")?;
+                let quoted = rest.trim_start().strip_prefix(['"', '\''])?;
+                quoted.split(['"', '\'']).next().map(str::to_string)
+            })
+            .collect(),
+        Language::Java | Language::Csharp => Vec::new(),
+    };
+    for name in &mut names {
+        name.make_ascii_lowercase();
+    }
+    names
+}
+
+/// `path`를 따라 내려간 TOML 테이블의 키 목록
+fn table_keys(table: &toml::Table, path: &[&str]) -> Vec<String> {
+    let mut current = table;
+    for key in path {
+        match current.get(*key).and_then(|v| v.as_table()) {
+            Some(next) => current = next,
+            None => return Vec::new(),
+        }
+    }
+    current.keys().cloned().collect()
+}
+
+/// `fastapi[all]>=0.110` 같은 Python 요구 사항에서 패키지 이름만
+fn requirement_name(spec: &str) -> String {
+    spec.split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// 감지 결과에서 언어 하나를 고른다.
+///
+/// `choice`(예: `--language`)가 있으면 그것을, 감지된 언어가 하나뿐이면 그것을 쓴다.
+/// 여러 언어가 감지되었는데 선택이 없으면 임의로 고르지 않고 에러를 낸다.
+pub fn resolve_language(
+    detected: &[(Language, Confidence)],
+    choice: Option<Language>,
+) -> Result<Option<Language>> {
+    if choice.is_some() {
+        return Ok(choice);
+    }
+    match detected {
+        [] => Ok(None),
+        [(language, _)] => Ok(Some(language.clone())),
+        _ => Err(KaelError::Project {
+            message: format!(
+                "Multiple languages detected: {}. Use --language to choose.",
+                describe(detected)
+            ),
+        }),
+    }
+}
+
+/// `rust (high), typescript (medium)` 형태로 감지 결과를 나열한다.
+pub fn describe(detected: &[(Language, Confidence)]) -> String {
+    detected
+        .iter()
+        .map(|(language, confidence)| format!("{language:?} ({confidence:?})").to_lowercase())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polyglot_detects_both_languages() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]").unwrap();
+        std::fs::write(dir.path().join("package.json"), "{}").unwrap();
+
+        let detected = detect_languages(dir.path());
+        assert_eq!(
+            detected,
+            vec![
+                (Language::Rust, Confidence::High),
+                (Language::Typescript, Confidence::Medium),
+            ]
+        );
+
+        let err = resolve_language(&detected, None).unwrap_err();
+        assert!(err.to_string().contains("rust (high), typescript (medium)"));
+        assert_eq!(
+            resolve_language(&detected, Some(Language::Typescript)).unwrap(),
+            Some(Language::Typescript)
+        );
+    }
+
+    #[test]
+    fn framework_is_sniffed_from_manifests() {
+        let cases: &[(Language, &str, &str, Option<&str>)] = &[
+            (
+                Language::Rust,
+                "Cargo.toml",
+                "[package]\nname = \"x\"\n\n[dependencies]\nserde = \"1\"\naxum = { version = \"0.7\" }\n",
+                Some("axum"),
+            ),
+            (
+                Language::Typescript,
+                "package.json",
+                r#"{"dependencies": {"react": "19", "next": "15.0.0"}}"#,
+                Some("nextjs"),
+            ),
+            (
+                Language::Python,
+                "pyproject.toml",
+                "[project]\nname = \"x\"\ndependencies = [\"FastAPI[all]>=0.110\", \"uvicorn\"]\n",
+                Some("fastapi"),
+            ),
+            (
+                Language::Python,
+                "requirements.txt",
+                "# web\nDjango==5.0\n",
+                Some("django"),
+            ),
+            (
+                Language::Go,
+                "go.mod",
+                "module x\n\nrequire (\n\tgithub.com/labstack/echo/v4 v4.12.0\n)\n",
+                Some("echo"),
+            ),
+            (
+                Language::Ruby,
+                "Gemfile",
+                "source \"https://rubygems.org\"\ngem 'rails', '~> 7.1'\n",
+                Some("rails"),
+            ),
+            // 알려진 프레임워크가 없거나 파싱할 수 없으면 추측하지 않는다
+            (
+                Language::Rust,
+                "Cargo.toml",
+                "[dependencies]\nclap = \"4\"\n",
+                None,
+            ),
+            (Language::Typescript, "package.json", "{ not json", None),
+        ];
+        for (language, file, content, expected) in cases {
+            let dir = tempfile::tempdir().unwrap();
+            std::fs::write(dir.path().join(file), content).unwrap();
+            assert_eq!(
+                sniff_framework(dir.path(), language).as_deref(),
+                *expected,
+                "{file}: {content}"
+            );
+        }
+
+        let empty = tempfile::tempdir().unwrap();
+        assert_eq!(sniff_framework(empty.path(), &Language::Go), None);
+    }
+
+    #[test]
+    fn strongest_signal_wins_per_language() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("package.json"), "{}").unwrap();
+        std::fs::write(dir.path().join("tsconfig.json"), "{}").unwrap();
+
+        let detected = detect_languages(dir.path());
+        assert_eq!(detected, vec![(Language::Typescript, Confidence::High)]);
+        assert_eq!(
+            resolve_language(&detected, None).unwrap(),
+            Some(Language::Typescript)
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use std::path::{Path, PathBuf};
+
+use similar::{ChangeTag, TextDiff};
+
+use crate::core::generator::GeneratedOutput;
+use crate::core::project::{self, LineEnding};
+
+/// 두 텍스트 사이의 줄 단위 변경량
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LineStats {
+    pub added: usize,
+    pub removed: usize,
+}
+
+/// `before` → `after`로 바뀔 때 추가/삭제된 줄 수를 센다.
+pub fn line_stats(before: &str, after: &str) -> LineStats {
+    let diff = TextDiff::from_lines(before, after);
+    let mut stats = LineStats::default();
+    for change in diff.iter_all_changes() {
+        match change.tag() {
+            ChangeTag::Insert => stats.added += 1,
+            ChangeTag::Delete => stats.removed += 1,
+            ChangeTag::Equal => {}
+        }
+    }
+    stats
+}
+
+/// 변경된 파일 한 줄 요약을 만든다. (예: `~ CLAUDE.md (+12 -3)`)
+pub fn summary_line(path: &str, stats: LineStats) -> String {
+    format!("~ {path} (+{} -{})", stats.added, stats.removed)
+}
+
+/// 재생성 결과와 디스크 파일의 차이 한 건
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileChange {
+    /// 디스크에 없어 새로 생길 파일
+    Added,
+    /// `--force`로 다시 생성하면 지워질 레지스트리 컴포넌트
+    Removed,
+    /// 내용이 달라질 파일. 디스크 → 생성 결과 unified diff를 담는다.
+    Changed(String),
+}
+
+/// 생성 결과를 `base`의 파일과 비교한다. 같은 파일은 빠지며 아무것도 기록하지 않는다.
+///
+/// 경로는 프로젝트 루트 기준이다. `.claude/`가 없으면 모든 파일이 `Added`다.
+pub fn compare_to_disk(base: &Path, output: &GeneratedOutput) -> Vec<(PathBuf, FileChange)> {
+    let mut changes = Vec::new();
+    for (relative, content) in output.files() {
+        // kael이 실제로 기록할 내용과 비교한다
+        let content = &project::normalize(content, LineEnding::default());
+        match std::fs::read_to_string(base.join(&relative)) {
+            Err(_) => changes.push((relative, FileChange::Added)),
+            Ok(existing) if existing != *content => {
+                let diff = unified(&relative.to_string_lossy(), &existing, content);
+                changes.push((relative, FileChange::Changed(diff)));
+            }
+            Ok(_) => {}
+        }
+    }
+    for path in project::stale_components(base, output) {
+        let relative = path.strip_prefix(base).unwrap_or(&path).to_path_buf();
+        changes.push((relative, FileChange::Removed));
+    }
+    changes
+}
+
+/// `a/<path>` → `b/<path>` 헤더의 unified diff
+pub fn unified(path: &str, before: &str, after: &str) -> String {
+    TextDiff::from_lines(before, after)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{path}"), &format!("b/{path}"))
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_added_and_removed_lines() {
+        let before = "# Title\nold line\nkept\n";
+        let after = "# Title\nnew line\nkept\nextra\n";
+        let stats = line_stats(before, after);
+
+        assert_eq!(
+            stats,
+            LineStats {
+                added: 2,
+                removed: 1
+            }
+        );
+    }
+
+    #[test]
+    fn identical_text_has_no_changes() {
+        let stats = line_stats("a\nb\n", "a\nb\n");
+        assert_eq!(stats, LineStats::default());
+    }
+
+    #[test]
+    fn summary_line_format() {
+        let stats = line_stats("a\nb\nc\n", "a\nx\ny\nz\n");
+        assert_eq!(summary_line("CLAUDE.md", stats), "~ CLAUDE.md (+3 -2)");
+    }
+}
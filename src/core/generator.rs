@@ -1,17 +1,28 @@
-use crate::core::matcher::{self, MatchResult};
-use crate::core::prd::Prd;
+use std::path::PathBuf;
+
+use crate::core::matcher::{self, ComponentOverrides, MatchResult, MatchStages};
+use crate::core::prd::{ParsedPrd, Prd, PrdBody};
 use crate::core::registry::{self, ComponentKind};
-use crate::error::Result;
-use crate::template::engine;
+use crate::core::timings::{Phase, Timings};
+use crate::core::validate;
+use crate::error::{KaelError, Result};
+use crate::template::engine::{self, SettingsFormat};
 
 /// 생성된 모든 파일 내용을 담는 구조체
 #[derive(Debug)]
 pub struct GeneratedOutput {
     pub claude_md: String,
-    pub settings_json: String,
+    /// `--minimal`이면 `None`이며 settings.json을 기록하지 않는다
+    pub settings_json: Option<String>,
+    /// settings.json 옆에 추가로 기록하는 파일 (`--env`의 `settings.<env>.json`, `--with-local`의 `settings.local.json`)
+    pub env_settings: Vec<GeneratedFile>,
     pub skills: Vec<GeneratedFile>,
     pub agents: Vec<GeneratedFile>,
     pub commands: Vec<GeneratedFile>,
+    /// 매칭됐지만 레지스트리에서 찾지 못해 빠진 컴포넌트 이름
+    pub missing: Vec<String>,
+    /// PRD마다 나온 매칭 경고 (`matcher::prd_warnings`)
+    pub prd_warnings: Vec<String>,
 }
 
 #[derive(Debug)]
@@ -21,63 +32,279 @@ pub struct GeneratedFile {
     pub content: String,
 }
 
+impl GeneratedOutput {
+    /// 기록할 모든 파일을 프로젝트 루트 기준 상대 경로와 함께 순서대로 반환한다.
+    ///
+    /// CLAUDE.md → `.claude/settings.json` → `settings.<env>.json` → skills → agents → commands 순이다.
+    pub fn files(&self) -> Vec<(PathBuf, &str)> {
+        let claude_dir = PathBuf::from(".claude");
+        let mut files = vec![(PathBuf::from("CLAUDE.md"), self.claude_md.as_str())];
+        if let Some(settings_json) = &self.settings_json {
+            files.push((claude_dir.join("settings.json"), settings_json.as_str()));
+        }
+        for file in self
+            .env_settings
+            .iter()
+            .chain(&self.skills)
+            .chain(&self.agents)
+            .chain(&self.commands)
+        {
+            files.push((claude_dir.join(&file.relative_path), file.content.as_str()));
+        }
+        files
+    }
+
+    /// 생성은 됐지만 사용자가 알아야 할 문제 (`generate --fail-on-warning`의 판단 기준)
+    ///
+    /// 빠진 컴포넌트, 그다음 `prd_warnings` 순이다.
+    pub fn warnings(&self) -> Vec<String> {
+        self.missing
+            .iter()
+            .map(|name| format!("'{name}' not found in registry, skipped"))
+            .chain(self.prd_warnings.iter().cloned())
+            .collect()
+    }
+}
+
 /// PRD frontmatter로부터 전체 `.claude/` 구성을 생성한다.
 pub fn generate(prd: &Prd) -> Result<GeneratedOutput> {
-    let matched = matcher::match_components(prd);
+    let matched = matcher::match_components_cached(prd, MatchStages::default()).sorted();
     generate_with_match(prd, &matched)
 }
 
-/// PRD와 명시적 MatchResult로부터 생성한다. (테스트용)
-pub fn generate_with_match(prd: &Prd, matched: &MatchResult) -> Result<GeneratedOutput> {
-    let claude_md = engine::render_claude_md(prd, matched)?;
-    let settings_json = engine::render_settings_json(prd, matched)?;
+/// 파싱된 PRD(frontmatter + 본문)로부터 생성한다. 본문 섹션이 CLAUDE.md에 들어간다.
+pub fn generate_parsed(parsed: &ParsedPrd) -> Result<GeneratedOutput> {
+    generate_timed(
+        parsed,
+        MatchStages::default(),
+        SettingsFormat::default(),
+        &mut Timings::default(),
+    )
+}
 
-    let skills = matched
-        .skills
-        .iter()
-        .filter_map(|name| {
-            registry::get_component(ComponentKind::Skill, name)
-                .ok()
-                .map(|content| GeneratedFile {
-                    relative_path: format!("skills/{name}/SKILL.md"),
-                    content: content.to_string(),
-                })
-        })
-        .collect();
+/// `stages`에 따라 매칭하고, match/render/resolve 단계별 소요 시간을 `timings`에 기록한다.
+///
+/// settings.json은 `format`으로 직렬화한다.
+pub fn generate_timed(
+    parsed: &ParsedPrd,
+    stages: MatchStages,
+    format: SettingsFormat,
+    timings: &mut Timings,
+) -> Result<GeneratedOutput> {
+    generate_single(
+        parsed,
+        stages,
+        format,
+        &ComponentOverrides::default(),
+        timings,
+    )
+}
 
-    let agents = matched
-        .agents
-        .iter()
-        .filter_map(|name| {
-            registry::get_component(ComponentKind::Agent, name)
-                .ok()
-                .map(|content| GeneratedFile {
-                    relative_path: format!("agents/{name}.md"),
-                    content: content.to_string(),
-                })
-        })
-        .collect();
+fn generate_single(
+    parsed: &ParsedPrd,
+    stages: MatchStages,
+    format: SettingsFormat,
+    overrides: &ComponentOverrides,
+    timings: &mut Timings,
+) -> Result<GeneratedOutput> {
+    let prd = &parsed.frontmatter;
+    let (matched, unknown) = timings.time(Phase::Match, || {
+        let (matched, unknown) = matcher::match_components_cached(prd, stages)
+            .apply_overrides(&overrides.add, &overrides.remove);
+        (matched.sorted(), unknown)
+    });
+    let (claude_md, settings_json) = timings.time(Phase::Render, || {
+        render(prd, &parsed.body, &matched, format)
+    })?;
+    let mut output = timings.time(Phase::Resolve, || {
+        resolve_components(claude_md, Some(settings_json), &matched)
+    });
+    validate::ensure_consistent(&matched, &output)?;
+    output.missing.extend(unknown);
+    output.prd_warnings = matcher::prd_warnings(prd, stages);
+    Ok(output)
+}
+
+/// 여러 PRD의 매칭 결과를 합쳐 하나의 구성을 생성한다. (`--from`을 여러 번 지정)
+///
+/// CLAUDE.md 머리말·본문 섹션과 settings.json은 첫 PRD를 따르고,
+/// CLAUDE.md에는 모든 프로젝트 이름을 나열한다. PRD가 하나면 `generate_timed`와 같다.
+pub fn generate_merged(
+    parsed: &[ParsedPrd],
+    stages: MatchStages,
+    format: SettingsFormat,
+    timings: &mut Timings,
+) -> Result<GeneratedOutput> {
+    generate_with_overrides(
+        parsed,
+        stages,
+        format,
+        &ComponentOverrides::default(),
+        timings,
+    )
+}
+
+/// `generate_merged`와 같되, 매칭 결과에 `overrides`를 적용한 뒤 생성한다.
+///
+/// 레지스트리에 없어 더하지 못한 컴포넌트는 `missing`에 들어간다.
+pub fn generate_with_overrides(
+    parsed: &[ParsedPrd],
+    stages: MatchStages,
+    format: SettingsFormat,
+    overrides: &ComponentOverrides,
+    timings: &mut Timings,
+) -> Result<GeneratedOutput> {
+    let Some((primary, rest)) = parsed.split_first() else {
+        return Err(KaelError::Prd {
+            message: "No PRD given".into(),
+        });
+    };
+    if rest.is_empty() {
+        return generate_single(primary, stages, format, overrides, timings);
+    }
 
-    let commands = matched
-        .commands
+    let prds: Vec<&Prd> = parsed.iter().map(|p| &p.frontmatter).collect();
+    let (matched, unknown) = timings.time(Phase::Match, || {
+        let results: Vec<MatchResult> = prds
+            .iter()
+            .map(|prd| matcher::match_components_cached(prd, stages))
+            .collect();
+        let (matched, unknown) =
+            matcher::merge(&results).apply_overrides(&overrides.add, &overrides.remove);
+        (matched.sorted(), unknown)
+    });
+    let (claude_md, settings_json) = timings.time(Phase::Render, || -> Result<_> {
+        let claude_md = engine::render_claude_md_for_projects(
+            &primary.frontmatter,
+            &primary.body,
+            &matched,
+            &prds,
+        )?;
+        let settings_json = engine::render_settings_json(&primary.frontmatter, &matched, format)?;
+        Ok((claude_md, settings_json))
+    })?;
+    let mut output = timings.time(Phase::Resolve, || {
+        resolve_components(claude_md, Some(settings_json), &matched)
+    });
+    validate::ensure_consistent(&matched, &output)?;
+    output.missing.extend(unknown);
+    output.prd_warnings = prds
         .iter()
-        .filter_map(|name| {
-            registry::get_component(ComponentKind::Command, name)
-                .ok()
-                .map(|content| GeneratedFile {
-                    relative_path: format!("commands/{name}.md"),
-                    content: content.to_string(),
-                })
-        })
+        .flat_map(|prd| matcher::prd_warnings(prd, stages))
         .collect();
+    Ok(output)
+}
+
+/// CLAUDE.md와 기본 commands(`init`, `review`, `commit`)만 생성한다. (`init --minimal`)
+///
+/// skills, agents, settings.json은 만들지 않는다.
+pub fn generate_minimal(parsed: &ParsedPrd) -> Result<GeneratedOutput> {
+    let prd = &parsed.frontmatter;
+    let matched = MatchResult {
+        skills: Vec::new(),
+        agents: Vec::new(),
+        commands: matcher::configured_base().commands,
+    }
+    .sorted();
+    let claude_md = engine::render_claude_md_with_body(prd, &parsed.body, &matched)?;
+    let mut output = resolve_components(claude_md, None, &matched);
+    validate::ensure_consistent(&matched, &output)?;
+    // 매칭을 하지 않으므로 framework 경고만 남는다
+    output.prd_warnings.extend(matcher::framework_warning(prd));
+    Ok(output)
+}
+
+/// `settings.<env>.json`을 생성 결과에 추가한다. (`generate --env`)
+pub fn add_env_settings(
+    output: &mut GeneratedOutput,
+    prd: &Prd,
+    stages: MatchStages,
+    env: &str,
+) -> Result<()> {
+    if env.is_empty()
+        || !env
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err(KaelError::Prd {
+            message: format!("Invalid env name '{env}': use lowercase letters, digits, and '-'"),
+        });
+    }
+    let matched = matcher::match_components_cached(prd, stages).sorted();
+    output.env_settings.push(GeneratedFile {
+        relative_path: format!("settings.{env}.json"),
+        content: engine::render_env_settings(prd, &matched, env)?,
+    });
+    Ok(())
+}
+
+/// 개발자별 덮어쓰기용 `settings.local.json` 뼈대를 생성 결과에 추가한다. (`init --with-local`)
+pub fn add_local_settings(
+    output: &mut GeneratedOutput,
+    prd: &Prd,
+    stages: MatchStages,
+) -> Result<()> {
+    let matched = matcher::match_components_cached(prd, stages).sorted();
+    output.env_settings.push(GeneratedFile {
+        relative_path: "settings.local.json".into(),
+        content: engine::render_settings_local_json(prd, &matched)?,
+    });
+    Ok(())
+}
+
+/// PRD와 명시적 MatchResult로부터 생성한다. (테스트용)
+pub fn generate_with_match(prd: &Prd, matched: &MatchResult) -> Result<GeneratedOutput> {
+    let (claude_md, settings_json) =
+        render(prd, &PrdBody::default(), matched, SettingsFormat::default())?;
+    Ok(resolve_components(claude_md, Some(settings_json), matched))
+}
 
-    Ok(GeneratedOutput {
+fn render(
+    prd: &Prd,
+    body: &PrdBody,
+    matched: &MatchResult,
+    format: SettingsFormat,
+) -> Result<(String, String)> {
+    let claude_md = engine::render_claude_md_with_body(prd, body, matched)?;
+    let settings_json = engine::render_settings_json(prd, matched, format)?;
+    Ok((claude_md, settings_json))
+}
+
+fn resolve_components(
+    claude_md: String,
+    settings_json: Option<String>,
+    matched: &MatchResult,
+) -> GeneratedOutput {
+    let mut missing = Vec::new();
+    let mut resolve = |kind: ComponentKind, names: &[String]| -> Vec<GeneratedFile> {
+        names
+            .iter()
+            .filter_map(|name| match registry::get_installable(kind, name) {
+                Ok(content) => Some(GeneratedFile {
+                    relative_path: kind.installed_path(name),
+                    content,
+                }),
+                Err(_) => {
+                    missing.push(name.clone());
+                    None
+                }
+            })
+            .collect()
+    };
+    let skills = resolve(ComponentKind::Skill, &matched.skills);
+    let agents = resolve(ComponentKind::Agent, &matched.agents);
+    let commands = resolve(ComponentKind::Command, &matched.commands);
+
+    GeneratedOutput {
         claude_md,
         settings_json,
+        env_settings: Vec::new(),
         skills,
         agents,
         commands,
-    })
+        missing,
+        prd_warnings: Vec::new(),
+    }
 }
 
 #[cfg(test)]
@@ -85,13 +312,156 @@ mod tests {
     use super::*;
     use crate::core::prd::{Language, ProjectType, Stack};
 
+    #[test]
+    fn agent_without_frontmatter_gets_a_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let agents = dir.path().join("agents/acme");
+        std::fs::create_dir_all(&agents).unwrap();
+        std::fs::write(agents.join("oncall.md"), "# Oncall\n\nPage people.\n").unwrap();
+        registry::set_overlays(vec![dir.path().to_path_buf()]);
+
+        let prd =
+            crate::core::prd::parse_prd("---\nname: o\nstack:\n  language: go\ntype: api\n---\n")
+                .unwrap()
+                .frontmatter;
+        let matched = MatchResult {
+            agents: vec!["acme/oncall".into()],
+            ..MatchResult::default()
+        };
+        let output = generate_with_match(&prd, &matched).unwrap();
+        registry::set_overlays(Vec::new());
+
+        assert_eq!(output.agents[0].relative_path, "agents/acme/oncall.md");
+        assert!(
+            output.agents[0]
+                .content
+                .starts_with("---\nname: oncall\n---\n\n# Oncall\n"),
+            "{}",
+            output.agents[0].content
+        );
+    }
+
+    #[test]
+    fn frontmatter_only_prd_renders_cleanly() {
+        let parsed = crate::core::prd::parse_prd(
+            "---\nname: \"minimal\"\nstack:\n  language: python\ntype: api\n---\n",
+        )
+        .unwrap();
+        assert!(parsed.body.sections.is_empty());
+        let output = generate_parsed(&parsed).unwrap();
+
+        for (path, content) in output.files().into_iter().take(2) {
+            assert!(!content.contains("None"), "{}", path.display());
+            assert!(!content.contains("null"), "{}", path.display());
+        }
+        assert!(output.claude_md.starts_with("# minimal\n\n## Quick Facts"));
+        assert!(!output.claude_md.contains("Project Notes"));
+    }
+
+    #[test]
+    fn merged_output_lists_every_project() {
+        let rust = crate::core::prd::parse_prd(
+            "---\nname: tool\nstack:\n  language: rust\ntype: cli\n---\n",
+        )
+        .unwrap();
+        let python = crate::core::prd::parse_prd(
+            "---\nname: api\nstack:\n  language: python\ntype: api\n---\n",
+        )
+        .unwrap();
+        let output = generate_merged(
+            &[rust.clone(), python],
+            MatchStages::default(),
+            SettingsFormat::default(),
+            &mut Timings::default(),
+        )
+        .unwrap();
+
+        assert!(output.claude_md.starts_with("# tool\n"));
+        assert!(output.claude_md.contains(
+            "## Projects\n\n- **tool** — rust / cli\n- **api** — python / api\n\n## Commands"
+        ));
+        let paths: Vec<String> = output
+            .files()
+            .into_iter()
+            .map(|(path, _)| path.to_string_lossy().into_owned())
+            .collect();
+        assert!(paths.contains(&".claude/skills/rust/error-handling/SKILL.md".to_string()));
+        assert!(paths.contains(&".claude/skills/python/fastapi/SKILL.md".to_string()));
+
+        // PRD 하나면 프로젝트 목록이 없다
+        let single = generate_parsed(&rust).unwrap();
+        assert!(!single.claude_md.contains("## Projects"));
+    }
+
+    #[test]
+    fn minimal_output_has_only_claude_md_and_base_commands() {
+        let parsed =
+            crate::core::prd::parse_prd("---\nname: m\nstack:\n  language: rust\ntype: cli\n---\n")
+                .unwrap();
+        let output = generate_minimal(&parsed).unwrap();
+
+        assert!(output.skills.is_empty());
+        assert!(output.agents.is_empty());
+        assert!(!output.commands.is_empty());
+        assert!(output.settings_json.is_none());
+
+        // 빈 skills/agents 디렉토리는 만들지 않는다
+        let dir = tempfile::tempdir().unwrap();
+        crate::core::project::write_output(dir.path(), &output, false).unwrap();
+        let claude = dir.path().join(".claude");
+        assert!(claude.join("commands/commit.md").is_file());
+        assert!(!claude.join("skills").exists());
+        assert!(!claude.join("agents").exists());
+        assert!(!claude.join("settings.json").exists());
+    }
+
+    #[test]
+    fn env_settings_layer_over_base() {
+        let parsed = crate::core::prd::parse_prd(
+            "---\nname: e\nstack:\n  language: rust\ntype: cli\nenvs:\n  ci:\n    agents:\n      defaultModel: haiku\n---\n",
+        )
+        .unwrap();
+        let prd = &parsed.frontmatter;
+        let mut output = generate_parsed(&parsed).unwrap();
+        add_env_settings(&mut output, prd, MatchStages::default(), "ci").unwrap();
+
+        let (path, content) = output.files().into_iter().nth(2).unwrap();
+        assert_eq!(path, PathBuf::from(".claude/settings.ci.json"));
+        let ci: serde_json::Value = serde_json::from_str(content).unwrap();
+        let base: serde_json::Value =
+            serde_json::from_str(output.settings_json.as_deref().unwrap()).unwrap();
+        assert_ne!(ci, base);
+        // PRD 덮어쓰기 → ci 템플릿 → 기본값 순으로 적용
+        assert_eq!(ci["agents"]["defaultModel"], "haiku");
+        assert_eq!(ci["agents"]["maxConcurrentAgents"], 1);
+        assert_eq!(
+            ci["agents"]["heavyTaskModel"],
+            base["agents"]["heavyTaskModel"]
+        );
+
+        let err = add_env_settings(&mut output, prd, MatchStages::default(), "staging");
+        assert!(err.unwrap_err().to_string().contains("envs.staging"));
+    }
+
+    #[test]
+    fn unknown_explicit_skill_is_reported_missing() {
+        let parsed = crate::core::prd::parse_prd(
+            "---\nname: m\nstack:\n  language: rust\ntype: cli\nskills:\n  - custom/does-not-exist\n  - rust/ffi\n---\n",
+        )
+        .unwrap();
+        let output = generate_parsed(&parsed).unwrap();
+
+        assert_eq!(output.missing, vec!["custom/does-not-exist".to_string()]);
+        assert_eq!(output.skills.len(), 1);
+    }
+
     #[test]
     fn generate_rust_cli() {
         let prd = Prd {
             name: "my-cli".into(),
             description: Some("A Rust CLI tool".into()),
             stack: Stack {
-                language: Language::Rust,
+                language: Language::Rust.into(),
                 framework: None,
                 database: None,
                 infra: None,
@@ -102,6 +472,9 @@ mod tests {
             agents: None,
             skills: None,
             mcp: None,
+            sections: None,
+            registry_paths: None,
+            envs: None,
             team: None,
         };
 
@@ -120,13 +493,44 @@ mod tests {
             .any(|s| s.relative_path.starts_with("skills/")));
     }
 
+    #[test]
+    fn prd_registry_path_shadows_embedded_skill() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill = dir.path().join(".kael-registry/skills/rust/async-patterns");
+        std::fs::create_dir_all(&skill).unwrap();
+        std::fs::write(skill.join("SKILL.md"), "# Team async rules").unwrap();
+        let prd_path = dir.path().join("PRD.md");
+        std::fs::write(
+            &prd_path,
+            "---\nname: local\nstack:\n  language: rust\ntype: cli\nregistry_paths: [./.kael-registry]\n---\n",
+        )
+        .unwrap();
+
+        let parsed = crate::core::prd::parse_prd_file(&prd_path).unwrap();
+        registry::set_overlays(parsed.frontmatter.resolved_registry_paths(&prd_path));
+        let output = generate_parsed(&parsed).unwrap();
+        registry::set_overlays(Vec::new());
+
+        let async_skill = output
+            .skills
+            .iter()
+            .find(|s| s.relative_path == "skills/rust/async-patterns/SKILL.md")
+            .unwrap();
+        assert_eq!(async_skill.content, "# Team async rules");
+        // overlay에 없는 스킬은 임베디드 내용 그대로
+        assert!(output
+            .skills
+            .iter()
+            .any(|s| s.relative_path == "skills/rust/error-handling/SKILL.md"));
+    }
+
     #[test]
     fn generate_settings_json_valid() {
         let prd = Prd {
             name: "test".into(),
             description: None,
             stack: Stack {
-                language: Language::Python,
+                language: Language::Python.into(),
                 framework: Some("fastapi".into()),
                 database: None,
                 infra: None,
@@ -137,11 +541,15 @@ mod tests {
             agents: None,
             skills: None,
             mcp: Some(vec!["github".into()]),
+            sections: None,
+            registry_paths: None,
+            envs: None,
             team: None,
         };
 
         let output = generate(&prd).unwrap();
-        let parsed: serde_json::Value = serde_json::from_str(&output.settings_json).unwrap();
+        let parsed: serde_json::Value =
+            serde_json::from_str(output.settings_json.as_deref().unwrap()).unwrap();
         assert_eq!(parsed["project"]["name"], "test");
         assert_eq!(parsed["project"]["language"], "python");
     }
@@ -0,0 +1,479 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::core::generator::GeneratedOutput;
+use crate::core::project::{self, LineEnding, WriteStatus};
+use crate::core::registry::{self, ComponentKind};
+use crate::error::{KaelError, Result};
+
+/// 프로젝트 루트 기준 manifest 경로
+pub const MANIFEST_PATH: &str = ".claude/.kael-manifest.json";
+
+/// kael이 설치한 파일 목록. `remove`/`doctor`가 디렉토리 내용을 추측하지 않도록 한다.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// manifest를 기록한 kael 버전
+    pub version: String,
+    /// manifest를 기록한 임베디드 레지스트리 버전. 버전 도입 전 manifest에는 없다.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry_version: Option<String>,
+    /// 기록 시각 (Unix epoch 초). `SOURCE_DATE_EPOCH`가 있으면 그 값을 쓴다.
+    #[serde(default)]
+    pub generated_at: u64,
+    pub files: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// 프로젝트 루트 기준 상대 경로 (`/` 구분자)
+    pub path: String,
+    /// 기록 시점 내용의 SHA-256 (hex)
+    pub sha256: String,
+    /// 사용자가 작성한 파일 (`add command --new` 등). 재생성 시 덮어쓰거나 지우지 않는다.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub user_authored: bool,
+}
+
+/// 생성 결과에 해당하는 파일들이 디스크에 모두 있는지 확인하고,
+/// 디스크의 현재 내용 해시로 manifest를 만든다.
+///
+/// 빠진 파일이 있으면 목록과 함께 에러를 낸다.
+pub fn from_disk(base: &Path, output: &GeneratedOutput) -> Result<Manifest> {
+    let mut files = Vec::new();
+    let mut missing = Vec::new();
+
+    for (relative, _) in output.files() {
+        match std::fs::read(base.join(&relative)) {
+            Ok(bytes) => files.push(ManifestEntry {
+                path: to_manifest_path(&relative),
+                sha256: sha256_hex(&bytes),
+                user_authored: false,
+            }),
+            Err(_) => missing.push(to_manifest_path(&relative)),
+        }
+    }
+
+    if !missing.is_empty() {
+        return Err(KaelError::Project {
+            message: format!(
+                "Cannot rebuild manifest, expected files are missing: {}. Run `kael generate` to restore them.",
+                missing.join(", ")
+            ),
+        });
+    }
+
+    Ok(Manifest {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        registry_version: Some(registry::version().to_string()),
+        generated_at: timestamp(),
+        files,
+    })
+}
+
+/// 디스크의 현재 내용으로 manifest를 다시 만든다. (`generate --write-manifest-only`)
+///
+/// 생성 결과에 없는 이전 항목(사용자 작성, `kael add`로 설치)은 파일이 남아 있으면 유지한다.
+/// 기록한 manifest 경로를 돌려준다.
+pub fn record(base: &Path, output: &GeneratedOutput) -> Result<PathBuf> {
+    let mut manifest = from_disk(base, output)?;
+    carry_over(base, &mut manifest, &[]);
+    write(base, &manifest)
+}
+
+/// `apply` 직후 manifest를 갱신한다.
+///
+/// 이번에 기록한 파일(Created/Updated/Merged/Touched)만 디스크 내용으로 해시한다.
+/// 건너뛴 파일은 이전 manifest의 해시를 그대로 두고, 이전 기록이 없으면
+/// kael이 쓰려던 내용의 해시를 남겨 사용자가 고친 내용이 kael 것으로 기록되지 않게 한다.
+pub fn record_applied(
+    base: &Path,
+    output: &GeneratedOutput,
+    applied: &[(PathBuf, WriteStatus)],
+) -> Result<PathBuf> {
+    let previous = read(base).ok().flatten();
+    let mut manifest = from_disk(base, output)?;
+    for (entry, (relative, content)) in manifest.files.iter_mut().zip(output.files()) {
+        let path = base.join(&relative);
+        let written = applied.iter().any(|(p, status)| {
+            *p == path
+                && matches!(
+                    status,
+                    WriteStatus::Created
+                        | WriteStatus::Updated(_)
+                        | WriteStatus::Merged(_)
+                        | WriteStatus::Touched
+                )
+        });
+        if written {
+            continue;
+        }
+        let recorded = previous
+            .as_ref()
+            .and_then(|m| m.files.iter().find(|e| e.path == entry.path));
+        match recorded {
+            Some(recorded) => *entry = recorded.clone(),
+            None => {
+                let content = project::normalize(content, LineEnding::default());
+                entry.sha256 = sha256_hex(content.as_bytes());
+            }
+        }
+    }
+    let removed: Vec<PathBuf> = applied
+        .iter()
+        .filter(|(_, status)| *status == WriteStatus::Removed)
+        .map(|(path, _)| path.clone())
+        .collect();
+    carry_over(base, &mut manifest, &removed);
+    write(base, &manifest)
+}
+
+/// 이전 manifest 항목 중 새 manifest에 없는 것을 이어 붙인다.
+///
+/// 사용자 작성 항목은 같은 경로의 생성 항목보다 앞선다. 지웠거나(`removed`) 디스크에 없는 파일은 뺀다.
+fn carry_over(base: &Path, manifest: &mut Manifest, removed: &[PathBuf]) {
+    let Some(previous) = read(base).ok().flatten() else {
+        return;
+    };
+    for entry in previous.files {
+        let path = base.join(&entry.path);
+        if removed.contains(&path) || !path.is_file() {
+            continue;
+        }
+        match manifest.files.iter_mut().find(|e| e.path == entry.path) {
+            Some(current) if entry.user_authored => *current = entry,
+            Some(_) => {}
+            None => manifest.files.push(entry),
+        }
+    }
+}
+
+fn timestamp() -> u64 {
+    std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default()
+        })
+}
+
+/// manifest를 `.claude/.kael-manifest.json`에 기록한다.
+pub fn write(base: &Path, manifest: &Manifest) -> Result<PathBuf> {
+    let path = base.join(MANIFEST_PATH);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(manifest)?;
+    std::fs::write(&path, json + "\n")?;
+    Ok(path)
+}
+
+/// manifest를 읽는다. 없으면 `None`.
+pub fn read(base: &Path) -> Result<Option<Manifest>> {
+    let path = base.join(MANIFEST_PATH);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path)?;
+    Ok(Some(serde_json::from_str(&content)?))
+}
+
+/// 사용자가 작성한 파일을 manifest에 기록한다. manifest가 없으면 새로 만든다.
+pub fn record_user_file(base: &Path, relative: &Path, bytes: &[u8]) -> Result<()> {
+    record_file(base, relative, bytes, true)
+}
+
+/// kael이 따로 설치한 파일(`kael add`, `generate --emit-only`)을 manifest에 기록한다.
+///
+/// 같은 경로의 이전 항목만 바꾸고 다른 항목은 그대로 둔다.
+pub fn record_installed_file(base: &Path, relative: &Path, bytes: &[u8]) -> Result<()> {
+    record_file(base, relative, bytes, false)
+}
+
+fn record_file(base: &Path, relative: &Path, bytes: &[u8], user_authored: bool) -> Result<()> {
+    let mut manifest = read(base)?.unwrap_or_else(|| Manifest {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        registry_version: Some(registry::version().to_string()),
+        generated_at: timestamp(),
+        files: Vec::new(),
+    });
+    let path = to_manifest_path(relative);
+    manifest.files.retain(|entry| entry.path != path);
+    manifest.files.push(ManifestEntry {
+        path,
+        sha256: sha256_hex(bytes),
+        user_authored,
+    });
+    write(base, &manifest)?;
+    Ok(())
+}
+
+/// 지운 파일의 manifest 항목을 뺀다. manifest가 없으면 아무것도 하지 않는다.
+pub fn forget(base: &Path, relative: &Path) -> Result<()> {
+    let Some(mut manifest) = read(base)? else {
+        return Ok(());
+    };
+    let path = to_manifest_path(relative);
+    manifest.files.retain(|entry| entry.path != path);
+    write(base, &manifest)?;
+    Ok(())
+}
+
+/// manifest에 사용자 작성으로 기록된 파일 경로 (`base` 기준 절대 경로)
+///
+/// manifest가 없거나 읽을 수 없으면 빈 목록이다.
+pub fn user_authored_paths(base: &Path) -> Vec<PathBuf> {
+    read(base)
+        .ok()
+        .flatten()
+        .map(|manifest| {
+            manifest
+                .files
+                .into_iter()
+                .filter(|entry| entry.user_authored)
+                .map(|entry| base.join(entry.path))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 설치된 컴포넌트와 레지스트리 내용의 관계
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallState {
+    /// 레지스트리 내용과 같다
+    Current,
+    /// kael이 기록한 그대로지만 레지스트리 내용이 바뀌었다
+    Outdated,
+    /// 기록 이후 사용자가 수정했다 (manifest가 없으면 레지스트리와 다른 모든 파일)
+    Modified,
+}
+
+impl InstallState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            InstallState::Current => "current",
+            InstallState::Outdated => "outdated",
+            InstallState::Modified => "modified",
+        }
+    }
+}
+
+/// 설치된 컴포넌트 하나의 비교 결과
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentDrift {
+    pub kind: ComponentKind,
+    pub name: String,
+    pub state: InstallState,
+}
+
+/// `.claude/`에 설치된 컴포넌트를 레지스트리 내용 및 manifest 해시와 비교한다.
+///
+/// 레지스트리에 없는 컴포넌트와 사용자 작성 파일은 결과에서 빠진다.
+pub fn compare_install_to_registry(base: &Path) -> Vec<ComponentDrift> {
+    let manifest = read(base).ok().flatten();
+    let recorded = |path: &str| {
+        manifest
+            .as_ref()
+            .and_then(|m| m.files.iter().find(|entry| entry.path == path))
+    };
+    let claude_dir = base.join(".claude");
+
+    let mut drift = Vec::new();
+    for kind in ComponentKind::ALL {
+        for name in registry::installed_components(kind, &claude_dir) {
+            let relative = Path::new(".claude").join(kind.installed_path(&name));
+            let path = to_manifest_path(&relative);
+            let entry = recorded(&path);
+            if entry.is_some_and(|e| e.user_authored) {
+                continue;
+            }
+            let (Ok(bytes), Ok(upstream)) = (
+                std::fs::read(base.join(&relative)),
+                registry::get_installable(kind, &name),
+            ) else {
+                continue;
+            };
+            let on_disk = sha256_hex(&bytes);
+            let upstream = project::normalize(&upstream, LineEnding::default());
+            let state = if on_disk == sha256_hex(upstream.as_bytes()) {
+                InstallState::Current
+            } else if entry.is_some_and(|e| e.sha256 == on_disk) {
+                InstallState::Outdated
+            } else {
+                InstallState::Modified
+            };
+            drift.push(ComponentDrift { kind, name, state });
+        }
+    }
+    drift
+}
+
+/// 바이트 내용의 SHA-256을 hex 문자열로 반환한다.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+fn to_manifest_path(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::generator::GeneratedFile;
+    use crate::core::project;
+
+    fn mock_output() -> GeneratedOutput {
+        GeneratedOutput {
+            claude_md: "# Test".into(),
+            settings_json: Some("{}".into()),
+            env_settings: vec![],
+            skills: vec![GeneratedFile {
+                relative_path: "skills/rust/error-handling/SKILL.md".into(),
+                content: "# Error Handling".into(),
+            }],
+            agents: vec![],
+            commands: vec![GeneratedFile {
+                relative_path: "commands/init.md".into(),
+                content: "# Init".into(),
+            }],
+            missing: vec![],
+            prd_warnings: vec![],
+        }
+    }
+
+    #[test]
+    fn sha256_known_value() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn rebuild_manifest_from_existing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = mock_output();
+        project::write_output(dir.path(), &output, false).unwrap();
+
+        // 사용자가 수정한 파일도 그대로 두고 현재 해시를 기록
+        let skill = dir
+            .path()
+            .join(".claude/skills/rust/error-handling/SKILL.md");
+        std::fs::write(&skill, "# Edited").unwrap();
+        let before = std::fs::metadata(&skill).unwrap().modified().unwrap();
+
+        let manifest = from_disk(dir.path(), &output).unwrap();
+        write(dir.path(), &manifest).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&skill).unwrap(), "# Edited");
+        assert_eq!(
+            std::fs::metadata(&skill).unwrap().modified().unwrap(),
+            before
+        );
+
+        let loaded = read(dir.path()).unwrap().unwrap();
+        assert_eq!(loaded, manifest);
+        let paths: Vec<_> = loaded.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "CLAUDE.md",
+                ".claude/settings.json",
+                ".claude/skills/rust/error-handling/SKILL.md",
+                ".claude/commands/init.md",
+            ]
+        );
+        let skill_entry = &loaded.files[2];
+        assert_eq!(skill_entry.sha256, sha256_hex(b"# Edited"));
+    }
+
+    #[test]
+    fn skipped_edits_stay_modified_after_merge() {
+        let dir = tempfile::tempdir().unwrap();
+        let parsed =
+            crate::core::prd::parse_prd("---\nname: m\nstack:\n  language: rust\ntype: cli\n---\n")
+                .unwrap();
+        let output = crate::core::generator::generate_parsed(&parsed).unwrap();
+        project::apply(dir.path(), &output, project::ApplyMode::Update).unwrap();
+        // `kael add`로 설치한 항목은 재생성 후에도 남아야 한다
+        crate::cli::add::install(dir.path(), ComponentKind::Skill, &["rust/ffi".into()]).unwrap();
+
+        let skill = dir
+            .path()
+            .join(".claude/skills/rust/error-handling/SKILL.md");
+        std::fs::write(&skill, "# Locally edited\n").unwrap();
+        // 다른 파일이 새로 기록되어 manifest가 다시 쓰이는 경우
+        std::fs::remove_file(dir.path().join("CLAUDE.md")).unwrap();
+        let report = project::apply(dir.path(), &output, project::ApplyMode::Merge).unwrap();
+        assert!(report.entries.contains(&(skill.clone(), WriteStatus::Kept)));
+
+        let state = |name: &str| {
+            compare_install_to_registry(dir.path())
+                .into_iter()
+                .find(|d| d.name == name)
+                .map(|d| d.state)
+        };
+        assert_eq!(state("rust/error-handling"), Some(InstallState::Modified));
+        assert_eq!(state("rust/ffi"), Some(InstallState::Current));
+        let manifest = read(dir.path()).unwrap().unwrap();
+        assert!(manifest
+            .files
+            .iter()
+            .any(|e| e.path == ".claude/skills/rust/ffi/SKILL.md"));
+    }
+
+    #[test]
+    fn single_file_write_updates_only_its_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = mock_output();
+        project::write_single(dir.path(), &output, "CLAUDE.md", false).unwrap();
+        let manifest = read(dir.path()).unwrap().unwrap();
+        assert_eq!(manifest.files.len(), 1);
+        assert_eq!(manifest.files[0].path, "CLAUDE.md");
+
+        project::apply(dir.path(), &output, project::ApplyMode::Force).unwrap();
+        let before = read(dir.path()).unwrap().unwrap();
+        let settings = dir.path().join(".claude/settings.json");
+        std::fs::write(&settings, "{}").unwrap();
+        std::fs::write(dir.path().join("CLAUDE.md"), "# Edited\n").unwrap();
+        project::write_single(dir.path(), &output, "CLAUDE.md", true).unwrap();
+
+        let after = read(dir.path()).unwrap().unwrap();
+        assert_eq!(after.files.len(), before.files.len());
+        // 다시 쓴 CLAUDE.md는 생성 내용, 손대지 않은 settings.json은 이전 해시 그대로
+        for entry in &before.files {
+            assert!(after.files.contains(entry), "{}", entry.path);
+        }
+        let settings_entry = after
+            .files
+            .iter()
+            .find(|e| e.path == ".claude/settings.json")
+            .unwrap();
+        assert_ne!(settings_entry.sha256, sha256_hex(b"{}"));
+    }
+
+    #[test]
+    fn rebuild_manifest_reports_missing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = mock_output();
+        project::write_output(dir.path(), &output, false).unwrap();
+        std::fs::remove_file(dir.path().join(".claude/commands/init.md")).unwrap();
+        let before = read(dir.path()).unwrap();
+
+        let err = from_disk(dir.path(), &output).unwrap_err();
+        assert!(err.to_string().contains(".claude/commands/init.md"));
+        assert_eq!(read(dir.path()).unwrap(), before);
+    }
+}
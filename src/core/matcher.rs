@@ -1,67 +1,581 @@
-use crate::core::prd::{Language, Prd, ProjectType};
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use crate::core::config::Config;
+use crate::core::prd::{Experience, Language, Prd, ProjectType};
+use crate::core::registry::{self, ComponentKind};
 
 // ── Matched result ──────────────────────────────────────────────────
 
 /// PRD로부터 자동 매칭된 컴포넌트 목록
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct MatchResult {
     pub skills: Vec<String>,
     pub agents: Vec<String>,
     pub commands: Vec<String>,
 }
 
+/// 매칭 결과에 더하고 뺄 컴포넌트 (`init --add-skill`, `--remove-agent` 등)
+///
+/// PRD를 고치지 않고 한 번만 구성을 바꿀 때 쓴다.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComponentOverrides {
+    pub add: MatchResult,
+    pub remove: MatchResult,
+}
+
+/// 자동 매칭 단계별 on/off. 기본값은 모든 단계 활성화이며, base와 명시적 목록은 항상 적용된다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MatchStages {
+    pub language: bool,
+    pub project_type: bool,
+    pub infra: bool,
+    /// `stack.database`
+    pub database: bool,
+    /// `team.experience` (시니어 팀의 docs-writer 제외 포함)
+    pub team: bool,
+    /// `features`
+    pub features: bool,
+    /// 언어가 둘 이상이면 매칭 뒤 `collapse_redundant_agents`를 적용한다 (기본값 off)
+    pub dedupe_agents: bool,
+}
+
+impl Default for MatchStages {
+    fn default() -> Self {
+        Self {
+            language: true,
+            project_type: true,
+            infra: true,
+            database: true,
+            team: true,
+            features: true,
+            dedupe_agents: false,
+        }
+    }
+}
+
+impl MatchResult {
+    fn empty() -> Self {
+        Self {
+            skills: Vec::new(),
+            agents: Vec::new(),
+            commands: Vec::new(),
+        }
+    }
+
+    /// `add`의 컴포넌트를 더하고 `remove`의 컴포넌트를 뺀다.
+    ///
+    /// 레지스트리에 없는 `add` 항목은 넣지 않고 이름을 따로 돌려준다.
+    /// 목록에 없는 `remove` 항목은 무시한다. 이름 비교는 `dedup`과 같은 규칙이다.
+    pub fn apply_overrides(
+        mut self,
+        add: &MatchResult,
+        remove: &MatchResult,
+    ) -> (MatchResult, Vec<String>) {
+        let mut unknown = Vec::new();
+        for (kind, names, added, removed) in [
+            (
+                ComponentKind::Skill,
+                &mut self.skills,
+                &add.skills,
+                &remove.skills,
+            ),
+            (
+                ComponentKind::Agent,
+                &mut self.agents,
+                &add.agents,
+                &remove.agents,
+            ),
+            (
+                ComponentKind::Command,
+                &mut self.commands,
+                &add.commands,
+                &remove.commands,
+            ),
+        ] {
+            for name in added {
+                if registry::has_component(kind, name) {
+                    names.push(name.clone());
+                } else {
+                    unknown.push(name.clone());
+                }
+            }
+            dedup(names);
+            let removed: Vec<String> = removed.iter().map(|name| normalize_name(name)).collect();
+            names.retain(|name| !removed.contains(&normalize_name(name)));
+        }
+        (self, unknown)
+    }
+
+    /// 목록마다 `_base/`, `_common/` 컴포넌트를 앞에 두고 나머지는 이름순으로 정렬한다.
+    ///
+    /// 매칭 로직이 바뀌어도 생성 결과의 순서가 흔들리지 않도록 생성 경로에서 쓴다.
+    /// frontmatter `priority`는 이 순서보다 앞선다.
+    pub fn sorted(mut self) -> MatchResult {
+        for (kind, names) in [
+            (ComponentKind::Skill, &mut self.skills),
+            (ComponentKind::Agent, &mut self.agents),
+            (ComponentKind::Command, &mut self.commands),
+        ] {
+            names.sort_by_cached_key(|name| {
+                let shared = name.starts_with("_base/") || name.starts_with("_common/");
+                (!shared, name.to_ascii_lowercase())
+            });
+            sort_by_priority(kind, names);
+        }
+        self
+    }
+}
+
 // ── Public API ──────────────────────────────────────────────────────
 
 /// PRD frontmatter를 분석하여 필요한 skills, agents, commands를 매칭한다.
 ///
 /// 명시적 `agents`/`skills` 필드가 있으면 해당 값을 우선 사용하고,
 /// 없으면 `stack.language` + `type`으로 자동 매칭한다.
+///
+/// `team.experience`에 따른 자동 매칭 규칙:
+/// - `junior`: `_base/mentor` agent와 `_common/code-review-checklist` skill을 추가
+/// - `senior`: `type`이 library/api가 아니면 `_base/docs-writer`를 뺀다
+/// - `mid` 또는 `team` 생략: 변화 없음
+///
+/// `features`에 알려진 값이 있으면 대응하는 skill을 추가한다 (`FEATURE_SKILLS`).
+/// 모르는 feature는 무시한다.
 pub fn match_components(prd: &Prd) -> MatchResult {
-    let mut skills = base_skills();
-    let mut agents = base_agents();
-    let mut commands = base_commands();
+    match_components_verbose(prd).0
+}
 
-    // 명시적 오버라이드 체크
-    let has_explicit_skills = prd.skills.as_ref().is_some_and(|s| !s.is_empty());
-    let has_explicit_agents = prd.agents.as_ref().is_some_and(|a| !a.is_empty());
+/// `match_components`와 같고, 명시적 목록 때문에 빠진 자동 매칭 컴포넌트를 경고로 함께 돌려준다.
+pub fn match_components_verbose(prd: &Prd) -> (MatchResult, Vec<String>) {
+    let breakdown = match_breakdown(prd);
+    let warnings = override_warnings(&breakdown);
+    (breakdown.result, warnings)
+}
+
+/// `stages`에서 끈 단계를 건너뛰고 매칭한다.
+pub fn match_components_with(prd: &Prd, stages: MatchStages) -> MatchResult {
+    #[cfg(test)]
+    MATCH_RUNS.with(|runs| runs.set(runs.get() + 1));
+
+    match_breakdown_with(prd, stages).result
+}
+
+/// 매칭 단계별로 어떤 컴포넌트가 추가되었는지 기록한 결과
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchBreakdown {
+    pub base: MatchResult,
+    pub language: MatchResult,
+    pub project_type: MatchResult,
+    pub infra: MatchResult,
+    /// `stack.database`로 추가된 컴포넌트
+    pub database: MatchResult,
+    /// `team.experience`로 추가된 컴포넌트
+    pub team: MatchResult,
+    /// `features`로 추가된 컴포넌트
+    pub features: MatchResult,
+    /// 명시적으로 지정되어 자동 매칭을 대체한 skills
+    pub explicit_skills: Option<Vec<String>>,
+    /// 명시적으로 지정되어 자동 매칭을 대체한 agents
+    pub explicit_agents: Option<Vec<String>>,
+    /// 모든 단계를 합치고 중복을 제거한 최종 결과
+    pub result: MatchResult,
+}
+
+/// 단계별 출처(provenance)를 보존하면서 매칭한다.
+pub fn match_breakdown(prd: &Prd) -> MatchBreakdown {
+    match_breakdown_with(prd, MatchStages::default())
+}
 
-    if has_explicit_skills {
-        skills = prd.skills.clone().unwrap();
+/// `match_breakdown`과 같지만 꺼진 단계는 빈 결과로 남긴다. base 목록은 `set_config`로 정한 설정을 따른다.
+pub fn match_breakdown_with(prd: &Prd, stages: MatchStages) -> MatchBreakdown {
+    CONFIG.with(|config| match_breakdown_configured(prd, stages, config.borrow().as_ref()))
+}
+
+/// `config`의 `[base]`로 base 목록을 조정해 매칭한다. `None`이면 기본 base 목록을 쓴다.
+pub fn match_breakdown_configured(
+    prd: &Prd,
+    stages: MatchStages,
+    config: Option<&Config>,
+) -> MatchBreakdown {
+    let framework = prd.stack.framework.as_deref();
+
+    let base = base(config);
+    // 여러 언어를 나열했으면 언어별 매칭을 모두 합친다
+    let language = if stages.language {
+        let mut language = MatchResult::empty();
+        for lang in prd.stack.language.iter() {
+            language.skills.extend(language_skills(lang, framework));
+            language.agents.extend(language_agents(lang, framework));
+        }
+        language
     } else {
-        skills.extend(language_skills(
-            &prd.stack.language,
-            prd.stack.framework.as_deref(),
-        ));
-        if let Some(infra) = &prd.stack.infra {
-            skills.extend(infra_skills(infra));
+        MatchResult::empty()
+    };
+    let project_type = if stages.project_type {
+        MatchResult {
+            skills: Vec::new(),
+            agents: type_agents(&prd.project_type),
+            commands: type_commands(&prd.project_type),
         }
-    }
-
-    if has_explicit_agents {
-        agents = prd.agents.clone().unwrap();
     } else {
-        agents.extend(language_agents(
-            &prd.stack.language,
-            prd.stack.framework.as_deref(),
-        ));
-        agents.extend(type_agents(&prd.project_type));
-    }
+        MatchResult::empty()
+    };
+    let infra = match prd.stack.infra.as_deref() {
+        Some(infra) if stages.infra => MatchResult {
+            skills: infra_skills(infra),
+            agents: Vec::new(),
+            commands: Vec::new(),
+        },
+        _ => MatchResult::empty(),
+    };
+    let database = if stages.database {
+        MatchResult {
+            skills: database_skills(prd.stack.database.as_deref()),
+            agents: Vec::new(),
+            commands: Vec::new(),
+        }
+    } else {
+        MatchResult::empty()
+    };
+    let experience = prd
+        .team
+        .as_ref()
+        .and_then(|t| t.experience.clone())
+        .filter(|_| stages.team);
+    let team = team_components(experience.as_ref());
+    let features = if stages.features {
+        MatchResult {
+            skills: feature_skills(prd.features.as_deref().unwrap_or_default()),
+            agents: Vec::new(),
+            commands: Vec::new(),
+        }
+    } else {
+        MatchResult::empty()
+    };
+
+    // 명시적 오버라이드 체크
+    let explicit_skills = prd.skills.clone().filter(|s| !s.is_empty());
+    let explicit_agents = prd.agents.clone().filter(|a| !a.is_empty());
 
-    commands.extend(type_commands(&prd.project_type));
+    let mut skills = match &explicit_skills {
+        Some(explicit) => explicit.clone(),
+        None => [
+            &base.skills,
+            &language.skills,
+            &infra.skills,
+            &database.skills,
+            &team.skills,
+            &features.skills,
+        ]
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect(),
+    };
+    let mut agents = match &explicit_agents {
+        Some(explicit) => explicit.clone(),
+        None => [
+            &base.agents,
+            &language.agents,
+            &project_type.agents,
+            &team.agents,
+        ]
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect(),
+    };
+    // 시니어 팀에는 문서 중심 프로젝트가 아니면 docs-writer를 붙이지 않는다
+    if explicit_agents.is_none()
+        && experience == Some(Experience::Senior)
+        && !matches!(prd.project_type, ProjectType::Library | ProjectType::Api)
+    {
+        agents.retain(|a| a != "_base/docs-writer");
+    }
+    let mut commands: Vec<String> = [&base.commands, &project_type.commands]
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect();
 
     dedup(&mut skills);
     dedup(&mut agents);
     dedup(&mut commands);
-
-    MatchResult {
+    let mut result = MatchResult {
         skills,
         agents,
         commands,
+    };
+    // 명시적 agents 목록은 사용자가 고른 그대로 둔다
+    if stages.dedupe_agents && explicit_agents.is_none() {
+        collapse_redundant_agents(&mut result, prd.stack.language.iter().as_slice());
+    }
+    sort_by_priority(ComponentKind::Skill, &mut result.skills);
+    sort_by_priority(ComponentKind::Agent, &mut result.agents);
+    sort_by_priority(ComponentKind::Command, &mut result.commands);
+
+    MatchBreakdown {
+        base,
+        language,
+        project_type,
+        infra,
+        database,
+        team,
+        features,
+        explicit_skills,
+        explicit_agents,
+        result,
+    }
+}
+
+/// 자동 매칭이 추가했을 컴포넌트 중 명시적 `skills`/`agents`가 빼 버린 것마다 경고 한 줄
+///
+/// 어느 단계가 추가했을지도 함께 적는다. 명시적 목록이 없으면 비어 있다.
+pub fn override_warnings(breakdown: &MatchBreakdown) -> Vec<String> {
+    let stages = [
+        ("base", &breakdown.base),
+        ("language", &breakdown.language),
+        ("type", &breakdown.project_type),
+        ("infra", &breakdown.infra),
+        ("database", &breakdown.database),
+        ("team", &breakdown.team),
+        ("features", &breakdown.features),
+    ];
+    let mut warnings = Vec::new();
+    if let Some(explicit) = &breakdown.explicit_skills {
+        let auto = stages
+            .iter()
+            .map(|(stage, g)| (*stage, g.skills.as_slice()));
+        push_dropped(
+            &mut warnings,
+            "skills",
+            ComponentKind::Skill,
+            explicit,
+            auto,
+        );
+    }
+    if let Some(explicit) = &breakdown.explicit_agents {
+        let auto = stages
+            .iter()
+            .map(|(stage, g)| (*stage, g.agents.as_slice()));
+        push_dropped(
+            &mut warnings,
+            "agents",
+            ComponentKind::Agent,
+            explicit,
+            auto,
+        );
+    }
+    warnings
+}
+
+fn push_dropped<'a>(
+    warnings: &mut Vec<String>,
+    field: &str,
+    kind: ComponentKind,
+    explicit: &[String],
+    auto: impl Iterator<Item = (&'a str, &'a [String])>,
+) {
+    let mut seen: Vec<String> = explicit.iter().map(|n| normalize_name(n)).collect();
+    for (stage, names) in auto {
+        for name in names {
+            let normalized = normalize_name(name);
+            if seen.contains(&normalized) {
+                continue;
+            }
+            warnings.push(format!(
+                "explicit `{field}` leaves out {} {name}, which {stage} matching would add",
+                kind.label()
+            ));
+            seen.push(normalized);
+        }
+    }
+}
+
+/// 여러 매칭 결과를 합친다. (`--from`을 여러 번 지정)
+///
+/// 처음 나온 순서대로 중복을 제거한 뒤 우선순위로 다시 정렬한다.
+pub fn merge(results: &[MatchResult]) -> MatchResult {
+    let mut merged = MatchResult::empty();
+    for result in results {
+        merged.skills.extend(result.skills.iter().cloned());
+        merged.agents.extend(result.agents.iter().cloned());
+        merged.commands.extend(result.commands.iter().cloned());
     }
+    dedup(&mut merged.skills);
+    dedup(&mut merged.agents);
+    dedup(&mut merged.commands);
+    sort_by_priority(ComponentKind::Skill, &mut merged.skills);
+    sort_by_priority(ComponentKind::Agent, &mut merged.agents);
+    sort_by_priority(ComponentKind::Command, &mut merged.commands);
+    merged
+}
+
+/// frontmatter `priority`가 높은 컴포넌트를 앞으로 옮긴다.
+///
+/// 안정 정렬이므로 우선순위가 같으면(기본값 0 포함) 매칭된 순서를 유지한다.
+fn sort_by_priority(kind: ComponentKind, names: &mut [String]) {
+    names.sort_by_cached_key(|name| {
+        Reverse(
+            registry::component_meta(kind, name)
+                .map(|meta| meta.priority)
+                .unwrap_or_default(),
+        )
+    });
+}
+
+/// 매칭 결과를 사람이 읽을 수 있는 여러 줄 설명으로 만든다.
+pub fn explain(prd: &Prd) -> String {
+    let breakdown = match_breakdown(prd);
+    let language = prd.stack.language.joined();
+    let project_type = format!("{:?}", prd.project_type).to_lowercase();
+
+    let mut out = format!("{} ({language} / {project_type})\n", prd.name);
+    push_group(&mut out, "Base defaults", &breakdown.base);
+    push_group(
+        &mut out,
+        &format!("Language ({language})"),
+        &breakdown.language,
+    );
+    push_group(
+        &mut out,
+        &format!("Type ({project_type})"),
+        &breakdown.project_type,
+    );
+    push_group(&mut out, "Infra", &breakdown.infra);
+    push_group(&mut out, "Database", &breakdown.database);
+    push_group(&mut out, "Team", &breakdown.team);
+    push_group(&mut out, "Features", &breakdown.features);
+
+    if breakdown.explicit_skills.is_some() || breakdown.explicit_agents.is_some() {
+        out.push_str("Overrides:\n");
+        if let Some(skills) = &breakdown.explicit_skills {
+            out.push_str(&format!(
+                "  skills (replace auto-matched): {}\n",
+                skills.join(", ")
+            ));
+        }
+        if let Some(agents) = &breakdown.explicit_agents {
+            out.push_str(&format!(
+                "  agents (replace auto-matched): {}\n",
+                agents.join(", ")
+            ));
+        }
+    }
+
+    let result = &breakdown.result;
+    out.push_str(&format!(
+        "Total: {} skills, {} agents, {} commands\n",
+        result.skills.len(),
+        result.agents.len(),
+        result.commands.len()
+    ));
+    out
+}
+
+fn push_group(out: &mut String, title: &str, group: &MatchResult) {
+    let rows = [
+        ("skills", &group.skills),
+        ("agents", &group.agents),
+        ("commands", &group.commands),
+    ];
+    if rows.iter().all(|(_, names)| names.is_empty()) {
+        return;
+    }
+    out.push_str(&format!("{title}:\n"));
+    for (label, names) in rows {
+        if !names.is_empty() {
+            out.push_str(&format!("  {label}: {}\n", names.join(", ")));
+        }
+    }
+}
+
+/// `match_components`의 메모이즈 버전.
+///
+/// 매칭은 순수 함수이므로, 한 프로세스 안에서 같은 PRD로 여러 번 호출하면
+/// (generate + diff + validate 조합 등) 첫 결과를 재사용한다.
+/// 캐시 키는 직렬화된 `Prd` 내용, `stages`, 그리고 우선순위 정렬이 읽는 레지스트리 출처와
+/// overlay 루트의 해시다. 그래서 `--registry`나 PRD의 `registry_paths`가 바뀌면 다시 매칭한다.
+pub fn match_components_cached(prd: &Prd, stages: MatchStages) -> MatchResult {
+    let key = prd_cache_key(prd, stages);
+    if let Some(hit) = MATCH_CACHE.with(|cache| cache.borrow().get(&key).cloned()) {
+        return hit;
+    }
+    let result = match_components_with(prd, stages);
+    MATCH_CACHE.with(|cache| cache.borrow_mut().insert(key, result.clone()));
+    result
+}
+
+// ── Memoization ─────────────────────────────────────────────────────
+
+thread_local! {
+    static MATCH_CACHE: RefCell<HashMap<u64, MatchResult>> = RefCell::new(HashMap::new());
+}
+
+#[cfg(test)]
+thread_local! {
+    static MATCH_RUNS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+fn prd_cache_key(prd: &Prd, stages: MatchStages) -> u64 {
+    // Prd는 Hash를 구현하지 않으므로 직렬화 결과를 해시한다
+    let serialized = serde_json::to_string(prd).unwrap_or_default();
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    serialized.hash(&mut hasher);
+    stages.hash(&mut hasher);
+    // `sort_by_priority`가 읽는 컴포넌트 메타데이터는 레지스트리 설정에 따라 다르다
+    registry::source().hash(&mut hasher);
+    registry::overlays().hash(&mut hasher);
+    hasher.finish()
 }
 
 // ── Always-included defaults ────────────────────────────────────────
 
+thread_local! {
+    static CONFIG: RefCell<Option<Config>> = const { RefCell::new(None) };
+}
+
+/// 이후 매칭에 쓸 설정을 정한다. (`main`에서 한 번)
+///
+/// base 목록이 바뀌므로 메모이즈한 결과도 비운다.
+pub fn set_config(config: Option<Config>) {
+    CONFIG.with(|c| *c.borrow_mut() = config);
+    MATCH_CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
+/// `set_config`로 정한 설정을 반영한 base 목록
+pub(crate) fn configured_base() -> MatchResult {
+    CONFIG.with(|config| base(config.borrow().as_ref()))
+}
+
+/// 기본 base 목록에 설정의 `[base]`를 덧붙이거나(기본) 바꾼다(`replace`).
+fn base(config: Option<&Config>) -> MatchResult {
+    let Some(lists) = config.map(|c| &c.base) else {
+        return MatchResult {
+            skills: base_skills(),
+            agents: base_agents(),
+            commands: base_commands(),
+        };
+    };
+    let adjust = |defaults: Vec<String>, configured: &Option<Vec<String>>| match configured {
+        Some(names) if lists.replace => names.clone(),
+        Some(names) => {
+            let mut merged = defaults;
+            merged.extend(names.iter().cloned());
+            dedup(&mut merged);
+            merged
+        }
+        None => defaults,
+    };
+    MatchResult {
+        skills: adjust(base_skills(), &lists.skills),
+        agents: adjust(base_agents(), &lists.agents),
+        commands: adjust(base_commands(), &lists.commands),
+    }
+}
+
 fn base_skills() -> Vec<String> {
     vec!["_common/git-workflow".into(), "_common/ci-cd".into()]
 }
@@ -74,6 +588,37 @@ fn base_commands() -> Vec<String> {
     vec!["init".into(), "review".into(), "commit".into()]
 }
 
+// ── Feature-based matching ──────────────────────────────────────────
+
+/// `features` 값 → 추가할 skill. 새 feature는 여기에 한 줄 추가하면 된다.
+const FEATURE_SKILLS: &[(&str, &str)] = &[
+    ("async-runtime", "rust/async-patterns"),
+    ("observability", "_common/observability"),
+    ("auth", "_common/auth-patterns"),
+];
+
+/// PRD 선언 순서와 관계없이 `FEATURE_SKILLS` 순서로 반환한다.
+fn feature_skills(features: &[String]) -> Vec<String> {
+    FEATURE_SKILLS
+        .iter()
+        .filter(|(feature, _)| features.iter().any(|f| f == feature))
+        .map(|(_, skill)| skill.to_string())
+        .collect()
+}
+
+// ── Team-based matching ─────────────────────────────────────────────
+
+fn team_components(experience: Option<&Experience>) -> MatchResult {
+    match experience {
+        Some(Experience::Junior) => MatchResult {
+            skills: vec!["_common/code-review-checklist".into()],
+            agents: vec!["_base/mentor".into()],
+            commands: Vec::new(),
+        },
+        _ => MatchResult::empty(),
+    }
+}
+
 // ── Language-based matching ─────────────────────────────────────────
 
 fn language_skills(language: &Language, framework: Option<&str>) -> Vec<String> {
@@ -94,11 +639,26 @@ fn language_skills(language: &Language, framework: Option<&str>) -> Vec<String>
             s
         }
         Language::Python => vec!["python/fastapi".into(), "python/ml-ops".into()],
-        Language::Go => vec![
-            "go/api-patterns".into(),
-            "go/concurrency".into(),
-            "go/testing".into(),
-        ],
+        Language::Go => {
+            let mut s = vec![
+                "go/api-patterns".into(),
+                "go/concurrency".into(),
+                "go/testing".into(),
+            ];
+            if let Some(web) = go_web_framework(framework) {
+                s.push(format!("go/http-{web}"));
+            }
+            s
+        }
+        Language::Java => vec!["java/spring-boot".into(), "java/testing".into()],
+        Language::Csharp => vec!["csharp/aspnet".into(), "csharp/testing".into()],
+        Language::Ruby => {
+            let mut s = vec!["ruby/rails-patterns".into(), "ruby/testing".into()];
+            if framework.is_some_and(|f| f.eq_ignore_ascii_case("rails")) {
+                s.push("ruby/activerecord".into());
+            }
+            s
+        }
     }
 }
 
@@ -122,10 +682,34 @@ fn language_agents(language: &Language, framework: Option<&str>) -> Vec<String>
             "python/ml-engineer".into(),
             "python/data-engineer".into(),
         ],
-        Language::Go => vec!["go/systems-expert".into(), "go/api-expert".into()],
+        Language::Go => {
+            let mut a = vec!["go/systems-expert".into(), "go/api-expert".into()];
+            if go_web_framework(framework).is_some() {
+                a.push("go/web-expert".into());
+            }
+            a
+        }
+        Language::Java => vec![
+            "java/backend-expert".into(),
+            "java/jvm-tuning-expert".into(),
+        ],
+        Language::Csharp => vec!["csharp/backend-expert".into(), "csharp/perf-expert".into()],
+        Language::Ruby => vec!["ruby/backend-expert".into()],
     }
 }
 
+/// `go/http-<name>` skill이 있는 Go 웹 프레임워크
+const GO_WEB_FRAMEWORKS: &[&str] = &["gin", "echo", "fiber"];
+
+/// `framework`가 Go 웹 프레임워크면 소문자 이름을 돌려준다. (대소문자 무시)
+fn go_web_framework(framework: Option<&str>) -> Option<&'static str> {
+    let framework = framework?;
+    GO_WEB_FRAMEWORKS
+        .iter()
+        .copied()
+        .find(|known| framework.eq_ignore_ascii_case(known))
+}
+
 // ── Type-based matching ─────────────────────────────────────────────
 
 fn type_commands(project_type: &ProjectType) -> Vec<String> {
@@ -133,6 +717,7 @@ fn type_commands(project_type: &ProjectType) -> Vec<String> {
         ProjectType::Cli => vec!["test".into(), "release".into()],
         ProjectType::Library => vec!["test".into(), "release".into()],
         ProjectType::Api | ProjectType::Web | ProjectType::Mobile => vec!["test".into()],
+        ProjectType::Desktop => vec!["test".into(), "package".into()],
     }
 }
 
@@ -143,11 +728,15 @@ fn type_agents(project_type: &ProjectType) -> Vec<String> {
         ProjectType::Api => vec!["_base/docs-writer".into(), "_base/test-architect".into()],
         ProjectType::Web => vec!["_base/ui-developer".into()],
         ProjectType::Mobile => vec!["_base/ui-developer".into()],
+        ProjectType::Desktop => vec!["_base/ui-developer".into(), "_base/packaging-expert".into()],
     }
 }
 
 // ── Infra-based matching ────────────────────────────────────────────
 
+/// infra 항목에 대응하는 skills를 반환한다.
+///
+/// PRD의 선언 순서가 출력 순서에 영향을 주지 않도록 결과를 정렬한다.
 fn infra_skills(infra: &[String]) -> Vec<String> {
     let mut skills = Vec::new();
     for item in infra {
@@ -158,15 +747,176 @@ fn infra_skills(infra: &[String]) -> Vec<String> {
             _ => {}
         }
     }
+    skills.sort();
     skills
 }
 
+// ── Framework validation ────────────────────────────────────────────
+
+/// 언어별로 알려진 `stack.framework` 값. 목록이 없는 언어는 검사하지 않는다.
+const KNOWN_FRAMEWORKS: &[(Language, &[&str])] = &[
+    (Language::Typescript, &["nextjs", "remix"]),
+    (Language::Rust, &["axum", "actix"]),
+    (Language::Python, &["fastapi", "django"]),
+    (Language::Go, &["gin", "echo", "fiber"]),
+    (Language::Ruby, &["rails", "sinatra"]),
+];
+
+/// `validate_framework` 결과
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrameworkCheck {
+    /// 알려진 값이거나, 검사 대상이 아님
+    Known,
+    /// 모르는 값. 철자가 가까운 알려진 값이 있으면 함께 준다.
+    Unknown { suggestion: Option<&'static str> },
+}
+
+/// `framework`가 `language`에서 알려진 값인지 확인한다. 대소문자는 구분하지 않는다.
+///
+/// 매칭은 모르는 값도 그대로 받아들이고, 이 결과는 `init`/`validate`의 경고에만 쓴다.
+/// `custom`은 어느 언어에서나 허용한다.
+pub fn validate_framework(language: &Language, framework: &str) -> FrameworkCheck {
+    let Some((_, known)) = KNOWN_FRAMEWORKS.iter().find(|(l, _)| l == language) else {
+        return FrameworkCheck::Known;
+    };
+    let framework = framework.trim().to_ascii_lowercase();
+    if framework == "custom" || known.contains(&framework.as_str()) {
+        return FrameworkCheck::Known;
+    }
+    // 오타로 볼 만큼 가까운 값만 추천한다
+    let suggestion = known
+        .iter()
+        .map(|name| (edit_distance(&framework, name), *name))
+        .filter(|(distance, name)| *distance <= 2.max(name.len() / 3))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, name)| name);
+    FrameworkCheck::Unknown { suggestion }
+}
+
+/// `stack.framework`가 어느 언어에서도 알려진 값이 아니면 경고 한 줄
+///
+/// 여러 언어를 나열했으면 어느 한 언어라도 아는 프레임워크면 경고하지 않는다.
+pub fn framework_warning(prd: &Prd) -> Option<String> {
+    let framework = prd.stack.framework.as_deref()?;
+    let mut suggestion = None;
+    for language in prd.stack.language.iter() {
+        match validate_framework(language, framework) {
+            FrameworkCheck::Known => return None,
+            FrameworkCheck::Unknown { suggestion: s } => suggestion = suggestion.or(s),
+        }
+    }
+    let hint = suggestion
+        .map(|name| format!(" (did you mean '{name}'?)"))
+        .unwrap_or_default();
+    Some(format!(
+        "unrecognized {} framework '{framework}'{hint}",
+        prd.stack.language.joined()
+    ))
+}
+
+/// PRD 하나에서 나오는 매칭 경고: 모르는 framework, 명시적 목록이 뺀 컴포넌트 순
+pub fn prd_warnings(prd: &Prd, stages: MatchStages) -> Vec<String> {
+    framework_warning(prd)
+        .into_iter()
+        .chain(override_warnings(&match_breakdown_with(prd, stages)))
+        .collect()
+}
+
+/// 두 문자열의 Levenshtein 거리
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = prev[j] + usize::from(ca != *cb);
+            row.push(substitute.min(prev[j + 1] + 1).min(row[j] + 1));
+        }
+        prev = row;
+    }
+    prev[b.len()]
+}
+
+// ── Database-based matching ─────────────────────────────────────────
+
+/// `stack.database`에 대응하는 skills를 반환한다. 대소문자는 구분하지 않고, 모르는 값은 무시한다.
+fn database_skills(database: Option<&str>) -> Vec<String> {
+    let skill = match database.map(str::to_ascii_lowercase).as_deref() {
+        Some("postgresql") => "_common/postgres",
+        Some("mongodb") => "_common/mongodb",
+        Some("redis") => "_common/redis",
+        _ => return Vec::new(),
+    };
+    vec![skill.into()]
+}
+
+// ── Polyglot collapsing ─────────────────────────────────────────────
+
+/// 언어마다 같은 역할을 맡는 전문가 agent와, 겹칠 때 대신 쓸 일반 전문가
+///
+/// | 역할 | 언어별 agent | 일반 agent |
+/// |------|--------------|------------|
+/// | API  | `go/api-expert`, `typescript/node-expert`, `python/backend-expert` | `_base/api-expert` |
+const REDUNDANT_AGENTS: &[(&[&str], &str)] = &[(
+    &[
+        "go/api-expert",
+        "typescript/node-expert",
+        "python/backend-expert",
+    ],
+    "_base/api-expert",
+)];
+
+/// 여러 언어를 쓰는 프로젝트에서 역할이 겹치는 언어별 전문가를 일반 전문가 하나로 합친다.
+///
+/// 언어가 하나뿐이거나 같은 역할의 agent가 둘 이상 매칭되지 않으면 그대로 둔다.
+/// 합쳐진 agent는 가장 먼저 나온 전문가의 자리에 들어간다.
+pub fn collapse_redundant_agents(matched: &mut MatchResult, languages: &[Language]) {
+    if languages.len() < 2 {
+        return;
+    }
+    for (experts, general) in REDUNDANT_AGENTS {
+        let present = matched
+            .agents
+            .iter()
+            .filter(|a| experts.contains(&a.as_str()))
+            .count();
+        if present < 2 {
+            continue;
+        }
+        let mut replaced = false;
+        matched.agents = std::mem::take(&mut matched.agents)
+            .into_iter()
+            .filter_map(|agent| {
+                if !experts.contains(&agent.as_str()) {
+                    return Some(agent);
+                }
+                (!std::mem::replace(&mut replaced, true)).then(|| general.to_string())
+            })
+            .collect();
+        dedup(&mut matched.agents);
+    }
+}
+
 // ── Helpers ─────────────────────────────────────────────────────────
 
 /// 순서를 유지하면서 중복을 제거한다.
+///
+/// 대소문자와 연속된 `/`를 무시하고 비교하며, 처음 나온 표기를 남긴다.
 fn dedup(vec: &mut Vec<String>) {
     let mut seen = std::collections::HashSet::new();
-    vec.retain(|item| seen.insert(item.clone()));
+    vec.retain(|item| seen.insert(normalize_name(item)));
+}
+
+/// 비교용 이름: 소문자로 바꾸고 `//`를 `/` 하나로 줄인다.
+fn normalize_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c == '/' && normalized.ends_with('/') {
+            continue;
+        }
+        normalized.extend(c.to_lowercase());
+    }
+    normalized
 }
 
 // ── Tests ───────────────────────────────────────────────────────────
@@ -174,14 +924,14 @@ fn dedup(vec: &mut Vec<String>) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::prd::{Experience, Stack, Team};
+    use crate::core::prd::{Stack, Team};
 
     fn make_prd(language: Language, project_type: ProjectType) -> Prd {
         Prd {
             name: "test".into(),
             description: None,
             stack: Stack {
-                language,
+                language: language.into(),
                 framework: None,
                 database: None,
                 infra: None,
@@ -192,10 +942,259 @@ mod tests {
             agents: None,
             skills: None,
             mcp: None,
+            sections: None,
+            registry_paths: None,
+            envs: None,
             team: None,
         }
     }
 
+    fn with_experience(mut prd: Prd, experience: Experience) -> Prd {
+        prd.team = Some(Team {
+            size: None,
+            experience: Some(experience),
+        });
+        prd
+    }
+
+    #[test]
+    fn merge_unions_rust_cli_and_python_api() {
+        let rust_cli = match_components(&make_prd(Language::Rust, ProjectType::Cli));
+        let python_api = match_components(&make_prd(Language::Python, ProjectType::Api));
+        let merged = merge(&[rust_cli.clone(), python_api.clone()]);
+
+        for name in rust_cli.skills.iter().chain(&python_api.skills) {
+            assert!(merged.skills.contains(name), "{name}");
+        }
+        for name in rust_cli.agents.iter().chain(&python_api.agents) {
+            assert!(merged.agents.contains(name), "{name}");
+        }
+        assert!(merged.agents.contains(&"_base/debugger".to_string()));
+        assert!(merged.agents.contains(&"_base/test-architect".to_string()));
+        assert!(merged.commands.contains(&"release".to_string()));
+
+        // 공통 컴포넌트는 한 번만 들어간다
+        let mut unique = merged.skills.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), merged.skills.len());
+        assert_eq!(
+            merged
+                .agents
+                .iter()
+                .filter(|a| *a == "_base/architect")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn known_frameworks_are_recognized_case_insensitively() {
+        assert_eq!(
+            validate_framework(&Language::Typescript, "nextjs"),
+            FrameworkCheck::Known
+        );
+        assert_eq!(
+            validate_framework(&Language::Rust, "Axum"),
+            FrameworkCheck::Known
+        );
+        assert_eq!(
+            validate_framework(&Language::Go, "custom"),
+            FrameworkCheck::Known
+        );
+        // 목록이 없는 언어는 검사하지 않는다
+        assert_eq!(
+            validate_framework(&Language::Java, "anything"),
+            FrameworkCheck::Known
+        );
+    }
+
+    #[test]
+    fn unknown_framework_suggests_closest_match() {
+        assert_eq!(
+            validate_framework(&Language::Typescript, "nextjss"),
+            FrameworkCheck::Unknown {
+                suggestion: Some("nextjs")
+            }
+        );
+        assert_eq!(
+            validate_framework(&Language::Python, "Djang0"),
+            FrameworkCheck::Unknown {
+                suggestion: Some("django")
+            }
+        );
+        assert_eq!(
+            validate_framework(&Language::Go, "beego"),
+            FrameworkCheck::Unknown { suggestion: None }
+        );
+        assert_eq!(
+            validate_framework(&Language::Go, "Fiber"),
+            FrameworkCheck::Known
+        );
+    }
+
+    #[test]
+    fn junior_team_adds_mentor_and_checklist() {
+        let prd = with_experience(
+            make_prd(Language::Rust, ProjectType::Cli),
+            Experience::Junior,
+        );
+        let result = match_components(&prd);
+        assert!(result.agents.contains(&"_base/mentor".to_string()));
+        assert!(result
+            .skills
+            .contains(&"_common/code-review-checklist".to_string()));
+        let breakdown = match_breakdown(&prd);
+        assert_eq!(breakdown.team.agents, vec!["_base/mentor".to_string()]);
+    }
+
+    #[test]
+    fn mid_team_and_no_team_match_identically() {
+        let plain = make_prd(Language::Python, ProjectType::Api);
+        let mid = with_experience(plain.clone(), Experience::Mid);
+        assert_eq!(match_components(&mid), match_components(&plain));
+        assert!(match_breakdown(&plain).team.agents.is_empty());
+    }
+
+    #[test]
+    fn senior_team_keeps_docs_writer_only_for_library_and_api() {
+        for project_type in [ProjectType::Library, ProjectType::Api] {
+            let prd = with_experience(make_prd(Language::Go, project_type), Experience::Senior);
+            assert!(match_components(&prd)
+                .agents
+                .contains(&"_base/docs-writer".to_string()));
+        }
+        let cli = with_experience(make_prd(Language::Go, ProjectType::Cli), Experience::Senior);
+        let result = match_components(&cli);
+        assert!(!result.agents.contains(&"_base/docs-writer".to_string()));
+        assert!(!result.agents.contains(&"_base/mentor".to_string()));
+    }
+
+    #[test]
+    fn observability_feature_adds_only_its_skill() {
+        let plain = make_prd(Language::Go, ProjectType::Api);
+        let mut with_feature = plain.clone();
+        with_feature.features = Some(vec!["observability".into(), "made-up".into()]);
+
+        let before = match_components(&plain);
+        let after = match_components(&with_feature);
+        let added: Vec<_> = after
+            .skills
+            .iter()
+            .filter(|s| !before.skills.contains(s))
+            .collect();
+        assert_eq!(added, ["_common/observability"]);
+        assert_eq!(after.skills.len(), before.skills.len() + 1);
+        assert_eq!(after.agents, before.agents);
+        assert_eq!(after.commands, before.commands);
+    }
+
+    #[test]
+    fn higher_priority_skill_sorts_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill = dir.path().join("skills/custom/urgent");
+        std::fs::create_dir_all(&skill).unwrap();
+        std::fs::write(
+            skill.join("SKILL.md"),
+            "---\nname: urgent\npriority: 10\n---\n# Urgent\n",
+        )
+        .unwrap();
+        registry::set_overlays(vec![dir.path().to_path_buf()]);
+
+        let mut prd = make_prd(Language::Rust, ProjectType::Cli);
+        prd.skills = Some(vec![
+            "_common/git-workflow".into(),
+            "custom/urgent".into(),
+            "rust/ffi".into(),
+        ]);
+        let result = match_components(&prd);
+        registry::set_overlays(Vec::new());
+
+        assert_eq!(
+            result.skills,
+            ["custom/urgent", "_common/git-workflow", "rust/ffi"]
+        );
+    }
+
+    #[test]
+    fn sorted_puts_shared_components_first() {
+        let mut prd = make_prd(Language::Rust, ProjectType::Cli);
+        prd.features = Some(vec!["observability".into()]);
+        let sorted = match_components(&prd).sorted();
+
+        assert_eq!(
+            sorted.skills,
+            [
+                "_common/ci-cd",
+                "_common/git-workflow",
+                "_common/observability",
+                "rust/async-patterns",
+                "rust/error-handling",
+                "rust/memory-optimization",
+            ]
+        );
+        assert_eq!(
+            sorted.agents[..3],
+            ["_base/architect", "_base/debugger", "_base/reviewer"]
+        );
+        assert_eq!(
+            sorted.commands,
+            ["commit", "init", "release", "review", "test"]
+        );
+        assert_eq!(sorted.clone().sorted(), sorted);
+    }
+
+    #[test]
+    fn polyglot_project_unions_language_matches() {
+        let mut prd = make_prd(Language::Rust, ProjectType::Web);
+        prd.stack = crate::core::prd::parse_prd(
+            "---\nname: p\nstack:\n  language: [rust, typescript]\n  framework: nextjs\ntype: web\n---\n",
+        )
+        .unwrap()
+        .frontmatter
+        .stack;
+        let result = match_components(&prd);
+
+        for skill in [
+            "rust/error-handling",
+            "typescript/testing",
+            "typescript/nextjs",
+        ] {
+            assert!(result.skills.contains(&skill.to_string()), "{skill}");
+        }
+        for agent in [
+            "rust/perf-engineer",
+            "typescript/node-expert",
+            "_base/ui-developer",
+        ] {
+            assert!(result.agents.contains(&agent.to_string()), "{agent}");
+        }
+        let rust_only = match_components(&make_prd(Language::Rust, ProjectType::Web));
+        assert!(rust_only.skills.iter().all(|s| result.skills.contains(s)));
+        assert!(explain(&prd).contains("(rust, typescript / web)"));
+    }
+
+    #[test]
+    fn overrides_add_and_remove_components() {
+        let matched = match_components(&make_prd(Language::Rust, ProjectType::Cli));
+        let add = MatchResult {
+            skills: vec!["rust/ffi".into(), "rust/no-such-skill".into()],
+            ..MatchResult::default()
+        };
+        let remove = MatchResult {
+            agents: vec!["_base/reviewer".into(), "_base/not-matched".into()],
+            ..MatchResult::default()
+        };
+        let (result, unknown) = matched.clone().apply_overrides(&add, &remove);
+
+        assert!(result.skills.contains(&"rust/ffi".to_string()));
+        assert!(!result.skills.contains(&"rust/no-such-skill".to_string()));
+        assert_eq!(unknown, ["rust/no-such-skill"]);
+        assert!(!result.agents.contains(&"_base/reviewer".to_string()));
+        assert_eq!(result.agents.len(), matched.agents.len() - 1);
+        assert_eq!(result.commands, matched.commands);
+    }
+
     #[test]
     fn rust_cli_matching() {
         let prd = make_prd(Language::Rust, ProjectType::Cli);
@@ -237,6 +1236,79 @@ mod tests {
         assert!(result.agents.contains(&"_base/ui-developer".into()));
     }
 
+    #[test]
+    fn go_gin_adds_framework_skill_and_web_expert() {
+        let mut prd = make_prd(Language::Go, ProjectType::Api);
+        prd.stack.framework = Some("Gin".into());
+        let result = match_components(&prd);
+
+        assert!(result.skills.contains(&"go/http-gin".into()));
+        assert!(result.agents.contains(&"go/web-expert".into()));
+        assert!(result.skills.contains(&"go/api-patterns".into()));
+        assert!(registry::has_component(ComponentKind::Skill, "go/http-gin"));
+        assert!(registry::has_component(
+            ComponentKind::Agent,
+            "go/web-expert"
+        ));
+        for web in GO_WEB_FRAMEWORKS {
+            assert!(
+                registry::has_component(ComponentKind::Skill, &format!("go/http-{web}")),
+                "{web}"
+            );
+        }
+    }
+
+    #[test]
+    fn bare_go_has_no_framework_components() {
+        let result = match_components(&make_prd(Language::Go, ProjectType::Api));
+
+        assert!(!result.skills.iter().any(|s| s.starts_with("go/http-")));
+        assert!(!result.agents.contains(&"go/web-expert".into()));
+        assert_eq!(
+            result
+                .agents
+                .iter()
+                .filter(|a| a.starts_with("go/"))
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn ruby_rails_web() {
+        let mut prd = make_prd(Language::Ruby, ProjectType::Web);
+        let plain = match_components(&prd);
+        assert!(plain.skills.contains(&"ruby/rails-patterns".into()));
+        assert!(plain.skills.contains(&"ruby/testing".into()));
+        assert!(!plain.skills.contains(&"ruby/activerecord".into()));
+
+        prd.stack.framework = Some("rails".into());
+        let result = match_components(&prd);
+
+        assert!(result.skills.contains(&"ruby/activerecord".into()));
+        assert!(result.agents.contains(&"ruby/backend-expert".into()));
+        assert!(result.agents.contains(&"_base/ui-developer".into()));
+        for name in &result.skills {
+            assert!(
+                registry::has_component(ComponentKind::Skill, name),
+                "{name}"
+            );
+        }
+        assert!(registry::has_component(
+            ComponentKind::Agent,
+            "ruby/backend-expert"
+        ));
+    }
+
+    #[test]
+    fn desktop_matches_ui_and_packaging() {
+        let result = match_components(&make_prd(Language::Typescript, ProjectType::Desktop));
+
+        assert!(result.agents.contains(&"_base/ui-developer".into()));
+        assert!(result.agents.contains(&"_base/packaging-expert".into()));
+        assert!(result.commands.contains(&"package".into()));
+    }
+
     #[test]
     fn python_api_matching() {
         let prd = make_prd(Language::Python, ProjectType::Api);
@@ -248,6 +1320,54 @@ mod tests {
         assert!(result.agents.contains(&"_base/test-architect".into()));
     }
 
+    #[test]
+    fn csharp_api_matching() {
+        let result = match_components(&make_prd(Language::Csharp, ProjectType::Api));
+
+        assert!(result.skills.contains(&"csharp/aspnet".into()));
+        assert!(result.skills.contains(&"csharp/testing".into()));
+        assert!(result.agents.contains(&"csharp/backend-expert".into()));
+        assert!(result.agents.contains(&"csharp/perf-expert".into()));
+        assert!(result.agents.contains(&"_base/test-architect".into()));
+        for name in result.skills.iter().chain(&result.agents) {
+            assert!(
+                registry::has_component(ComponentKind::Skill, name)
+                    || registry::has_component(ComponentKind::Agent, name),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn java_api_matching() {
+        let prd = make_prd(Language::Java, ProjectType::Api);
+        let result = match_components(&prd);
+
+        // base + java skills
+        assert!(result.skills.contains(&"_common/git-workflow".into()));
+        assert!(result.skills.contains(&"java/spring-boot".into()));
+        assert!(result.skills.contains(&"java/testing".into()));
+
+        // base + java + api agents
+        assert!(result.agents.contains(&"_base/architect".into()));
+        assert!(result.agents.contains(&"java/backend-expert".into()));
+        assert!(result.agents.contains(&"java/jvm-tuning-expert".into()));
+        assert!(result.agents.contains(&"_base/docs-writer".into()));
+
+        // base + api commands
+        assert!(result.commands.contains(&"init".into()));
+        assert!(result.commands.contains(&"test".into()));
+        assert!(!result.commands.contains(&"release".into()));
+
+        // 모든 매칭 결과가 레지스트리에 존재
+        for skill in &result.skills {
+            assert!(crate::core::registry::has_component(
+                crate::core::registry::ComponentKind::Skill,
+                skill
+            ));
+        }
+    }
+
     #[test]
     fn go_library_matching() {
         let prd = make_prd(Language::Go, ProjectType::Library);
@@ -281,6 +1401,47 @@ mod tests {
         assert!(!result.agents.contains(&"_base/architect".into()));
     }
 
+    #[test]
+    fn explicit_agents_warn_about_dropped_type_agent() {
+        let mut prd = make_prd(Language::Go, ProjectType::Api);
+        prd.agents = Some(vec![
+            "_base/architect".into(),
+            "_base/reviewer".into(),
+            "_base/docs-writer".into(),
+            "go/api-expert".into(),
+            "go/systems-expert".into(),
+        ]);
+        let (result, warnings) = match_components_verbose(&prd);
+
+        assert!(!result.agents.contains(&"_base/test-architect".to_string()));
+        assert_eq!(
+            warnings,
+            ["explicit `agents` leaves out agent _base/test-architect, which type matching would add"]
+        );
+
+        // 명시적 목록이 없으면 경고도 없다
+        prd.agents = None;
+        assert!(match_components_verbose(&prd).1.is_empty());
+    }
+
+    #[test]
+    fn database_adds_matching_skill() {
+        let mut prd = make_prd(Language::Python, ProjectType::Api);
+        let without = match_components(&prd);
+        assert!(!without
+            .skills
+            .iter()
+            .any(|s| s.starts_with("_common/postgres")));
+
+        prd.stack.database = Some("PostgreSQL".into());
+        let with = match_components(&prd);
+        assert!(with.skills.contains(&"_common/postgres".to_string()));
+        assert_eq!(with.skills.len(), without.skills.len() + 1);
+
+        prd.stack.database = Some("cockroachdb".into());
+        assert_eq!(match_components(&prd), without);
+    }
+
     #[test]
     fn infra_skills_added() {
         let mut prd = make_prd(Language::Rust, ProjectType::Cli);
@@ -291,6 +1452,16 @@ mod tests {
         assert!(result.skills.contains(&"infra/github-actions".into()));
     }
 
+    #[test]
+    fn infra_order_does_not_affect_result() {
+        let mut a = make_prd(Language::Rust, ProjectType::Cli);
+        a.stack.infra = Some(vec!["github-actions".into(), "docker".into()]);
+        let mut b = make_prd(Language::Rust, ProjectType::Cli);
+        b.stack.infra = Some(vec!["docker".into(), "github-actions".into()]);
+
+        assert_eq!(match_components(&a), match_components(&b));
+    }
+
     #[test]
     fn no_duplicates() {
         let prd = make_prd(Language::Rust, ProjectType::Cli);
@@ -335,13 +1506,183 @@ mod tests {
         assert!(result.agents.contains(&"_base/architect".into()));
     }
 
+    #[test]
+    fn cached_matching_reuses_result() {
+        let mut prd = make_prd(Language::Go, ProjectType::Api);
+        prd.name = "cached".into();
+
+        let before = MATCH_RUNS.with(|runs| runs.get());
+        let first = match_components_cached(&prd, MatchStages::default());
+        let second = match_components_cached(&prd, MatchStages::default());
+        let after = MATCH_RUNS.with(|runs| runs.get());
+
+        assert_eq!(first, second);
+        assert_eq!(first, match_components(&prd));
+        // 캐시된 두 호출에서 핵심 로직은 한 번만 실행
+        assert_eq!(after - before, 1);
+    }
+
+    #[test]
+    fn cache_follows_registry_overlays() {
+        let prd = make_prd(Language::Rust, ProjectType::Cli);
+        let plain = match_components_cached(&prd, MatchStages::default());
+        let last = plain.skills.last().unwrap().clone();
+        assert_ne!(plain.skills[0], last);
+
+        // overlay가 마지막 skill의 우선순위를 올리면 정렬이 바뀌어야 한다
+        let overlay = tempfile::tempdir().unwrap();
+        let skill = overlay.path().join("skills").join(&last);
+        std::fs::create_dir_all(&skill).unwrap();
+        std::fs::write(
+            skill.join("SKILL.md"),
+            "---\npriority: 100\n---\n# Boosted\n",
+        )
+        .unwrap();
+        registry::set_overlays(vec![overlay.path().to_path_buf()]);
+        let boosted = match_components_cached(&prd, MatchStages::default());
+        registry::set_overlays(Vec::new());
+
+        assert_eq!(boosted.skills[0], last);
+        assert_eq!(match_components_cached(&prd, MatchStages::default()), plain);
+    }
+
+    #[test]
+    fn cache_distinguishes_prd_content() {
+        let rust = make_prd(Language::Rust, ProjectType::Cli);
+        let go = make_prd(Language::Go, ProjectType::Cli);
+
+        let a = match_components_cached(&rust, MatchStages::default());
+        let b = match_components_cached(&go, MatchStages::default());
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn breakdown_result_matches_match_components() {
+        let mut prd = make_prd(Language::Rust, ProjectType::Cli);
+        prd.stack.infra = Some(vec!["docker".into()]);
+        let breakdown = match_breakdown(&prd);
+
+        assert_eq!(breakdown.result, match_components(&prd));
+        assert!(breakdown.infra.skills.contains(&"infra/docker".into()));
+        assert!(breakdown
+            .project_type
+            .agents
+            .contains(&"_base/debugger".into()));
+    }
+
+    #[test]
+    fn no_language_match_keeps_base_and_type() {
+        let prd = make_prd(Language::Rust, ProjectType::Cli);
+        let stages = MatchStages {
+            language: false,
+            ..MatchStages::default()
+        };
+        let result = match_components_with(&prd, stages);
+
+        assert!(!result.skills.contains(&"rust/async-patterns".into()));
+        assert!(!result.agents.contains(&"rust/perf-engineer".into()));
+        assert!(result.skills.contains(&"_common/git-workflow".into()));
+        assert!(result.agents.contains(&"_base/debugger".into()));
+        assert!(result.commands.contains(&"release".into()));
+    }
+
+    #[test]
+    fn all_stages_off_with_explicit_lists() {
+        let mut prd = make_prd(Language::Rust, ProjectType::Cli);
+        prd.stack.infra = Some(vec!["docker".into()]);
+        prd.skills = Some(vec!["rust/ffi".into()]);
+        let stages = MatchStages {
+            language: false,
+            project_type: false,
+            infra: false,
+            database: false,
+            team: false,
+            features: false,
+            dedupe_agents: false,
+        };
+        let result = match_components_with(&prd, stages);
+
+        assert_eq!(result.skills, vec!["rust/ffi"]);
+        assert_eq!(result.agents, vec!["_base/architect", "_base/reviewer"]);
+        assert_eq!(result.commands, vec!["init", "review", "commit"]);
+    }
+
+    #[test]
+    fn database_team_and_feature_stages_can_be_skipped() {
+        let plain = make_prd(Language::Go, ProjectType::Api);
+        let mut prd = with_experience(plain.clone(), Experience::Junior);
+        prd.stack.database = Some("postgresql".into());
+        prd.features = Some(vec!["observability".into()]);
+        let stages = MatchStages {
+            database: false,
+            team: false,
+            features: false,
+            ..MatchStages::default()
+        };
+
+        let all = match_breakdown_with(&prd, MatchStages::default());
+        assert!(!all.database.skills.is_empty());
+        assert!(!all.team.skills.is_empty());
+        assert!(!all.features.skills.is_empty());
+        let skipped = match_breakdown_with(&prd, stages);
+        assert_eq!(skipped.database, MatchResult::empty());
+        assert_eq!(skipped.team, MatchResult::empty());
+        assert_eq!(skipped.features, MatchResult::empty());
+        assert_eq!(skipped.result, match_components(&plain));
+    }
+
+    #[test]
+    fn polyglot_collapses_api_experts() {
+        let go = match_components(&make_prd(Language::Go, ProjectType::Api));
+        let ts = match_components(&make_prd(Language::Typescript, ProjectType::Api));
+        let mut matched = MatchResult {
+            skills: Vec::new(),
+            agents: [go.agents, ts.agents].concat(),
+            commands: Vec::new(),
+        };
+        dedup(&mut matched.agents);
+
+        collapse_redundant_agents(&mut matched, &[Language::Go, Language::Typescript]);
+        assert!(matched.agents.contains(&"_base/api-expert".into()));
+        assert!(!matched.agents.contains(&"go/api-expert".into()));
+        assert!(!matched.agents.contains(&"typescript/node-expert".into()));
+        assert!(matched.agents.contains(&"go/systems-expert".into()));
+
+        // 단일 언어에서는 손대지 않는다
+        let mut single = match_components(&make_prd(Language::Go, ProjectType::Api));
+        let before = single.clone();
+        collapse_redundant_agents(&mut single, &[Language::Go]);
+        assert_eq!(single, before);
+    }
+
+    #[test]
+    fn dedupe_agents_stage_collapses_polyglot_experts() {
+        let prd = crate::core::prd::parse_prd(
+            "---\nname: p\nstack:\n  language: [go, typescript]\ntype: api\n---\n",
+        )
+        .unwrap()
+        .frontmatter;
+        let stages = MatchStages {
+            dedupe_agents: true,
+            ..MatchStages::default()
+        };
+
+        let default = match_components_with(&prd, MatchStages::default());
+        assert!(default.agents.contains(&"go/api-expert".into()));
+        assert!(default.agents.contains(&"typescript/node-expert".into()));
+        let collapsed = match_components_with(&prd, stages);
+        assert!(collapsed.agents.contains(&"_base/api-expert".into()));
+        assert!(!collapsed.agents.contains(&"go/api-expert".into()));
+        assert!(!collapsed.agents.contains(&"typescript/node-expert".into()));
+    }
+
     #[test]
     fn full_prd_matching() {
         let prd = Prd {
             name: "my-project".into(),
             description: Some("A sample project".into()),
             stack: Stack {
-                language: Language::Rust,
+                language: Language::Rust.into(),
                 framework: Some("custom".into()),
                 database: Some("postgresql".into()),
                 infra: Some(vec!["docker".into(), "kubernetes".into()]),
@@ -352,6 +1693,9 @@ mod tests {
             agents: None,
             skills: None,
             mcp: Some(vec!["github".into()]),
+            sections: None,
+            registry_paths: None,
+            envs: None,
             team: Some(Team {
                 size: Some(3),
                 experience: Some(Experience::Senior),
@@ -368,4 +1712,89 @@ mod tests {
         assert!(result.agents.contains(&"rust/perf-engineer".into()));
         assert!(result.agents.contains(&"_base/debugger".into()));
     }
+
+    #[test]
+    fn dedup_ignores_case_and_repeated_slashes() {
+        let mut names = vec![
+            "rust/async-patterns".to_string(),
+            "Rust/Async-Patterns".to_string(),
+            "rust//async-patterns".to_string(),
+            "_common/git-workflow".to_string(),
+            "_COMMON/Git-Workflow".to_string(),
+        ];
+        dedup(&mut names);
+        assert_eq!(names, vec!["rust/async-patterns", "_common/git-workflow"]);
+
+        // 처음 나온 표기를 그대로 남긴다
+        let mut first_mixed = vec!["Rust/FFI".to_string(), "rust/ffi".to_string()];
+        dedup(&mut first_mixed);
+        assert_eq!(first_mixed, vec!["Rust/FFI"]);
+    }
+
+    fn base_config(replace: bool) -> Config {
+        Config {
+            base: crate::core::config::BaseLists {
+                replace,
+                skills: Some(vec!["_common/observability".into()]),
+                agents: Some(vec!["_base/security-auditor".into()]),
+                commands: None,
+            },
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn base_lists_default_without_config() {
+        let prd = make_prd(Language::Go, ProjectType::Cli);
+        let breakdown = match_breakdown_configured(&prd, MatchStages::default(), None);
+        assert_eq!(breakdown.base.skills, base_skills());
+        assert_eq!(breakdown.base.agents, base_agents());
+        assert_eq!(breakdown.base.commands, base_commands());
+        assert_eq!(breakdown.result, match_components(&prd));
+    }
+
+    #[test]
+    fn config_replaces_base_lists() {
+        let prd = make_prd(Language::Go, ProjectType::Cli);
+        let config = base_config(true);
+        let breakdown = match_breakdown_configured(&prd, MatchStages::default(), Some(&config));
+
+        assert_eq!(breakdown.base.skills, vec!["_common/observability"]);
+        assert_eq!(breakdown.base.agents, vec!["_base/security-auditor"]);
+        // 지정하지 않은 목록은 기본값 그대로
+        assert_eq!(breakdown.base.commands, base_commands());
+        assert!(!breakdown
+            .result
+            .skills
+            .contains(&"_common/git-workflow".into()));
+        assert!(!breakdown.result.agents.contains(&"_base/architect".into()));
+    }
+
+    #[test]
+    fn config_appends_to_base_lists() {
+        let prd = make_prd(Language::Go, ProjectType::Cli);
+        let config = base_config(false);
+        let breakdown = match_breakdown_configured(&prd, MatchStages::default(), Some(&config));
+
+        assert_eq!(
+            breakdown.base.skills,
+            vec![
+                "_common/git-workflow",
+                "_common/ci-cd",
+                "_common/observability"
+            ]
+        );
+        assert_eq!(
+            breakdown.base.agents,
+            vec![
+                "_base/architect",
+                "_base/reviewer",
+                "_base/security-auditor"
+            ]
+        );
+        assert!(breakdown
+            .result
+            .agents
+            .contains(&"_base/security-auditor".into()));
+    }
 }
@@ -0,0 +1,223 @@
+use std::path::{Path, PathBuf};
+
+use crate::error::{KaelError, Result};
+
+/// 외부 레지스트리 레이아웃을 현재 규칙에 맞추기 위한 이동 한 건
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+    /// 레지스트리 루트 기준 현재 경로
+    pub from: PathBuf,
+    /// 레지스트리 루트 기준 옮길 경로
+    pub to: PathBuf,
+    pub reason: &'static str,
+}
+
+/// 카테고리 디렉토리 없이 놓인 agent를 옮길 기본 카테고리
+const DEFAULT_AGENT_CATEGORY: &str = "_base";
+
+/// 레지스트리 루트를 훑어 흔한 레이아웃 실수를 찾고 이동 계획을 만든다.
+///
+/// - `agents/<name>.md`: 카테고리 디렉토리 없음 → `agents/_base/<name>.md`
+/// - `skills/**/skill.md` 등 대소문자가 다른 파일 → `SKILL.md`
+/// - `skills/<category>/<name>.md`: 디렉토리 없는 스킬 → `skills/<category>/<name>/SKILL.md`
+pub fn plan_migration(root: &Path) -> Result<Vec<Migration>> {
+    if !root.is_dir() {
+        return Err(KaelError::Project {
+            message: format!("Registry directory not found: {}", root.display()),
+        });
+    }
+
+    let mut plan = Vec::new();
+    let agents = root.join("agents");
+    for file in md_files(&agents) {
+        let name = file.file_name().unwrap_or_default();
+        plan.push(Migration {
+            from: Path::new("agents").join(name),
+            to: Path::new("agents").join(DEFAULT_AGENT_CATEGORY).join(name),
+            reason: "agent without a category directory",
+        });
+    }
+
+    let skills = root.join("skills");
+    plan_skills(root, &skills, 0, false, &mut plan);
+
+    plan.sort_by(|a, b| a.from.cmp(&b.from));
+    Ok(plan)
+}
+
+/// 계획을 실제로 적용한다. 대상 경로가 이미 있으면 아무것도 옮기기 전에 에러를 낸다.
+pub fn apply_migration(root: &Path, plan: &[Migration]) -> Result<()> {
+    for migration in plan {
+        let target = root.join(&migration.to);
+        // 대소문자만 다른 rename은 대소문자 무시 파일시스템에서 원본과 같은 파일로 보인다
+        let same_file = migration.from.to_string_lossy().to_lowercase()
+            == migration.to.to_string_lossy().to_lowercase();
+        if target.exists() && !same_file {
+            return Err(KaelError::FileExists { path: target });
+        }
+    }
+    for migration in plan {
+        let target = root.join(&migration.to);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::rename(root.join(&migration.from), target)?;
+    }
+    Ok(())
+}
+
+/// `in_skill`이면 `dir`은 스킬 디렉토리(또는 그 안)이므로 파일을 옮기지 않는다.
+fn plan_skills(root: &Path, dir: &Path, depth: usize, in_skill: bool, plan: &mut Vec<Migration>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let entries: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+    // SKILL.md가 있는 디렉토리의 다른 .md 파일은 스킬의 부속 문서다
+    let is_skill_dir = entries.iter().any(|p| {
+        p.file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.eq_ignore_ascii_case("SKILL.md"))
+    });
+    for path in &entries {
+        if path.is_dir() {
+            // 옆의 `<name>.md`가 `<name>/SKILL.md`로 옮겨질 디렉토리도 스킬 디렉토리다
+            let becomes_skill = depth >= 1
+                && !is_skill_dir
+                && entries.contains(&path.with_extension("md"))
+                && path.extension().is_none();
+            plan_skills(
+                root,
+                path,
+                depth + 1,
+                in_skill || is_skill_dir || becomes_skill,
+                plan,
+            );
+        }
+    }
+    // 스킬 안의 references/ 같은 하위 디렉토리는 부속 문서뿐이다
+    if in_skill {
+        return;
+    }
+    for path in entries {
+        let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+        if path.is_dir() {
+            continue;
+        }
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if name.eq_ignore_ascii_case("SKILL.md") {
+            if name != "SKILL.md" {
+                plan.push(Migration {
+                    to: relative.with_file_name("SKILL.md"),
+                    from: relative,
+                    reason: "skill file must be named SKILL.md",
+                });
+            }
+        } else if depth >= 1 && !is_skill_dir && path.extension().is_some_and(|e| e == "md") {
+            // skills/<category>/<name>.md → skills/<category>/<name>/SKILL.md
+            let stem = path.file_stem().unwrap_or_default();
+            plan.push(Migration {
+                to: relative.with_file_name(stem).join("SKILL.md"),
+                from: relative,
+                reason: "skill without its own directory",
+            });
+        }
+    }
+}
+
+/// 디렉토리 바로 아래의 `.md` 파일 목록
+fn md_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == "md"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mislaid_registry() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for (path, content) in [
+            ("agents/helper.md", "# helper"),
+            ("agents/rust/perf.md", "# ok"),
+            ("skills/rust/ffi/skill.md", "# ffi"),
+            ("skills/rust/ffi/notes.md", "# extra"),
+            ("skills/rust/ffi/references/abi.md", "# nested doc"),
+            ("skills/rust/async/SKILL.md", "# ok"),
+            ("skills/go/cgo.md", "# cgo"),
+            ("skills/go/cgo/references/linking.md", "# nested doc"),
+        ] {
+            let file = root.join(path);
+            std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+            std::fs::write(file, content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn plan_detects_common_mistakes() {
+        let dir = mislaid_registry();
+        let plan = plan_migration(dir.path()).unwrap();
+        let moves: Vec<(PathBuf, PathBuf)> = plan.into_iter().map(|m| (m.from, m.to)).collect();
+
+        assert_eq!(
+            moves,
+            vec![
+                ("agents/helper.md".into(), "agents/_base/helper.md".into()),
+                ("skills/go/cgo.md".into(), "skills/go/cgo/SKILL.md".into()),
+                (
+                    "skills/rust/ffi/skill.md".into(),
+                    "skills/rust/ffi/SKILL.md".into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn apply_fixes_layout() {
+        let dir = mislaid_registry();
+        let plan = plan_migration(dir.path()).unwrap();
+        apply_migration(dir.path(), &plan).unwrap();
+
+        assert!(dir.path().join("agents/_base/helper.md").is_file());
+        assert!(dir.path().join("skills/go/cgo/SKILL.md").is_file());
+        let ffi: Vec<_> = std::fs::read_dir(dir.path().join("skills/rust/ffi"))
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name())
+            .filter(|n| n == "SKILL.md" || n == "skill.md")
+            .collect();
+        assert_eq!(ffi, vec!["SKILL.md"]);
+        assert!(plan_migration(dir.path()).unwrap().is_empty());
+        // 스킬 안의 부속 문서는 제자리에 남는다
+        assert!(dir
+            .path()
+            .join("skills/rust/ffi/references/abi.md")
+            .is_file());
+        assert!(dir
+            .path()
+            .join("skills/go/cgo/references/linking.md")
+            .is_file());
+    }
+
+    #[test]
+    fn apply_refuses_to_clobber() {
+        let dir = mislaid_registry();
+        std::fs::create_dir_all(dir.path().join("agents/_base")).unwrap();
+        std::fs::write(dir.path().join("agents/_base/helper.md"), "# existing").unwrap();
+
+        let plan = plan_migration(dir.path()).unwrap();
+        let err = apply_migration(dir.path(), &plan).unwrap_err();
+        assert!(matches!(err, KaelError::FileExists { .. }));
+        // 아무것도 옮기지 않았다
+        assert!(dir.path().join("skills/go/cgo.md").is_file());
+    }
+}
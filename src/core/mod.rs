@@ -1,5 +1,14 @@
+pub mod config;
+pub mod detect;
+pub mod diff;
 pub mod generator;
+pub mod manifest;
 pub mod matcher;
+pub mod migrate;
 pub mod prd;
+pub mod prd_upgrade;
 pub mod project;
 pub mod registry;
+pub mod remote;
+pub mod timings;
+pub mod validate;
@@ -1,52 +1,195 @@
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
-use pulldown_cmark::{Event, HeadingLevel, Parser, Tag, TagEnd};
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Parser, Tag, TagEnd};
 use serde::{Deserialize, Serialize};
 
+use crate::core::matcher;
 use crate::error::{KaelError, Result};
 
 // ── Data types ──────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Prd {
     pub name: String,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     pub stack: Stack,
     #[serde(rename = "type")]
     pub project_type: ProjectType,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub features: Option<Vec<String>>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub constraints: Option<Vec<String>>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub agents: Option<Vec<String>>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub skills: Option<Vec<String>>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mcp: Option<Vec<String>>,
-    #[serde(default)]
+    /// CLAUDE.md에 포함할 본문 섹션 헤딩 목록. 생략하면 모든 섹션을 포함한다.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sections: Option<Vec<String>>,
+    /// 임베디드 레지스트리보다 먼저 찾을 로컬 레지스트리 디렉토리 (PRD 파일 기준 상대 경로)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry_paths: Option<Vec<PathBuf>>,
+    /// 환경별 settings.json 덮어쓰기 (`generate --env <name>`이 `settings.<name>.json`에 반영)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub envs: Option<BTreeMap<String, serde_json::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub team: Option<Team>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Stack {
-    pub language: Language,
-    #[serde(default)]
+    pub language: Languages,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub framework: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub database: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub infra: Option<Vec<String>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, clap::ValueEnum)]
 #[serde(rename_all = "lowercase")]
 pub enum Language {
     Rust,
     Typescript,
     Python,
     Go,
+    Java,
+    Csharp,
+    Ruby,
+}
+
+impl Language {
+    pub const ALL: [Language; 7] = [
+        Language::Rust,
+        Language::Typescript,
+        Language::Python,
+        Language::Go,
+        Language::Java,
+        Language::Csharp,
+        Language::Ruby,
+    ];
+
+    /// frontmatter에 쓰는 값 (`stack.language`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Language::Rust => "rust",
+            Language::Typescript => "typescript",
+            Language::Python => "python",
+            Language::Go => "go",
+            Language::Java => "java",
+            Language::Csharp => "csharp",
+            Language::Ruby => "ruby",
+        }
+    }
+}
+
+/// `stack.language`: 언어 하나, 또는 여러 언어를 섞은 프로젝트라면 목록
+///
+/// 비어 있지 않으며 첫 언어가 주 언어다. 하나일 때는 예전처럼 스칼라로 직렬화한다.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Languages(Vec<Language>);
+
+impl Languages {
+    /// 나열된 순서대로, 중복 없이
+    pub fn iter(&self) -> std::slice::Iter<'_, Language> {
+        self.0.iter()
+    }
+
+    /// 첫 번째(주) 언어
+    pub fn primary(&self) -> &Language {
+        &self.0[0]
+    }
+
+    pub fn contains(&self, language: &Language) -> bool {
+        self.0.contains(language)
+    }
+
+    /// 템플릿과 메시지에 쓰는 쉼표 목록 (예: `rust, typescript`)
+    pub fn joined(&self) -> String {
+        let names: Vec<&str> = self.0.iter().map(Language::as_str).collect();
+        names.join(", ")
+    }
+}
+
+impl From<Language> for Languages {
+    fn from(language: Language) -> Self {
+        Languages(vec![language])
+    }
+}
+
+impl PartialEq<Language> for Languages {
+    fn eq(&self, other: &Language) -> bool {
+        self.0.len() == 1 && self.0[0] == *other
+    }
+}
+
+/// 하나면 `Rust`, 여럿이면 `[Rust, Typescript]`
+impl std::fmt::Debug for Languages {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0.as_slice() {
+            [language] => language.fmt(f),
+            languages => f.debug_list().entries(languages).finish(),
+        }
+    }
+}
+
+impl Serialize for Languages {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        match self.0.as_slice() {
+            [language] => language.serialize(serializer),
+            languages => languages.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Languages {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = Languages;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a language or a list of languages")
+            }
+
+            fn visit_str<E: serde::de::Error>(
+                self,
+                value: &str,
+            ) -> std::result::Result<Languages, E> {
+                Language::deserialize(serde::de::value::StrDeserializer::new(value))
+                    .map(Languages::from)
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> std::result::Result<Languages, A::Error> {
+                let mut languages = Vec::new();
+                while let Some(language) = seq.next_element::<Language>()? {
+                    if !languages.contains(&language) {
+                        languages.push(language);
+                    }
+                }
+                if languages.is_empty() {
+                    return Err(serde::de::Error::invalid_length(0, &self));
+                }
+                Ok(Languages(languages))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -57,13 +200,37 @@ pub enum ProjectType {
     Web,
     Api,
     Mobile,
+    Desktop,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl ProjectType {
+    pub const ALL: [ProjectType; 6] = [
+        ProjectType::Library,
+        ProjectType::Cli,
+        ProjectType::Web,
+        ProjectType::Api,
+        ProjectType::Mobile,
+        ProjectType::Desktop,
+    ];
+
+    /// frontmatter에 쓰는 값 (`type`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProjectType::Library => "library",
+            ProjectType::Cli => "cli",
+            ProjectType::Web => "web",
+            ProjectType::Api => "api",
+            ProjectType::Mobile => "mobile",
+            ProjectType::Desktop => "desktop",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Team {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub size: Option<u32>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub experience: Option<Experience>,
 }
 
@@ -75,6 +242,45 @@ pub enum Experience {
     Senior,
 }
 
+impl Experience {
+    /// frontmatter에 쓰는 값 (`team.experience`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Experience::Junior => "junior",
+            Experience::Mid => "mid",
+            Experience::Senior => "senior",
+        }
+    }
+}
+
+impl Prd {
+    /// 이 PRD가 어떤 컴포넌트로 해석되는지 단계별로 설명한다.
+    ///
+    /// 기본값, 언어/타입/infra 추가분, 명시적 오버라이드, 최종 개수를 포함한다.
+    pub fn effective_match_explanation(&self) -> String {
+        matcher::explain(self)
+    }
+
+    /// frontmatter용 YAML로 직렬화한다.
+    ///
+    /// 필드 순서는 스키마 순서를 따르고 `None`인 필드는 생략한다.
+    /// `kael init`이 PRD 파일을 쓸 때 사용한다. `upgrade-prd`는 모르는 키를 보존해야 하므로
+    /// `Prd`가 아닌 원본 YAML 값을 직접 직렬화한다.
+    pub fn to_yaml(&self) -> Result<String> {
+        Ok(serde_yaml_ng::to_string(self)?)
+    }
+
+    /// `registry_paths`를 PRD 파일이 있는 디렉토리 기준으로 해석한다.
+    pub fn resolved_registry_paths(&self, prd_path: &Path) -> Vec<PathBuf> {
+        let dir = base_dir(prd_path);
+        self.registry_paths
+            .iter()
+            .flatten()
+            .map(|p| dir.join(p))
+            .collect()
+    }
+}
+
 // ── Parsed PRD (frontmatter + body) ─────────────────────────────────
 
 #[derive(Debug, Clone)]
@@ -88,71 +294,430 @@ pub struct PrdBody {
     pub sections: Vec<Section>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct Section {
     pub heading: String,
     pub level: u32,
+    /// 코드 블록을 제외한 본문 텍스트
     pub content: String,
+    /// 섹션 안의 fenced/indented 코드 블록 (문서 순서)
+    pub code_blocks: Vec<CodeBlock>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CodeBlock {
+    /// info string의 첫 단어 (```` ```rust ```` → `rust`). 없으면 `None`.
+    pub language: Option<String>,
+    /// 블록 원문 (마지막 줄바꿈 포함)
+    pub code: String,
+}
+
+impl PrdBody {
+    /// 헤딩이 일치하는 첫 번째 섹션을 반환한다. 대소문자는 구분하지 않는다.
+    pub fn section(&self, heading: &str) -> Option<&Section> {
+        self.sections
+            .iter()
+            .find(|s| s.heading.eq_ignore_ascii_case(heading))
+    }
+
+    /// 헤딩 수준이 `level`인 섹션들 (문서 순서)
+    pub fn sections_at_level(&self, level: u32) -> Vec<&Section> {
+        self.sections.iter().filter(|s| s.level == level).collect()
+    }
+
+    /// `headings`에 나열된 섹션만 문서 순서대로 고른다. `None`이면 전부 반환한다.
+    pub fn select(&self, headings: Option<&[String]>) -> Vec<&Section> {
+        match headings {
+            Some(headings) => self
+                .sections
+                .iter()
+                .filter(|s| headings.iter().any(|h| h == &s.heading))
+                .collect(),
+            None => self.sections.iter().collect(),
+        }
+    }
 }
 
 // ── Public API ──────────────────────────────────────────────────────
 
 /// PRD.md 파일을 읽고 파싱하여 `ParsedPrd`를 반환한다.
+///
+/// frontmatter의 `include: [stack.yaml, ...]`는 PRD 파일 기준 상대 경로로 해석되며,
+/// 포함된 YAML 위에 PRD 자신의 값이 덮어씌워진 뒤 역직렬화된다.
 pub fn parse_prd_file(path: &Path) -> Result<ParsedPrd> {
     let content = std::fs::read_to_string(path)?;
-    parse_prd(&content)
+    let (format, source, body_str) = extract_frontmatter(&content)?;
+    let line_offset = frontmatter_line_offset(&content);
+
+    let value = frontmatter_value(format, source, line_offset)?;
+    let frontmatter = if value.get(INCLUDE_KEY).is_some() {
+        // 병합한 값에는 원본 위치가 없으므로 줄 번호 없이 보고한다
+        let mut chain = vec![canonical(path)];
+        let merged = resolve_includes(value, base_dir(path), &mut chain)?;
+        deserialize_prd(format, merged)?
+    } else {
+        deserialize_source(format, source, value, line_offset)?
+    };
+    let body = parse_body(body_str);
+    Ok(ParsedPrd { frontmatter, body })
 }
 
 /// PRD 문자열을 파싱하여 `ParsedPrd`를 반환한다.
+///
+/// 기준 디렉토리가 없으므로 `include`는 지원하지 않는다. (`parse_prd_file` 사용)
 pub fn parse_prd(content: &str) -> Result<ParsedPrd> {
-    let (yaml_str, body_str) = extract_frontmatter(content)?;
-    let frontmatter = parse_frontmatter(yaml_str)?;
+    let (format, source, body_str) = extract_frontmatter(content)?;
+    let frontmatter = parse_frontmatter(format, source, frontmatter_line_offset(content))?;
     let body = parse_body(body_str);
     Ok(ParsedPrd { frontmatter, body })
 }
 
 // ── Internal helpers ────────────────────────────────────────────────
 
-/// `---` 구분자로 YAML frontmatter와 markdown body를 분리한다.
-fn extract_frontmatter(content: &str) -> Result<(&str, &str)> {
-    let trimmed = content.trim_start();
+/// frontmatter 구분자 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrontmatterFormat {
+    /// `---`로 감싼 YAML
+    Yaml,
+    /// `+++`로 감싼 TOML
+    Toml,
+}
+
+impl FrontmatterFormat {
+    fn delimiter(self) -> &'static str {
+        match self {
+            FrontmatterFormat::Yaml => "---",
+            FrontmatterFormat::Toml => "+++",
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            FrontmatterFormat::Yaml => "YAML",
+            FrontmatterFormat::Toml => "TOML",
+        }
+    }
+}
+
+/// `---`(YAML) 또는 `+++`(TOML) 구분자로 frontmatter와 markdown body를 분리한다.
+///
+/// 여는 구분자 앞의 빈 줄, HTML 주석, PRD 주석 줄(`is_prd_comment`)은 건너뛴다.
+/// 닫는 구분자는 그 줄에 구분자만 있을 때만 인정하므로, 문자열 안의 `+++`는 무시된다.
+pub(crate) fn extract_frontmatter(content: &str) -> Result<(FrontmatterFormat, &str, &str)> {
+    let trimmed = skip_preamble(content);
+
+    let format = [FrontmatterFormat::Yaml, FrontmatterFormat::Toml]
+        .into_iter()
+        .find(|f| trimmed.starts_with(f.delimiter()))
+        .ok_or_else(|| KaelError::Prd {
+            message: "PRD must start with frontmatter (--- for YAML or +++ for TOML)".into(),
+        })?;
+    let delimiter = format.delimiter();
+
+    // 여는 구분자 줄 다음부터 구분자만 있는 줄을 찾는다
+    let after_first = &trimmed[delimiter.len()..];
+    let mut offset = 0;
+    let mut closing = None;
+    for line in after_first.split_inclusive('\n') {
+        if offset > 0 && line.trim_end() == delimiter {
+            closing = Some((offset, offset + line.len()));
+            break;
+        }
+        offset += line.len();
+    }
+    let (start, end) = closing.ok_or_else(|| KaelError::Prd {
+        message: format!("Missing closing frontmatter delimiter ({delimiter})"),
+    })?;
+
+    let frontmatter = &after_first[..start];
+    let body_str = &after_first[end..];
+    Ok((format, frontmatter, body_str))
+}
+
+/// frontmatter 문자열의 1번째 줄(여는 구분자 줄)이 파일에서 몇 줄 뒤에 있는지
+///
+/// `skip_preamble`이 건너뛴 줄 수와 같다. 파서가 알려준 줄 번호에 더하면 파일 기준이 된다.
+fn frontmatter_line_offset(content: &str) -> usize {
+    let skipped = content.len() - skip_preamble(content).len();
+    content[..skipped].matches('\n').count()
+}
+
+/// frontmatter 앞에서 `skip_preamble`이 건너뛰는 부분(주석과 빈 줄)
+pub(crate) fn preamble(content: &str) -> &str {
+    &content[..content.len() - skip_preamble(content).len()]
+}
+
+/// 첫 번째 내용 줄 전까지의 빈 줄, HTML 주석(`<!-- … -->`), PRD 주석 줄을 잘라낸다.
+fn skip_preamble(content: &str) -> &str {
+    let mut rest = content.trim_start_matches('\u{feff}');
+    loop {
+        let trimmed = rest.trim_start();
+        if let Some(comment) = trimmed.strip_prefix("<!--") {
+            match comment.find("-->") {
+                Some(end) => rest = &comment[end + 3..],
+                // 닫히지 않은 주석은 내용으로 본다
+                None => return trimmed,
+            }
+            continue;
+        }
+        let (line, next) = rest.split_once('\n').unwrap_or((rest, ""));
+        if !line.trim().is_empty() && !is_prd_comment(line) {
+            return trimmed;
+        }
+        if next.is_empty() {
+            return next;
+        }
+        rest = next;
+    }
+}
+
+/// frontmatter 앞에 둘 수 있는 `#` 주석 줄인지
+///
+/// 들여쓴 `#` 줄, `#` 뒤에 공백이 없는 줄(`#!`, `#note`), 생성 도구의 출처 표기
+/// (`# generated …`)만 주석이다. 그 밖의 `# Title`은 Markdown 제목이므로 내용으로 본다.
+fn is_prd_comment(line: &str) -> bool {
+    let line = line.trim_end();
+    if line.starts_with(char::is_whitespace) {
+        return line.trim_start().starts_with('#');
+    }
+    let Some(text) = line.strip_prefix('#') else {
+        return false;
+    };
+    let heading = text.trim_start_matches('#');
+    if !heading.is_empty() && !heading.starts_with(' ') {
+        return true;
+    }
+    heading.trim_start().to_lowercase().starts_with("generated")
+}
 
-    if !trimmed.starts_with("---") {
+/// frontmatter 문자열을 `Prd` 구조체로 역직렬화한다.
+fn parse_frontmatter(format: FrontmatterFormat, source: &str, line_offset: usize) -> Result<Prd> {
+    let value = frontmatter_value(format, source, line_offset)?;
+    if value.get(INCLUDE_KEY).is_some() {
         return Err(KaelError::Prd {
-            message: "PRD must start with YAML frontmatter (---)".into(),
+            message: "`include` is only supported when parsing a PRD file".into(),
         });
     }
+    deserialize_source(format, source, value, line_offset)
+}
+
+/// frontmatter를 형식에 맞게 파싱해 공통 YAML 값으로 만든다. (include 병합이 이 값 위에서 동작)
+fn frontmatter_value(
+    format: FrontmatterFormat,
+    source: &str,
+    line_offset: usize,
+) -> Result<serde_yaml_ng::Value> {
+    match format {
+        FrontmatterFormat::Yaml => {
+            serde_yaml_ng::from_str(source).map_err(|e| yaml_error(&e, line_offset))
+        }
+        FrontmatterFormat::Toml => {
+            let value: toml::Value = toml::from_str(source).map_err(|e| format_error(format, e))?;
+            serde_yaml_ng::to_value(value).map_err(|e| format_error(format, e))
+        }
+    }
+}
+
+/// include가 없으면 YAML은 원문에서 바로 역직렬화해 에러 위치를 살린다.
+fn deserialize_source(
+    format: FrontmatterFormat,
+    source: &str,
+    value: serde_yaml_ng::Value,
+    line_offset: usize,
+) -> Result<Prd> {
+    match format {
+        FrontmatterFormat::Yaml => {
+            serde_yaml_ng::from_str(source).map_err(|e| yaml_error(&e, line_offset))
+        }
+        FrontmatterFormat::Toml => deserialize_prd(format, value),
+    }
+}
+
+fn deserialize_prd(format: FrontmatterFormat, value: serde_yaml_ng::Value) -> Result<Prd> {
+    match format {
+        FrontmatterFormat::Yaml => serde_yaml_ng::from_value(value).map_err(|e| yaml_error(&e, 0)),
+        // TOML 입력의 필드 에러가 YAML 에러로 보이지 않도록 형식을 밝힌다
+        FrontmatterFormat::Toml => {
+            serde_yaml_ng::from_value(value).map_err(|e| format_error(format, e))
+        }
+    }
+}
+
+/// YAML 에러를 읽기 쉬운 `KaelError::Prd`로 바꾼다.
+///
+/// 위치가 있으면 `line_offset`을 더해 파일 기준 줄/열을 붙인다.
+fn yaml_error(err: &serde_yaml_ng::Error, line_offset: usize) -> KaelError {
+    let raw = err.to_string();
+    let location = err.location();
+    // Display에 붙는 frontmatter 기준 위치는 떼고 파일 기준으로 다시 붙인다
+    let detail = match location {
+        Some(_) => raw
+            .rsplit_once(" at line ")
+            .map_or(raw.as_str(), |(d, _)| d),
+        None => raw.as_str(),
+    };
+    let mut message = explain_yaml_error(detail);
+    if let Some(location) = location {
+        message.push_str(&format!(
+            " (line {}, column {})",
+            location.line() + line_offset,
+            location.column()
+        ));
+    }
+    KaelError::Prd { message }
+}
 
-    // 첫 번째 "---" 이후에서 두 번째 "---"를 찾는다
-    let after_first = &trimmed[3..];
-    let closing = after_first.find("\n---").ok_or(KaelError::Prd {
-        message: "Missing closing frontmatter delimiter (---)".into(),
+/// 자주 나오는 구조 문제(필수 필드 누락, 모르는 값)에 안내를 덧붙인다.
+fn explain_yaml_error(detail: &str) -> String {
+    if let Some(field) = detail
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.strip_suffix('`'))
+    {
+        let hint = match field {
+            "name" => "every PRD needs a project `name`",
+            "stack" => "add a `stack:` block with at least `language`",
+            "type" => "add `type:` (see `kael schema values`)",
+            _ => return format!("Invalid frontmatter: {detail}"),
+        };
+        return format!("missing field `{field}`: {hint}");
+    }
+    // 예: "stack.language: unknown variant `cobol`, expected one of `rust`, `go`"
+    if let Some((field, rest)) = detail.split_once(": unknown variant `") {
+        if let Some((value, expected)) = rest.split_once("`, expected one of ") {
+            return format!(
+                "unknown variant `{value}` for `{field}`; expected one of: {} (see `kael schema values`)",
+                expected.replace('`', "")
+            );
+        }
+    }
+    format!("Invalid frontmatter: {detail}")
+}
+
+fn format_error(format: FrontmatterFormat, err: impl std::fmt::Display) -> KaelError {
+    KaelError::Prd {
+        message: format!("Invalid {} frontmatter: {err}", format.name()),
+    }
+}
+
+const INCLUDE_KEY: &str = "include";
+
+/// `include` 목록을 재귀적으로 읽어 하나의 YAML 매핑으로 병합한다.
+///
+/// 포함된 파일들이 먼저(선언 순서대로) 깔리고, 포함한 쪽의 값이 위에 덮어쓴다.
+/// 매핑은 깊게 병합되고 시퀀스/스칼라는 통째로 교체된다.
+fn resolve_includes(
+    mut value: serde_yaml_ng::Value,
+    dir: &Path,
+    chain: &mut Vec<PathBuf>,
+) -> Result<serde_yaml_ng::Value> {
+    let includes = match value.as_mapping_mut() {
+        Some(map) => map.remove(INCLUDE_KEY),
+        None => None,
+    };
+    let Some(includes) = includes else {
+        return Ok(value);
+    };
+
+    let paths: Vec<String> = serde_yaml_ng::from_value(includes).map_err(|_| KaelError::Prd {
+        message: "`include` must be a list of file paths".into(),
     })?;
 
-    let yaml_str = &after_first[..closing];
-    let rest = &after_first[closing + 4..]; // skip "\n---"
+    let mut merged = serde_yaml_ng::Value::Mapping(Default::default());
+    for relative in paths {
+        let path = dir.join(&relative);
+        if !path.exists() {
+            return Err(KaelError::Prd {
+                message: format!("Included file not found: {}", path.display()),
+            });
+        }
+        let key = canonical(&path);
+        if chain.contains(&key) {
+            return Err(KaelError::Prd {
+                message: format!("Include cycle detected at {}", path.display()),
+            });
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        let included: serde_yaml_ng::Value = serde_yaml_ng::from_str(&content)?;
+        chain.push(key);
+        let included = resolve_includes(included, base_dir(&path), chain)?;
+        chain.pop();
 
-    // body는 closing --- 뒤의 줄바꿈 이후부터
-    let body_str = rest.strip_prefix('\n').unwrap_or(rest);
+        // 빈 include 파일은 무시
+        if !included.is_null() {
+            merge_yaml(&mut merged, included);
+        }
+    }
 
-    Ok((yaml_str, body_str))
+    merge_yaml(&mut merged, value);
+    Ok(merged)
 }
 
-/// YAML frontmatter 문자열을 `Prd` 구조체로 역직렬화한다.
-fn parse_frontmatter(yaml_str: &str) -> Result<Prd> {
-    let prd: Prd = serde_yaml_ng::from_str(yaml_str)?;
-    Ok(prd)
+/// `overlay`의 값을 `base` 위에 덮어쓴다. 매핑끼리는 키 단위로 재귀 병합한다.
+fn merge_yaml(base: &mut serde_yaml_ng::Value, overlay: serde_yaml_ng::Value) {
+    match (base, overlay) {
+        (serde_yaml_ng::Value::Mapping(base_map), serde_yaml_ng::Value::Mapping(overlay_map)) => {
+            for (key, value) in overlay_map {
+                match base_map.get_mut(&key) {
+                    Some(existing) => merge_yaml(existing, value),
+                    None => {
+                        base_map.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+fn base_dir(path: &Path) -> &Path {
+    path.parent().unwrap_or_else(|| Path::new("."))
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// kael이 생성 파일 맨 앞에 붙이는 배너 문구 (대소문자 무시)
+pub const BANNER_MARKER: &str = "generated by kael";
+
+/// 본문 맨 앞의 kael 생성 배너를 제거한다.
+///
+/// `<!-- Generated by kael ... -->` 주석과 `# Generated by kael ...` 줄을 배너로 본다.
+/// 배너가 섹션으로 잡히거나 재생성할 때마다 쌓이지 않도록 재파싱 전에 호출한다.
+pub fn strip_banner(markdown: &str) -> &str {
+    let mut rest = markdown;
+    loop {
+        // 앞의 빈 줄을 건너뛴 뒤 HTML 주석은 `-->`까지(여러 줄이어도), `#` 줄은 그 줄만 본다
+        let trimmed = rest.trim_start();
+        let (block, next) = if trimmed.starts_with("<!--") {
+            match trimmed.find("-->") {
+                Some(end) => trimmed.split_at(end + 3),
+                None => (trimmed, ""),
+            }
+        } else if trimmed.starts_with('#') {
+            trimmed.split_once('\n').unwrap_or((trimmed, ""))
+        } else {
+            return rest;
+        };
+        if !block.to_lowercase().contains(BANNER_MARKER) {
+            return rest;
+        }
+        rest = next;
+    }
 }
 
 /// Markdown body를 헤딩별 섹션으로 파싱한다.
 fn parse_body(markdown: &str) -> PrdBody {
-    let parser = Parser::new(markdown);
+    let parser = Parser::new(strip_banner(markdown));
     let mut sections = Vec::new();
     let mut current_heading: Option<(String, u32)> = None;
     let mut current_content = String::new();
     let mut in_heading = false;
     let mut heading_text = String::new();
     let mut heading_level = 0u32;
+    let mut code_blocks = Vec::new();
+    let mut current_code: Option<CodeBlock> = None;
 
     for event in parser {
         match event {
@@ -163,6 +728,7 @@ fn parse_body(markdown: &str) -> PrdBody {
                         heading,
                         level,
                         content: current_content.trim().to_string(),
+                        code_blocks: std::mem::take(&mut code_blocks),
                     });
                     current_content.clear();
                 }
@@ -174,6 +740,28 @@ fn parse_body(markdown: &str) -> PrdBody {
                 in_heading = false;
                 current_heading = Some((heading_text.clone(), heading_level));
             }
+            Event::Start(Tag::CodeBlock(kind)) if current_heading.is_some() => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split_whitespace()
+                        .next()
+                        .map(|lang| lang.trim_matches(['{', '}']).to_string())
+                        .filter(|lang| !lang.is_empty()),
+                    CodeBlockKind::Indented => None,
+                };
+                current_code = Some(CodeBlock {
+                    language,
+                    code: String::new(),
+                });
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                code_blocks.extend(current_code.take());
+            }
+            Event::Text(text) if current_code.is_some() => {
+                if let Some(block) = current_code.as_mut() {
+                    block.code.push_str(&text);
+                }
+            }
             Event::Text(text) | Event::Code(text) => {
                 if in_heading {
                     heading_text.push_str(&text);
@@ -181,10 +769,8 @@ fn parse_body(markdown: &str) -> PrdBody {
                     current_content.push_str(&text);
                 }
             }
-            Event::SoftBreak | Event::HardBreak => {
-                if !in_heading && current_heading.is_some() {
-                    current_content.push('\n');
-                }
+            Event::SoftBreak | Event::HardBreak if !in_heading && current_heading.is_some() => {
+                current_content.push('\n');
             }
             _ => {}
         }
@@ -196,6 +782,7 @@ fn parse_body(markdown: &str) -> PrdBody {
             heading,
             level,
             content: current_content.trim().to_string(),
+            code_blocks,
         });
     }
 
@@ -314,6 +901,28 @@ type: api
         assert!(fm.team.is_none());
     }
 
+    #[test]
+    fn to_yaml_round_trips() {
+        for source in [FULL_PRD, MINIMAL_PRD] {
+            let prd = parse_prd(source).unwrap().frontmatter;
+            let yaml = prd.to_yaml().unwrap();
+            let reparsed = parse_prd(&format!("---\n{yaml}---\n")).unwrap().frontmatter;
+            assert_eq!(prd, reparsed);
+        }
+    }
+
+    #[test]
+    fn to_yaml_omits_none_and_keeps_schema_order() {
+        let prd = parse_prd(MINIMAL_PRD).unwrap().frontmatter;
+        let yaml = prd.to_yaml().unwrap();
+        assert!(!yaml.contains("null"));
+        assert!(!yaml.contains("description"));
+        let name = yaml.find("name:").unwrap();
+        let stack = yaml.find("stack:").unwrap();
+        let project_type = yaml.find("type:").unwrap();
+        assert!(name < stack && stack < project_type);
+    }
+
     #[test]
     fn parse_body_sections() {
         let parsed = parse_prd(FULL_PRD).unwrap();
@@ -328,6 +937,49 @@ type: api
         assert_eq!(sections[2].level, 2);
     }
 
+    #[test]
+    fn section_lookup_by_heading() {
+        let parsed = parse_prd(FULL_PRD).unwrap();
+        let architecture = parsed.body.section("Architecture").unwrap();
+
+        assert_eq!(architecture.level, 2);
+        assert_eq!(
+            architecture.content,
+            "The system uses a layered architecture."
+        );
+        assert!(parsed.body.section("Deployment").is_none());
+        assert_eq!(parsed.body.section("architecture"), Some(architecture));
+        assert_eq!(parsed.body.section("GOALS").unwrap().heading, "Goals");
+    }
+
+    #[test]
+    fn sections_by_heading_level() {
+        let parsed = parse_prd(FULL_PRD).unwrap();
+        let headings = |level| -> Vec<_> {
+            parsed
+                .body
+                .sections_at_level(level)
+                .iter()
+                .map(|s| s.heading.clone())
+                .collect()
+        };
+        assert_eq!(headings(1), ["My Project"]);
+        assert_eq!(headings(2), ["Architecture", "Goals"]);
+        assert!(headings(3).is_empty());
+    }
+
+    #[test]
+    fn select_sections_filters_in_document_order() {
+        let parsed = parse_prd(FULL_PRD).unwrap();
+        let all = parsed.body.select(None);
+        assert_eq!(all.len(), 3);
+
+        let wanted = vec!["Goals".to_string(), "Architecture".to_string()];
+        let picked = parsed.body.select(Some(&wanted));
+        let headings: Vec<_> = picked.iter().map(|s| s.heading.as_str()).collect();
+        assert_eq!(headings, ["Architecture", "Goals"]);
+    }
+
     #[test]
     fn missing_name_errors() {
         let prd = r#"---
@@ -357,6 +1009,169 @@ type: cli
         assert!(err.to_string().contains("frontmatter"));
     }
 
+    #[test]
+    fn leading_blank_line_before_frontmatter() {
+        let parsed = parse_prd(&format!("\n\n{MINIMAL_PRD}")).unwrap();
+        assert_eq!(parsed.frontmatter.name, "minimal");
+    }
+
+    #[test]
+    fn leading_comment_before_frontmatter() {
+        let prd = format!("# generated by kael-import v1\n  # do not edit\n\n{FULL_PRD}");
+        let parsed = parse_prd(&prd).unwrap();
+        assert_eq!(parsed.frontmatter.name, "my-project");
+        assert!(!parsed.body.sections.is_empty());
+    }
+
+    #[test]
+    fn html_comment_before_frontmatter() {
+        let prd = format!("<!--\n  exported from the wiki\n-->\n\n{FULL_PRD}");
+        let parsed = parse_prd(&prd).unwrap();
+        assert_eq!(parsed.frontmatter.name, "my-project");
+        assert_eq!(preamble(&prd), "<!--\n  exported from the wiki\n-->\n\n");
+    }
+
+    #[test]
+    fn markdown_heading_is_not_a_preamble_comment() {
+        let prd = format!("# My project\n\n{FULL_PRD}");
+        assert!(parse_prd(&prd).is_err());
+        assert!(is_prd_comment("#!kael"));
+        assert!(is_prd_comment("  # indented"));
+        assert!(!is_prd_comment("## Overview"));
+    }
+
+    #[test]
+    fn code_blocks_are_kept_apart_from_prose() {
+        let prd = "---\nname: c\nstack:\n  language: rust\ntype: cli\n---\n## Usage\n\nRun it like this:\n\n```rust\nfn main() {\n    kael::run();\n}\n```\n\nThen check the output.\n";
+        let parsed = parse_prd(prd).unwrap();
+        let usage = parsed.body.section("Usage").unwrap();
+
+        assert_eq!(usage.content, "Run it like this:Then check the output.");
+        assert_eq!(
+            usage.code_blocks,
+            vec![CodeBlock {
+                language: Some("rust".into()),
+                code: "fn main() {\n    kael::run();\n}\n".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn banner_does_not_become_a_section() {
+        let plain = parse_prd(FULL_PRD).unwrap();
+        let (front, body) = FULL_PRD.rsplit_once("---\n").unwrap();
+        for banner in [
+            "<!-- Generated by kael v0.4. Do not edit. -->\n",
+            "# Generated by kael — do not edit\n\n",
+            "<!--\n  Generated by kael\n  Regenerate with `kael generate`\n-->\n",
+            "\n\n<!--\nGenerated by kael\n-->\n# Generated by kael\n",
+        ] {
+            let prd = format!("{front}---\n{banner}{body}");
+            let parsed = parse_prd(&prd).unwrap();
+            assert_eq!(parsed.body.sections, plain.body.sections, "{banner:?}");
+            // 주석은 섹션이 되지 않으므로 배너가 실제로 잘려 나갔는지 따로 확인한다
+            assert_eq!(
+                strip_banner(&format!("{banner}{body}")).trim_start(),
+                body.trim_start(),
+                "{banner:?}"
+            );
+        }
+        // 배너가 아닌 주석은 남긴다
+        let note = "<!--\n  draft\n-->\n## Goals\n";
+        assert_eq!(strip_banner(note), note);
+    }
+
+    const TOML_PRD: &str = r#"+++
+name = "toml-project"
+description = """
+Uses +++ inline, and
++++ at the start of a line
+"""
+type = "api"
+
+[stack]
+language = "go"
+infra = ["docker"]
++++
+
+## Overview
+
+Body after TOML frontmatter.
+"#;
+
+    #[test]
+    fn language_accepts_scalar_or_list() {
+        let scalar = parse_prd("---\nname: s\nstack:\n  language: rust\ntype: cli\n---\n").unwrap();
+        assert_eq!(scalar.frontmatter.stack.language, Language::Rust);
+        assert_eq!(scalar.frontmatter.stack.language.joined(), "rust");
+        assert!(scalar
+            .frontmatter
+            .to_yaml()
+            .unwrap()
+            .contains("language: rust\n"));
+
+        let list = parse_prd(
+            "---\nname: p\nstack:\n  language: [rust, typescript, rust]\ntype: web\n---\n",
+        )
+        .unwrap();
+        let languages = &list.frontmatter.stack.language;
+        assert_eq!(
+            languages.iter().collect::<Vec<_>>(),
+            [&Language::Rust, &Language::Typescript]
+        );
+        assert_eq!(languages.primary(), &Language::Rust);
+        assert_eq!(languages.joined(), "rust, typescript");
+        assert_eq!(format!("{languages:?}"), "[Rust, Typescript]");
+        let reparsed = parse_prd(&format!(
+            "---\n{}---\n",
+            list.frontmatter.to_yaml().unwrap()
+        ));
+        assert_eq!(&reparsed.unwrap().frontmatter.stack.language, languages);
+
+        let toml = parse_prd("+++\nname = \"t\"\ntype = \"web\"\n\n[stack]\nlanguage = [\"go\", \"typescript\"]\n+++\n").unwrap();
+        assert_eq!(toml.frontmatter.stack.language.joined(), "go, typescript");
+
+        let empty = parse_prd("---\nname: e\nstack:\n  language: []\ntype: cli\n---\n");
+        assert!(empty.is_err());
+        let unknown =
+            parse_prd("---\nname: u\nstack:\n  language: [rust, cobol]\ntype: cli\n---\n")
+                .unwrap_err();
+        assert!(
+            unknown.to_string().contains("unknown variant `cobol`"),
+            "{unknown}"
+        );
+    }
+
+    #[test]
+    fn parse_toml_frontmatter() {
+        let parsed = parse_prd(TOML_PRD).unwrap();
+        let fm = &parsed.frontmatter;
+        assert_eq!(fm.name, "toml-project");
+        assert_eq!(fm.stack.language, Language::Go);
+        assert_eq!(fm.project_type, ProjectType::Api);
+        assert!(fm
+            .description
+            .as_deref()
+            .unwrap()
+            .contains("+++ at the start"));
+        assert_eq!(parsed.body.sections.len(), 1);
+        assert_eq!(parsed.body.sections[0].heading, "Overview");
+    }
+
+    #[test]
+    fn toml_errors_name_the_format() {
+        let missing_name = "+++\ntype = \"cli\"\n[stack]\nlanguage = \"rust\"\n+++\n";
+        let err = parse_prd(missing_name).unwrap_err();
+        assert!(err.to_string().contains("Invalid TOML frontmatter"));
+        assert!(err.to_string().contains("missing field"));
+
+        let syntax = parse_prd("+++\nname = \n+++\n").unwrap_err();
+        assert!(syntax.to_string().contains("Invalid TOML frontmatter"));
+
+        let unclosed = parse_prd("+++\nname = \"x\"\n").unwrap_err();
+        assert!(unclosed.to_string().contains("(+++)"));
+    }
+
     #[test]
     fn unclosed_frontmatter_errors() {
         let prd = "---\nname: test\n";
@@ -370,12 +1185,77 @@ type: cli
         assert!(parsed.body.sections.is_empty());
     }
 
+    #[test]
+    fn explanation_mentions_language_skills() {
+        let parsed = parse_prd(MINIMAL_PRD).unwrap();
+        let explanation = parsed.frontmatter.effective_match_explanation();
+
+        assert!(explanation.contains("Language (python)"));
+        assert!(explanation.contains("python/fastapi"));
+        assert!(explanation.contains("Base defaults"));
+        assert!(explanation.contains("Total:"));
+    }
+
+    #[test]
+    fn include_merges_stack_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("stack.yaml"),
+            "stack:\n  language: go\n  framework: gin\ntype: api\n",
+        )
+        .unwrap();
+        let prd_path = dir.path().join("PRD.md");
+        std::fs::write(
+            &prd_path,
+            "---\nname: composed\ninclude: [stack.yaml]\ntype: cli\n---\n\n# Composed\n",
+        )
+        .unwrap();
+
+        let parsed = parse_prd_file(&prd_path).unwrap();
+        let fm = &parsed.frontmatter;
+        assert_eq!(fm.name, "composed");
+        assert_eq!(fm.stack.language, Language::Go);
+        assert_eq!(fm.stack.framework.as_deref(), Some("gin"));
+        // PRD 자신의 값이 include보다 우선
+        assert_eq!(fm.project_type, ProjectType::Cli);
+        assert_eq!(parsed.body.sections[0].heading, "Composed");
+    }
+
+    #[test]
+    fn include_missing_file_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let prd_path = dir.path().join("PRD.md");
+        std::fs::write(&prd_path, "---\nname: x\ninclude: [nope.yaml]\n---\n").unwrap();
+
+        let err = parse_prd_file(&prd_path).unwrap_err();
+        assert!(err.to_string().contains("Included file not found"));
+    }
+
+    #[test]
+    fn include_cycle_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.yaml"), "include: [b.yaml]\n").unwrap();
+        std::fs::write(dir.path().join("b.yaml"), "include: [a.yaml]\n").unwrap();
+        let prd_path = dir.path().join("PRD.md");
+        std::fs::write(&prd_path, "---\nname: x\ninclude: [a.yaml]\n---\n").unwrap();
+
+        let err = parse_prd_file(&prd_path).unwrap_err();
+        assert!(err.to_string().contains("cycle"));
+    }
+
+    #[test]
+    fn include_requires_file_path() {
+        let prd = "---\nname: x\ninclude: [stack.yaml]\n---\n";
+        let err = parse_prd(prd).unwrap_err();
+        assert!(err.to_string().contains("include"));
+    }
+
     #[test]
     fn invalid_language_errors() {
         let prd = r#"---
 name: "test"
 stack:
-  language: java
+  language: cobol
 type: cli
 ---
 "#;
@@ -383,16 +1263,86 @@ type: cli
         assert!(err.to_string().contains("unknown variant"));
     }
 
+    #[test]
+    fn unknown_language_reports_file_line() {
+        let prd =
+            "# generated by a tool\n\n---\nname: test\nstack:\n  language: cobol\ntype: cli\n---\n";
+        let err = parse_prd(prd).unwrap_err().to_string();
+        assert!(
+            err.contains("unknown variant `cobol` for `stack.language`"),
+            "{err}"
+        );
+        assert!(err.contains("expected one of: rust, typescript"), "{err}");
+        // 파일의 6번째 줄 `  language: cobol`
+        assert!(err.ends_with("(line 6, column 13)"), "{err}");
+
+        let missing = parse_prd("---\nstack:\n  language: rust\ntype: cli\n---\n").unwrap_err();
+        assert!(missing
+            .to_string()
+            .contains("missing field `name`: every PRD needs a project `name`"));
+    }
+
+    #[test]
+    fn unknown_language_line_in_prd_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("PRD.md");
+        std::fs::write(
+            &path,
+            "---\nname: t\ntype: cli\nstack:\n  language: cobol\n---\n",
+        )
+        .unwrap();
+        let err = parse_prd_file(&path).unwrap_err().to_string();
+        assert!(err.ends_with("(line 5, column 13)"), "{err}");
+    }
+
     #[test]
     fn invalid_type_errors() {
         let prd = r#"---
 name: "test"
 stack:
   language: rust
-type: desktop
+type: embedded
 ---
 "#;
         let err = parse_prd(prd).unwrap_err();
         assert!(err.to_string().contains("unknown variant"));
     }
+
+    #[test]
+    fn csharp_language_parses() {
+        let prd = "---\nname: svc\nstack:\n  language: csharp\ntype: api\n---\n";
+        let parsed = parse_prd(prd).unwrap();
+        assert_eq!(parsed.frontmatter.stack.language, Language::Csharp);
+    }
+
+    #[test]
+    fn ruby_language_parses() {
+        let prd = "---\nname: shop\nstack:\n  language: ruby\n  framework: rails\ntype: web\n---\n";
+        let parsed = parse_prd(prd).unwrap();
+        assert_eq!(parsed.frontmatter.stack.language, Language::Ruby);
+        assert_eq!(parsed.frontmatter.stack.framework.as_deref(), Some("rails"));
+    }
+
+    #[test]
+    fn cli_language_values_match_prd_names() {
+        use clap::ValueEnum;
+        // `init --language`에 쓰는 이름은 PRD의 `stack.language`와 같다
+        for language in Language::ALL {
+            let value = language.to_possible_value().unwrap();
+            assert_eq!(value.get_name(), language.as_str());
+        }
+    }
+
+    #[test]
+    fn desktop_type_parses() {
+        let prd = r#"---
+name: "test"
+stack:
+  language: typescript
+type: desktop
+---
+"#;
+        let parsed = parse_prd(prd).unwrap();
+        assert_eq!(parsed.frontmatter.project_type, ProjectType::Desktop);
+    }
 }
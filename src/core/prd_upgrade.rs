@@ -0,0 +1,167 @@
+use serde_yaml_ng::{Mapping, Value};
+
+use crate::core::prd::{self, FrontmatterFormat};
+use crate::error::{KaelError, Result};
+
+/// 예전 스키마의 최상위 키 → 현재 스키마 경로
+const RENAMES: &[(&str, &[&str])] = &[
+    ("lang", &["stack", "language"]),
+    ("language", &["stack", "language"]),
+    ("framework", &["stack", "framework"]),
+    ("database", &["stack", "database"]),
+    ("infra", &["stack", "infra"]),
+    ("project_type", &["type"]),
+];
+
+/// 키 하나를 옮긴 기록
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChange {
+    pub from: &'static str,
+    /// 현재 스키마 경로 (`stack.language` 형식)
+    pub to: String,
+    /// 새 경로에 이미 값이 있어 예전 키를 버렸는지
+    pub dropped: bool,
+}
+
+/// `upgrade_prd` 결과
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrdUpgrade {
+    pub changes: Vec<KeyChange>,
+    /// 바뀐 PRD 전체 내용. 바뀐 것이 없으면 원본 그대로다.
+    pub content: String,
+}
+
+/// PRD의 예전 키를 현재 스키마 위치로 옮긴다.
+///
+/// 여는 구분자 앞의 주석과 markdown body는 그대로 두고 frontmatter만 다시 직렬화한다.
+/// frontmatter 안의 주석은 직렬화하면서 사라진다. YAML frontmatter만 지원한다.
+pub fn upgrade_prd(content: &str) -> Result<PrdUpgrade> {
+    let (format, source, body) = prd::extract_frontmatter(content)?;
+    if format != FrontmatterFormat::Yaml {
+        return Err(KaelError::Prd {
+            message: "upgrade-prd only supports YAML frontmatter (---)".into(),
+        });
+    }
+    let mut value: Value = serde_yaml_ng::from_str(source)?;
+    let Some(root) = value.as_mapping_mut() else {
+        return Err(KaelError::Prd {
+            message: "PRD frontmatter must be a mapping".into(),
+        });
+    };
+
+    let mut changes = Vec::new();
+    for (legacy, path) in RENAMES {
+        let Some(old) = root.remove(*legacy) else {
+            continue;
+        };
+        let dropped = !insert_path(root, legacy, path, old)?;
+        changes.push(KeyChange {
+            from: legacy,
+            to: path.join("."),
+            dropped,
+        });
+    }
+    if changes.is_empty() {
+        return Ok(PrdUpgrade {
+            changes,
+            content: content.to_string(),
+        });
+    }
+
+    let preamble = prd::preamble(content);
+    let frontmatter = serde_yaml_ng::to_string(&value)?;
+    Ok(PrdUpgrade {
+        changes,
+        content: format!("{preamble}---\n{frontmatter}---\n{body}"),
+    })
+}
+
+/// `path` 위치에 값을 넣는다. 이미 값이 있으면 건드리지 않고 `false`를 돌려준다.
+///
+/// 중간 키가 mapping이 아니면 값을 버리지 않도록 오류를 낸다.
+fn insert_path(root: &mut Mapping, legacy: &str, path: &[&str], value: Value) -> Result<bool> {
+    let Some((last, parents)) = path.split_last() else {
+        return Ok(false);
+    };
+    let mut current = root;
+    for (depth, key) in parents.iter().enumerate() {
+        let entry = current
+            .entry(Value::from(*key))
+            .or_insert_with(|| Value::Mapping(Mapping::new()));
+        let Some(mapping) = entry.as_mapping_mut() else {
+            return Err(KaelError::Prd {
+                message: format!(
+                    "cannot move `{legacy}` to `{}`: `{}` is not a mapping",
+                    path.join("."),
+                    path[..=depth].join(".")
+                ),
+            });
+        };
+        current = mapping;
+    }
+    if current.contains_key(*last) {
+        return Ok(false);
+    }
+    current.insert(Value::from(*last), value);
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::prd::{Language, ProjectType};
+
+    #[test]
+    fn scalar_parent_is_a_conflict_not_a_drop() {
+        let legacy = "---\nname: shop\nstack: ruby\nlang: ruby\n---\n";
+        let err = upgrade_prd(legacy).unwrap_err().to_string();
+        assert!(err.contains("`lang`"), "{err}");
+        assert!(err.contains("`stack` is not a mapping"), "{err}");
+    }
+
+    #[test]
+    fn legacy_keys_move_into_current_schema() {
+        let legacy = "# generated by the old tool\n---\nname: shop\nlang: ruby\nframework: rails\nproject_type: web\n---\n\n## Architecture\n\nMonolith.\n";
+        let upgraded = upgrade_prd(legacy).unwrap();
+
+        let moved: Vec<_> = upgraded
+            .changes
+            .iter()
+            .map(|c| (c.from, c.to.as_str(), c.dropped))
+            .collect();
+        assert_eq!(
+            moved,
+            [
+                ("lang", "stack.language", false),
+                ("framework", "stack.framework", false),
+                ("project_type", "type", false),
+            ]
+        );
+
+        let parsed = prd::parse_prd(&upgraded.content).unwrap();
+        assert_eq!(parsed.frontmatter.stack.language, Language::Ruby);
+        assert_eq!(parsed.frontmatter.stack.framework.as_deref(), Some("rails"));
+        assert_eq!(parsed.frontmatter.project_type, ProjectType::Web);
+        assert!(upgraded
+            .content
+            .starts_with("# generated by the old tool\n---\n"));
+        assert!(upgraded
+            .content
+            .ends_with("---\n\n## Architecture\n\nMonolith.\n"));
+    }
+
+    #[test]
+    fn current_prd_is_left_untouched() {
+        let current = "---\nname: t\n# keep me\nstack:\n  language: rust\ntype: cli\n---\n";
+        let upgraded = upgrade_prd(current).unwrap();
+        assert!(upgraded.changes.is_empty());
+        assert_eq!(upgraded.content, current);
+
+        // 새 경로에 값이 있으면 예전 키는 버린다
+        let both = "---\nname: t\nlang: go\nstack:\n  language: rust\ntype: cli\n---\n";
+        let upgraded = upgrade_prd(both).unwrap();
+        assert!(upgraded.changes[0].dropped);
+        let parsed = prd::parse_prd(&upgraded.content).unwrap();
+        assert_eq!(parsed.frontmatter.stack.language, Language::Rust);
+    }
+}
@@ -1,49 +1,368 @@
 use std::path::{Path, PathBuf};
 
+use crate::core::diff::{self, LineStats};
 use crate::core::generator::GeneratedOutput;
+use crate::core::manifest;
+use crate::core::registry::{self, ComponentKind};
 use crate::error::{KaelError, Result};
 
 /// `.claude/` 디렉토리에 생성된 설정을 기록한다.
+///
+/// 모든 파일을 실제로 기록하고 마지막으로 manifest를 남긴다. 반환 목록의 마지막이 manifest 경로다.
+/// `force`가 없으면 manifest를 포함해 기존 파일이 하나라도 있을 때 에러.
 pub fn write_output(base: &Path, output: &GeneratedOutput, force: bool) -> Result<Vec<PathBuf>> {
-    let claude_dir = base.join(".claude");
-    let mut written = Vec::new();
+    let manifest_path = base.join(manifest::MANIFEST_PATH);
+    if !force && manifest_path.exists() {
+        return Err(KaelError::FileExists {
+            path: manifest_path,
+        });
+    }
+    let mode = if force {
+        ApplyMode::Force
+    } else {
+        ApplyMode::Create
+    };
+    let options = ApplyOptions {
+        mode,
+        touch: true,
+        ..ApplyOptions::default()
+    };
+    let report = apply(base, output, options)?;
+    let mut written: Vec<PathBuf> = report.entries.into_iter().map(|(path, _)| path).collect();
+    written.extend(report.manifest);
+    Ok(written)
+}
 
-    // CLAUDE.md는 프로젝트 루트에 생성
-    write_file(&base.join("CLAUDE.md"), &output.claude_md, force)?;
-    written.push(base.join("CLAUDE.md"));
+// ── Apply ───────────────────────────────────────────────────────────
 
-    // settings.json
-    std::fs::create_dir_all(&claude_dir)?;
-    write_file(
-        &claude_dir.join("settings.json"),
-        &output.settings_json,
-        force,
-    )?;
-    written.push(claude_dir.join("settings.json"));
+/// 기존 파일과 충돌할 때의 처리 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ApplyMode {
+    /// 새 프로젝트 생성. 기존 파일이 하나라도 있으면 에러 (`init` 기본값)
+    Create,
+    /// 없는 파일은 생성, 같은 파일은 건너뜀 (`generate` 기본값)
+    ///
+    /// 내용이 다른 파일은 manifest 해시와 같으면(kael이 쓴 그대로면) 덮어쓰고,
+    /// 사용자가 고친 파일이면 에러를 낸다.
+    #[default]
+    Update,
+    /// 내용이 다른 파일도 덮어씀
+    Force,
+    /// 내용이 다른 파일은 보존하되, settings.json은 사용자 값을 유지한 채 새 키만 병합
+    Merge,
+    /// 없는 파일만 생성하고, 이미 있는 파일은 내용과 관계없이 건드리지 않음
+    OnlyNew,
+}
+
+/// 기록하는 파일의 줄바꿈
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    Crlf,
+}
 
-    // skills, agents, commands
-    for file in &output.skills {
-        let path = claude_dir.join(&file.relative_path);
-        ensure_parent(&path)?;
-        write_file(&path, &file.content, force)?;
-        written.push(path);
+/// 줄바꿈을 `line_ending`으로 통일하고 끝의 줄바꿈을 정확히 하나로 맞춘다. 빈 내용은 그대로 둔다.
+///
+/// 기록뿐 아니라 기존 파일과의 비교(`apply`, `kael diff`, `kael doctor`)도 이 결과로 한다.
+pub fn normalize(content: &str, line_ending: LineEnding) -> String {
+    let lf = content.replace("\r\n", "\n");
+    let trimmed = lf.trim_end_matches('\n');
+    if trimmed.is_empty() {
+        return String::new();
     }
+    match line_ending {
+        LineEnding::Lf => format!("{trimmed}\n"),
+        LineEnding::Crlf => format!("{}\r\n", trimmed.replace('\n', "\r\n")),
+    }
+}
 
-    for file in &output.agents {
-        let path = claude_dir.join(&file.relative_path);
-        ensure_parent(&path)?;
-        write_file(&path, &file.content, force)?;
-        written.push(path);
+/// `apply` 옵션
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ApplyOptions {
+    pub mode: ApplyMode,
+    /// 내용이 같은 파일도 다시 기록해 mtime을 갱신한다
+    pub touch: bool,
+    /// 새 결과에 없는 레지스트리 컴포넌트 파일을 `.claude/`에서 지운다.
+    ///
+    /// 레지스트리에 있는 이름의 파일만 대상이며, 사용자가 직접 추가한 파일은 지우지 않는다.
+    pub prune: bool,
+    pub line_ending: LineEnding,
+}
+
+impl From<ApplyMode> for ApplyOptions {
+    fn from(mode: ApplyMode) -> Self {
+        Self {
+            mode,
+            ..Self::default()
+        }
     }
+}
 
-    for file in &output.commands {
-        let path = claude_dir.join(&file.relative_path);
-        ensure_parent(&path)?;
-        write_file(&path, &file.content, force)?;
-        written.push(path);
+/// 파일 하나가 어떻게 처리되었는지
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteStatus {
+    /// 새로 생성됨
+    Created,
+    /// 내용이 달라 덮어씀. 이전 내용 대비 줄 변경량을 담는다.
+    Updated(LineStats),
+    /// 내용이 같아 건너뜀
+    Unchanged,
+    /// 내용이 같지만 `touch`로 다시 기록함
+    Touched,
+    /// `Merge`/`OnlyNew` 모드라 기존 파일을 보존함
+    Kept,
+    /// 기존 settings.json에 새 키를 병합함
+    Merged(LineStats),
+    /// 새 결과에 없는 레지스트리 컴포넌트라 `prune`으로 지움
+    Removed,
+}
+
+/// `apply` 결과. 파일별 처리 상태를 기록 순서대로 담는다.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApplyReport {
+    pub entries: Vec<(PathBuf, WriteStatus)>,
+    /// 이번에 갱신한 manifest 경로. 바뀐 파일이 없고 manifest가 이미 있으면 `None`.
+    pub manifest: Option<PathBuf>,
+}
+
+impl ApplyReport {
+    /// 실제로 디스크에 기록된 파일 수
+    pub fn written(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|(_, s)| {
+                !matches!(
+                    s,
+                    WriteStatus::Unchanged | WriteStatus::Kept | WriteStatus::Removed
+                )
+            })
+            .count()
     }
 
-    Ok(written)
+    /// `prune`으로 지운 파일 수
+    pub fn removed(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|(_, s)| *s == WriteStatus::Removed)
+            .count()
+    }
+
+    /// 기록하지 않은 파일 수 (unchanged + kept)
+    pub fn skipped(&self) -> usize {
+        self.entries.len() - self.written() - self.removed()
+    }
+}
+
+/// 생성 결과를 `mode`에 따라 기록한다. `init`/`generate`의 공통 기록 경로다.
+///
+/// 충돌 검사를 먼저 끝낸 뒤 기록하므로, 에러가 나면 아무 파일도 바뀌지 않는다.
+pub fn apply(
+    base: &Path,
+    output: &GeneratedOutput,
+    options: impl Into<ApplyOptions>,
+) -> Result<ApplyReport> {
+    let ApplyOptions {
+        mode,
+        touch,
+        prune,
+        line_ending,
+    } = options.into();
+
+    // 1단계: 계획 수립 (충돌 시 여기서 중단)
+    let user_authored = manifest::user_authored_paths(base);
+    let recorded = manifest::read(base).ok().flatten();
+    // manifest에 기록된 해시 그대로인 kael 관리 파일인지
+    let unmodified = |path: &Path, existing: &str| {
+        recorded.as_ref().is_some_and(|m| {
+            let sha = manifest::sha256_hex(existing.as_bytes());
+            m.files
+                .iter()
+                .any(|e| !e.user_authored && base.join(&e.path) == path && e.sha256 == sha)
+        })
+    };
+    let mut plan = Vec::new();
+    for (relative, content) in output.files() {
+        let path = base.join(&relative);
+        let content = normalize(content, line_ending);
+        let content = content.as_str();
+        // 사용자가 작성한 파일은 어떤 모드에서도 건드리지 않는다
+        if user_authored.contains(&path) || (mode == ApplyMode::OnlyNew && path.exists()) {
+            plan.push((path, Action::Skip(WriteStatus::Kept)));
+            continue;
+        }
+        let existing = std::fs::read_to_string(&path).ok();
+        let action = match existing {
+            None if path.exists() => {
+                // 읽을 수 없는 기존 파일 (바이너리 등)
+                match mode {
+                    ApplyMode::Force => Action::Write(content.to_string(), WriteStatus::Created),
+                    ApplyMode::Merge => Action::Skip(WriteStatus::Kept),
+                    _ => return Err(KaelError::FileExists { path }),
+                }
+            }
+            None => Action::Write(content.to_string(), WriteStatus::Created),
+            Some(_) if mode == ApplyMode::Create => {
+                return Err(KaelError::FileExists { path });
+            }
+            Some(existing) if existing == content => {
+                if touch {
+                    Action::Write(content.to_string(), WriteStatus::Touched)
+                } else {
+                    Action::Skip(WriteStatus::Unchanged)
+                }
+            }
+            Some(existing) => match mode {
+                ApplyMode::OnlyNew => Action::Skip(WriteStatus::Kept),
+                ApplyMode::Update if !unmodified(&path, &existing) => {
+                    return Err(KaelError::FileExists { path });
+                }
+                ApplyMode::Create => return Err(KaelError::FileExists { path }),
+                ApplyMode::Update | ApplyMode::Force => Action::Write(
+                    content.to_string(),
+                    WriteStatus::Updated(diff::line_stats(&existing, content)),
+                ),
+                ApplyMode::Merge => match merge_settings(&relative, &existing, content) {
+                    Some(merged) if merged == existing => Action::Skip(WriteStatus::Kept),
+                    Some(merged) => {
+                        let stats = diff::line_stats(&existing, &merged);
+                        Action::Write(merged, WriteStatus::Merged(stats))
+                    }
+                    None => Action::Skip(WriteStatus::Kept),
+                },
+            },
+        };
+        plan.push((path, action));
+    }
+
+    // 2단계: 기록
+    std::fs::create_dir_all(base.join(".claude"))?;
+    let mut report = ApplyReport::default();
+    for (path, action) in plan {
+        let status = match action {
+            Action::Write(content, status) => {
+                ensure_parent(&path)?;
+                write_file(&path, &content, true, line_ending)?;
+                status
+            }
+            Action::Skip(status) => status,
+        };
+        report.entries.push((path, status));
+    }
+
+    if prune {
+        for path in stale_components(base, output) {
+            std::fs::remove_file(&path)?;
+            report.entries.push((path, WriteStatus::Removed));
+        }
+    }
+
+    // 3단계: manifest는 항상 마지막에 기록한다
+    let changed = report.written() + report.removed() > 0;
+    if changed || !base.join(manifest::MANIFEST_PATH).exists() {
+        report.manifest = Some(manifest::record_applied(base, output, &report.entries)?);
+    }
+    Ok(report)
+}
+
+/// `.claude/` 안에 있지만 새 결과에는 없는 레지스트리 컴포넌트 파일 목록
+pub(crate) fn stale_components(base: &Path, output: &GeneratedOutput) -> Vec<PathBuf> {
+    let generated: Vec<PathBuf> = output
+        .files()
+        .into_iter()
+        .map(|(relative, _)| base.join(relative))
+        .collect();
+    let user_authored = manifest::user_authored_paths(base);
+    let claude_dir = base.join(".claude");
+    let mut stale = Vec::new();
+    for kind in [
+        ComponentKind::Skill,
+        ComponentKind::Agent,
+        ComponentKind::Command,
+    ] {
+        for name in registry::list_components(kind) {
+            let path = claude_dir.join(kind.installed_path(&name));
+            if path.is_file() && !generated.contains(&path) && !user_authored.contains(&path) {
+                stale.push(path);
+            }
+        }
+    }
+    stale
+}
+
+enum Action {
+    Write(String, WriteStatus),
+    Skip(WriteStatus),
+}
+
+/// settings.json이면 기존 값을 유지하면서 생성된 키 중 없는 것만 추가한 결과를 반환한다.
+///
+/// settings.json이 아니거나 어느 한쪽이 유효한 JSON이 아니면 `None`.
+fn merge_settings(relative: &Path, existing: &str, generated: &str) -> Option<String> {
+    if relative != Path::new(".claude").join("settings.json") {
+        return None;
+    }
+    let mut current: serde_json::Value = serde_json::from_str(existing).ok()?;
+    let generated: serde_json::Value = serde_json::from_str(generated).ok()?;
+    add_missing_keys(&mut current, generated);
+    let mut merged = serde_json::to_string_pretty(&current).ok()?;
+    merged.push('\n');
+    // 병합 결과가 기존 내용과 의미상 같으면 원문을 유지
+    if serde_json::from_str::<serde_json::Value>(existing).ok()? == current {
+        return Some(existing.to_string());
+    }
+    Some(merged)
+}
+
+fn add_missing_keys(current: &mut serde_json::Value, generated: serde_json::Value) {
+    if let (Some(current), serde_json::Value::Object(generated)) =
+        (current.as_object_mut(), generated)
+    {
+        for (key, value) in generated {
+            match current.get_mut(&key) {
+                Some(existing) => add_missing_keys(existing, value),
+                None => {
+                    current.insert(key, value);
+                }
+            }
+        }
+    }
+}
+
+/// 생성 결과 중 `target` 하나만 기록하고 manifest에서도 그 항목만 갱신한다.
+///
+/// `target`은 `CLAUDE.md`, `settings.json`, 또는 `.claude/` 기준 컴포넌트 경로
+/// (예: `skills/rust/async-patterns/SKILL.md`)이며 `.claude/` 접두사도 허용한다.
+pub fn write_single(
+    base: &Path,
+    output: &GeneratedOutput,
+    target: &str,
+    force: bool,
+) -> Result<PathBuf> {
+    let (relative, content) = select_file(output, target)?;
+    let path = base.join(&relative);
+    ensure_parent(&path)?;
+    write_file(&path, content, force, LineEnding::default())?;
+    manifest::record_installed_file(base, &relative, &std::fs::read(&path)?)?;
+    Ok(path)
+}
+
+/// `target`에 해당하는 생성 파일을 찾는다.
+pub fn select_file<'a>(output: &'a GeneratedOutput, target: &str) -> Result<(PathBuf, &'a str)> {
+    let normalized = target.trim_start_matches("./");
+    output
+        .files()
+        .into_iter()
+        .find(|(relative, _)| {
+            let in_claude = relative.strip_prefix(".claude").unwrap_or(relative);
+            relative == Path::new(normalized) || in_claude == Path::new(normalized)
+        })
+        .ok_or_else(|| KaelError::Project {
+            message: format!(
+                "'{target}' is not part of the generated output. \
+                 Use CLAUDE.md, settings.json, or a component path like skills/<name>/SKILL.md."
+            ),
+        })
 }
 
 /// `.claude/` 디렉토리가 이미 존재하는지 확인한다.
@@ -51,13 +370,84 @@ pub fn has_existing_config(base: &Path) -> bool {
     base.join(".claude").exists() || base.join("CLAUDE.md").exists()
 }
 
-fn write_file(path: &Path, content: &str, force: bool) -> Result<()> {
+/// `.gitignore`에서 kael이 관리하는 블록의 시작/끝 표시
+const GITIGNORE_START: &str = "# >>> kael";
+const GITIGNORE_END: &str = "# <<< kael";
+
+/// 커밋하지 말아야 할, 기기별 생성 파일
+pub const GITIGNORE_ENTRIES: &[&str] = &[".claude/settings.local.json"];
+
+/// 프로젝트 `.gitignore`에 kael 관리 블록을 추가하거나 제자리에서 갱신한다.
+///
+/// 블록 밖의 내용은 건드리지 않는다. 내용이 바뀌었으면 `true`를 반환한다.
+pub fn ensure_gitignore(base: &Path, entries: &[&str]) -> Result<bool> {
+    let path = base.join(".gitignore");
+    let current = if path.exists() {
+        std::fs::read_to_string(&path)?
+    } else {
+        String::new()
+    };
+    let updated = with_gitignore_block(&current, entries);
+    if updated == current {
+        return Ok(false);
+    }
+    std::fs::write(&path, updated)?;
+    Ok(true)
+}
+
+fn with_gitignore_block(current: &str, entries: &[&str]) -> String {
+    let mut block = format!("{GITIGNORE_START}\n");
+    for entry in entries {
+        block.push_str(entry);
+        block.push('\n');
+    }
+    block.push_str(GITIGNORE_END);
+    block.push('\n');
+
+    let lines: Vec<&str> = current.lines().collect();
+    let start = lines.iter().position(|l| l.trim_end() == GITIGNORE_START);
+    let end = start.and_then(|start| {
+        lines[start..]
+            .iter()
+            .position(|l| l.trim_end() == GITIGNORE_END)
+            .map(|offset| start + offset)
+    });
+    match (start, end) {
+        (Some(start), Some(end)) => {
+            let mut out = String::new();
+            for line in &lines[..start] {
+                out.push_str(line);
+                out.push('\n');
+            }
+            out.push_str(&block);
+            for line in &lines[end + 1..] {
+                out.push_str(line);
+                out.push('\n');
+            }
+            out
+        }
+        _ => {
+            let mut out = current.to_string();
+            if !out.is_empty() && !out.ends_with('\n') {
+                out.push('\n');
+            }
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&block);
+            out
+        }
+    }
+}
+
+/// `normalize`한 내용을 기록한다. `force`가 없으면 기존 파일이 있을 때 에러.
+fn write_file(path: &Path, content: &str, force: bool, line_ending: LineEnding) -> Result<()> {
     if path.exists() && !force {
         return Err(KaelError::FileExists {
             path: path.to_path_buf(),
         });
     }
-    std::fs::write(path, content)?;
+    std::fs::write(path, normalize(content, line_ending))?;
     Ok(())
 }
 
@@ -75,8 +465,9 @@ mod tests {
 
     fn mock_output() -> GeneratedOutput {
         GeneratedOutput {
-            claude_md: "# Test\nGenerated CLAUDE.md".into(),
-            settings_json: r#"{"project":{"name":"test"}}"#.into(),
+            claude_md: "# Test\nGenerated CLAUDE.md\n".into(),
+            settings_json: Some(r#"{"project":{"name":"test"}}"#.into()),
+            env_settings: vec![],
             skills: vec![GeneratedFile {
                 relative_path: "skills/rust/error-handling/SKILL.md".into(),
                 content: "# Error Handling".into(),
@@ -89,6 +480,8 @@ mod tests {
                 relative_path: "commands/init.md".into(),
                 content: "# Init".into(),
             }],
+            missing: vec![],
+            prd_warnings: vec![],
         }
     }
 
@@ -109,7 +502,49 @@ mod tests {
             .join(".claude/agents/_base/architect.md")
             .exists());
         assert!(dir.path().join(".claude/commands/init.md").exists());
-        assert_eq!(written.len(), 5);
+        assert_eq!(written.len(), 6);
+        assert_eq!(written[5], dir.path().join(manifest::MANIFEST_PATH));
+    }
+
+    #[test]
+    fn manifest_lists_written_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let written = write_output(dir.path(), &mock_output(), false).unwrap();
+        let (manifest_path, files) = written.split_last().unwrap();
+
+        let json = std::fs::read_to_string(manifest_path).unwrap();
+        let parsed: manifest::Manifest = serde_json::from_str(&json).unwrap();
+        let round_trip: manifest::Manifest =
+            serde_json::from_str(&serde_json::to_string(&parsed).unwrap()).unwrap();
+        assert_eq!(round_trip, parsed);
+
+        assert_eq!(parsed.version, env!("CARGO_PKG_VERSION"));
+        assert!(parsed.generated_at > 0);
+        let listed: Vec<PathBuf> = parsed
+            .files
+            .iter()
+            .map(|entry| dir.path().join(&entry.path))
+            .collect();
+        assert_eq!(listed, files);
+    }
+
+    #[test]
+    fn gitignore_block_is_written_once_and_updated_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".gitignore");
+        std::fs::write(&path, "target/").unwrap();
+
+        assert!(ensure_gitignore(dir.path(), &[".claude/settings.local.json"]).unwrap());
+        assert!(!ensure_gitignore(dir.path(), &[".claude/settings.local.json"]).unwrap());
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content.matches(GITIGNORE_START).count(), 1);
+        assert!(content.starts_with("target/\n\n# >>> kael\n"));
+
+        // 항목이 바뀌면 블록만 교체하고 뒤에 붙은 사용자 내용은 유지한다
+        std::fs::write(&path, format!("{content}*.log\n")).unwrap();
+        ensure_gitignore(dir.path(), &["a", "b"]).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content, "target/\n\n# >>> kael\na\nb\n# <<< kael\n*.log\n");
     }
 
     #[test]
@@ -132,6 +567,325 @@ mod tests {
         assert!(dir.path().join("CLAUDE.md").exists());
     }
 
+    #[test]
+    fn write_single_writes_only_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = mock_output();
+        let path = write_single(dir.path(), &output, "CLAUDE.md", false).unwrap();
+
+        assert_eq!(path, dir.path().join("CLAUDE.md"));
+        assert!(dir.path().join("CLAUDE.md").exists());
+        // .claude/에는 manifest만 생긴다
+        let claude: Vec<_> = std::fs::read_dir(dir.path().join(".claude"))
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(claude, [".kael-manifest.json"]);
+    }
+
+    #[test]
+    fn write_single_accepts_component_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = mock_output();
+        write_single(dir.path(), &output, "settings.json", false).unwrap();
+        write_single(dir.path(), &output, ".claude/commands/init.md", false).unwrap();
+
+        assert!(dir.path().join(".claude/settings.json").exists());
+        assert!(dir.path().join(".claude/commands/init.md").exists());
+        assert!(!dir.path().join("CLAUDE.md").exists());
+        assert!(!dir.path().join(".claude/agents").exists());
+    }
+
+    #[test]
+    fn write_single_unknown_target_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = write_single(dir.path(), &mock_output(), "nope.md", false).unwrap_err();
+        assert!(err.to_string().contains("not part of the generated output"));
+    }
+
+    #[test]
+    fn apply_create_refuses_existing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = mock_output();
+        std::fs::write(dir.path().join("CLAUDE.md"), "mine").unwrap();
+
+        let err = apply(dir.path(), &output, ApplyMode::Create).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+        // 충돌 검사가 먼저이므로 아무것도 기록되지 않음
+        assert!(!dir.path().join(".claude").exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("CLAUDE.md")).unwrap(),
+            "mine"
+        );
+    }
+
+    #[test]
+    fn apply_update_is_idempotent() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = mock_output();
+        let first = apply(dir.path(), &output, ApplyMode::Update).unwrap();
+        assert!(first
+            .entries
+            .iter()
+            .all(|(_, s)| *s == WriteStatus::Created));
+
+        let second = apply(dir.path(), &output, ApplyMode::Update).unwrap();
+        assert!(second
+            .entries
+            .iter()
+            .all(|(_, s)| *s == WriteStatus::Unchanged));
+        assert_eq!(second.written(), 0);
+    }
+
+    #[test]
+    fn apply_update_refuses_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = mock_output();
+        apply(dir.path(), &output, ApplyMode::Update).unwrap();
+        std::fs::write(dir.path().join("CLAUDE.md"), "edited").unwrap();
+        std::fs::remove_file(dir.path().join(".claude/commands/init.md")).unwrap();
+
+        let err = apply(dir.path(), &output, ApplyMode::Update).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+        assert!(!dir.path().join(".claude/commands/init.md").exists());
+    }
+
+    #[test]
+    fn apply_update_overwrites_unmodified_managed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut output = mock_output();
+        apply(dir.path(), &output, ApplyMode::Update).unwrap();
+        output.claude_md = "# Test\nRegenerated CLAUDE.md\n".into();
+
+        let report = apply(dir.path(), &output, ApplyMode::Update).unwrap();
+        assert_eq!(report.written(), 1);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("CLAUDE.md")).unwrap(),
+            output.claude_md
+        );
+    }
+
+    #[test]
+    fn apply_force_overwrites_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = mock_output();
+        apply(dir.path(), &output, ApplyMode::Update).unwrap();
+        std::fs::write(dir.path().join("CLAUDE.md"), "edited").unwrap();
+
+        let report = apply(dir.path(), &output, ApplyMode::Force).unwrap();
+        assert_eq!(
+            report.entries[0].1,
+            WriteStatus::Updated(LineStats {
+                added: 2,
+                removed: 1
+            })
+        );
+        assert_eq!(report.written(), 1);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("CLAUDE.md")).unwrap(),
+            output.claude_md
+        );
+    }
+
+    #[test]
+    fn apply_merge_keeps_edits_and_merges_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = mock_output();
+        apply(dir.path(), &output, ApplyMode::Update).unwrap();
+        std::fs::write(dir.path().join("CLAUDE.md"), "edited").unwrap();
+        std::fs::write(
+            dir.path().join(".claude/settings.json"),
+            r#"{"project":{"name":"renamed"},"custom":true}"#,
+        )
+        .unwrap();
+        std::fs::remove_file(dir.path().join(".claude/commands/init.md")).unwrap();
+
+        let report = apply(dir.path(), &output, ApplyMode::Merge).unwrap();
+        let statuses: Vec<_> = report.entries.iter().map(|(_, s)| *s).collect();
+        assert_eq!(statuses[0], WriteStatus::Kept);
+        assert_eq!(statuses[4], WriteStatus::Created);
+
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("CLAUDE.md")).unwrap(),
+            "edited"
+        );
+        // 사용자 값은 유지 (생성된 값에 새 키가 없으므로 그대로)
+        let settings: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(dir.path().join(".claude/settings.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(settings["project"]["name"], "renamed");
+        assert_eq!(settings["custom"], true);
+    }
+
+    #[test]
+    fn apply_only_new_fills_gaps_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = mock_output();
+        apply(dir.path(), &output, ApplyMode::Update).unwrap();
+        std::fs::write(dir.path().join("CLAUDE.md"), "edited").unwrap();
+        std::fs::write(dir.path().join(".claude/settings.json"), "{}").unwrap();
+        std::fs::remove_file(dir.path().join(".claude/commands/init.md")).unwrap();
+
+        let options = ApplyOptions {
+            mode: ApplyMode::OnlyNew,
+            touch: true,
+            ..ApplyOptions::default()
+        };
+        let report = apply(dir.path(), &output, options).unwrap();
+        assert_eq!(report.written(), 1);
+        assert_eq!(report.skipped(), 4);
+        assert_eq!(report.entries[4].1, WriteStatus::Created);
+
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("CLAUDE.md")).unwrap(),
+            "edited"
+        );
+        // settings.json도 병합하지 않는다
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(".claude/settings.json")).unwrap(),
+            "{}"
+        );
+        assert!(dir.path().join(".claude/commands/init.md").exists());
+    }
+
+    fn force_options(prune: bool) -> ApplyOptions {
+        ApplyOptions {
+            mode: ApplyMode::Force,
+            prune,
+            ..ApplyOptions::default()
+        }
+    }
+
+    #[test]
+    fn prune_removes_stale_registry_components_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = mock_output();
+        apply(dir.path(), &output, ApplyMode::Create).unwrap();
+        let claude = dir.path().join(".claude");
+        std::fs::write(claude.join("commands/release.md"), "# stale").unwrap();
+        std::fs::write(claude.join("commands/my-own.md"), "# custom").unwrap();
+
+        let report = apply(dir.path(), &output, force_options(true)).unwrap();
+        assert!(report
+            .entries
+            .contains(&(claude.join("commands/release.md"), WriteStatus::Removed)));
+        assert!(!claude.join("commands/release.md").exists());
+        assert!(claude.join("commands/my-own.md").exists());
+        assert!(claude.join("commands/init.md").exists());
+        assert_eq!(report.removed(), 1);
+    }
+
+    #[test]
+    fn user_authored_files_survive_force_and_prune() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = mock_output();
+        apply(dir.path(), &output, ApplyMode::Create).unwrap();
+        let claude = dir.path().join(".claude");
+        for name in ["init", "release"] {
+            let relative = Path::new(".claude/commands").join(format!("{name}.md"));
+            std::fs::write(dir.path().join(&relative), "# mine").unwrap();
+            manifest::record_user_file(dir.path(), &relative, b"# mine").unwrap();
+        }
+
+        let report = apply(dir.path(), &output, force_options(true)).unwrap();
+        assert_eq!(report.removed(), 0);
+        for name in ["init", "release"] {
+            let path = claude.join(format!("commands/{name}.md"));
+            assert_eq!(std::fs::read_to_string(path).unwrap(), "# mine");
+        }
+    }
+
+    #[test]
+    fn force_without_prune_never_removes_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = mock_output();
+        apply(dir.path(), &output, ApplyMode::Create).unwrap();
+        let claude = dir.path().join(".claude");
+        std::fs::write(claude.join("commands/release.md"), "# stale").unwrap();
+        std::fs::write(claude.join("notes.md"), "# custom").unwrap();
+
+        let report = apply(dir.path(), &output, force_options(false)).unwrap();
+        assert!(report
+            .entries
+            .iter()
+            .all(|(_, s)| *s != WriteStatus::Removed));
+        assert!(claude.join("commands/release.md").exists());
+        assert_eq!(
+            std::fs::read_to_string(claude.join("notes.md")).unwrap(),
+            "# custom"
+        );
+    }
+
+    #[test]
+    fn merge_settings_adds_missing_keys_only() {
+        let relative = Path::new(".claude").join("settings.json");
+        let merged = merge_settings(
+            &relative,
+            r#"{"project":{"name":"mine"}}"#,
+            r#"{"project":{"name":"gen","type":"cli"},"mcp":{"autoLoad":false}}"#,
+        )
+        .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&merged).unwrap();
+
+        assert_eq!(value["project"]["name"], "mine");
+        assert_eq!(value["project"]["type"], "cli");
+        assert_eq!(value["mcp"]["autoLoad"], false);
+        assert!(merge_settings(Path::new("CLAUDE.md"), "{}", "{}").is_none());
+    }
+
+    #[test]
+    fn apply_touch_rewrites_identical_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = mock_output();
+        apply(dir.path(), &output, ApplyMode::Update).unwrap();
+
+        let claude_md = dir.path().join("CLAUDE.md");
+        let old = std::time::SystemTime::UNIX_EPOCH;
+        std::fs::File::options()
+            .write(true)
+            .open(&claude_md)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        let options = ApplyOptions {
+            mode: ApplyMode::Update,
+            touch: true,
+            ..ApplyOptions::default()
+        };
+        let report = apply(dir.path(), &output, options).unwrap();
+        assert!(report
+            .entries
+            .iter()
+            .all(|(_, s)| *s == WriteStatus::Touched));
+
+        let meta = std::fs::metadata(&claude_md).unwrap();
+        assert!(meta.modified().unwrap() > old);
+        assert_eq!(
+            std::fs::read_to_string(&claude_md).unwrap(),
+            output.claude_md
+        );
+    }
+
+    #[test]
+    fn written_files_are_normalized() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut output = mock_output();
+        output.claude_md = "# Test\r\nmixed\nendings".into();
+        apply(dir.path(), &output, ApplyMode::Create).unwrap();
+
+        let path = dir.path().join("CLAUDE.md");
+        assert_eq!(std::fs::read(&path).unwrap(), b"# Test\nmixed\nendings\n");
+        // 정규화 후 같은 내용이면 다시 실행해도 바뀌지 않는다
+        let report = apply(dir.path(), &output, ApplyMode::Update).unwrap();
+        assert_eq!(report.written(), 0);
+
+        write_file(&path, "a\nb\n\n\n", true, LineEnding::Crlf).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"a\r\nb\r\n");
+        assert_eq!(normalize("", LineEnding::Lf), "");
+    }
+
     #[test]
     fn has_existing_config_detection() {
         let dir = tempfile::tempdir().unwrap();
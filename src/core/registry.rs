@@ -1,5 +1,11 @@
-use include_dir::{include_dir, Dir};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
 
+use include_dir::{include_dir, Dir, DirEntry};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::core::prd::{self, FrontmatterFormat};
 use crate::error::{KaelError, Result};
 
 // ── Embedded registry ───────────────────────────────────────────────
@@ -8,10 +14,12 @@ static SKILLS_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR/registry/skills");
 static AGENTS_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR/registry/agents");
 static COMMANDS_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR/registry/commands");
 static TEMPLATES_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR/registry/templates");
+/// 임베디드 레지스트리 버전. 컴포넌트 내용이 바뀌는 릴리스마다 올린다.
+const REGISTRY_VERSION: &str = include_str!("../../registry/VERSION");
 
 // ── Component kind ──────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
 pub enum ComponentKind {
     Skill,
     Agent,
@@ -19,6 +27,12 @@ pub enum ComponentKind {
 }
 
 impl ComponentKind {
+    pub const ALL: [ComponentKind; 3] = [
+        ComponentKind::Skill,
+        ComponentKind::Agent,
+        ComponentKind::Command,
+    ];
+
     fn dir(&self) -> &'static Dir<'static> {
         match self {
             ComponentKind::Skill => &SKILLS_DIR,
@@ -27,7 +41,30 @@ impl ComponentKind {
         }
     }
 
-    fn label(&self) -> &'static str {
+    /// 레지스트리 루트 아래 하위 디렉토리 이름
+    fn dir_name(&self) -> &'static str {
+        match self {
+            ComponentKind::Skill => "skills",
+            ComponentKind::Agent => "agents",
+            ComponentKind::Command => "commands",
+        }
+    }
+
+    /// 컴포넌트 이름을 종류별 디렉토리 기준 파일 경로로 바꾼다.
+    fn file_path(&self, name: &str) -> String {
+        match self {
+            ComponentKind::Skill => format!("{name}/SKILL.md"),
+            ComponentKind::Agent => format!("{name}.md"),
+            ComponentKind::Command => format!("{name}.md"),
+        }
+    }
+
+    /// 설치된 컴포넌트의 `.claude/` 기준 경로 (예: `skills/rust/ffi/SKILL.md`)
+    pub fn installed_path(&self, name: &str) -> String {
+        format!("{}/{}", self.dir_name(), self.file_path(name))
+    }
+
+    pub fn label(&self) -> &'static str {
         match self {
             ComponentKind::Skill => "skill",
             ComponentKind::Agent => "agent",
@@ -36,6 +73,128 @@ impl ComponentKind {
     }
 }
 
+// ── Filesystem overlays ──────────────────────────────────────────────
+
+thread_local! {
+    static OVERLAYS: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+    static GLOBAL_OVERLAYS: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+    static REGISTRY_DIR: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+}
+
+/// `--registry`/`KAEL_REGISTRY`로 지정한 레지스트리 디렉토리를 설정한다.
+///
+/// 다른 모든 overlay보다 먼저 찾는다. 디렉토리가 없으면 어디서 온 경로인지 밝혀 에러를 낸다.
+pub fn set_registry_dir(dir: Option<PathBuf>) -> Result<()> {
+    if let Some(dir) = &dir {
+        if !dir.is_dir() {
+            return Err(KaelError::Project {
+                message: format!(
+                    "Registry directory not found: {} (from {})",
+                    dir.display(),
+                    registry_dir_origin(dir)
+                ),
+            });
+        }
+    }
+    REGISTRY_DIR.with(|registry| *registry.borrow_mut() = dir);
+    Ok(())
+}
+
+/// `--registry`와 `KAEL_REGISTRY` 중 `dir`을 준 쪽. 둘 다 같은 값이면 플래그로 본다.
+fn registry_dir_origin(dir: &Path) -> &'static str {
+    match std::env::var_os("KAEL_REGISTRY") {
+        Some(env) if Path::new(&env) == dir => "KAEL_REGISTRY",
+        _ => "--registry",
+    }
+}
+
+/// `--registry-source overlay`에서 overlay 경로를 줄 수 있는 곳 (에러 메시지용)
+pub const OVERLAY_SOURCES: &str =
+    "--registry, KAEL_REGISTRY, PRD registry_paths, or config registry_paths";
+
+/// 임베디드 레지스트리보다 먼저 찾을 파일시스템 레지스트리 루트를 설정한다.
+///
+/// 각 루트는 임베디드와 같은 레이아웃(`skills/`, `agents/`, `commands/`)을 따르며,
+/// 앞에 있는 루트일수록 우선한다.
+pub fn set_overlays(roots: Vec<PathBuf>) {
+    OVERLAYS.with(|overlays| *overlays.borrow_mut() = roots);
+}
+
+/// PRD와 무관하게 항상 쓸 레지스트리 루트(설정 파일 등)를 설정한다.
+///
+/// [`set_overlays`]로 설정한 PRD 쪽 루트보다 우선순위가 낮다.
+pub fn set_global_overlays(roots: Vec<PathBuf>) {
+    GLOBAL_OVERLAYS.with(|overlays| *overlays.borrow_mut() = roots);
+}
+
+/// 현재 설정된 파일시스템 레지스트리 루트 목록 (`--registry` → PRD 쪽 → 전역 순)
+pub fn overlays() -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = REGISTRY_DIR
+        .with(|registry| registry.borrow().clone())
+        .into_iter()
+        .collect();
+    roots.extend(OVERLAYS.with(|overlays| overlays.borrow().clone()));
+    roots.extend(GLOBAL_OVERLAYS.with(|overlays| overlays.borrow().clone()));
+    roots
+}
+
+/// 레지스트리를 읽을 때 어느 쪽을 볼지
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum RegistrySource {
+    /// 임베디드 레지스트리만 (overlay 무시)
+    Embedded,
+    /// 파일시스템 overlay만. overlay가 없으면 컴포넌트 조회가 에러를 낸다.
+    Overlay,
+    /// overlay가 임베디드를 가린다
+    #[default]
+    Both,
+}
+
+impl RegistrySource {
+    fn uses_embedded(self) -> bool {
+        self != RegistrySource::Overlay
+    }
+
+    fn uses_overlay(self) -> bool {
+        self != RegistrySource::Embedded
+    }
+}
+
+thread_local! {
+    static SOURCE: std::cell::Cell<RegistrySource> = const { std::cell::Cell::new(RegistrySource::Both) };
+}
+
+/// 이후의 모든 컴포넌트 조회에 적용할 레지스트리 출처를 설정한다. 템플릿은 항상 임베디드다.
+pub fn set_source(source: RegistrySource) {
+    SOURCE.with(|s| s.set(source));
+}
+
+pub fn source() -> RegistrySource {
+    SOURCE.with(|s| s.get())
+}
+
+/// 현재 출처 설정에서 실제로 조회할 overlay 루트
+fn active_overlays() -> Vec<PathBuf> {
+    if source().uses_overlay() {
+        overlays()
+    } else {
+        Vec::new()
+    }
+}
+
+// ── Component metadata ──────────────────────────────────────────────
+
+/// 컴포넌트 파일 frontmatter에서 읽어낸 메타데이터
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ComponentMeta {
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 매칭 결과 정렬 우선순위. 클수록 앞에 오고, 기본값 0은 매칭 순서를 그대로 둔다.
+    #[serde(default)]
+    pub priority: i32,
+}
+
 // ── Public API ──────────────────────────────────────────────────────
 
 /// 특정 컴포넌트의 내용을 반환한다.
@@ -43,28 +202,156 @@ impl ComponentKind {
 /// - skill: `"rust/async-patterns"` → `registry/skills/rust/async-patterns/SKILL.md`
 /// - agent: `"_base/architect"` → `registry/agents/_base/architect.md`
 /// - command: `"init"` → `registry/commands/init.md`
-pub fn get_component(kind: ComponentKind, name: &str) -> Result<&'static str> {
-    let path = match kind {
-        ComponentKind::Skill => format!("{name}/SKILL.md"),
-        ComponentKind::Agent => format!("{name}.md"),
-        ComponentKind::Command => format!("{name}.md"),
-    };
+///
+/// 파일시스템 overlay가 설정되어 있으면 그쪽을 먼저 찾는다. 조회 범위는 [`set_source`]를 따른다.
+pub fn get_component(kind: ComponentKind, name: &str) -> Result<String> {
+    validate_name(name)?;
+    let path = kind.file_path(name);
+    let source = source();
+    let overlays = active_overlays();
+    if source == RegistrySource::Overlay && overlays.is_empty() {
+        return Err(KaelError::Project {
+            message: format!(
+                "--registry-source overlay requires a registry path ({OVERLAY_SOURCES})"
+            ),
+        });
+    }
 
-    kind.dir()
-        .get_file(&path)
+    for root in overlays {
+        if let Ok(content) = std::fs::read_to_string(root.join(kind.dir_name()).join(&path)) {
+            return Ok(content);
+        }
+    }
+
+    Some(kind.dir())
+        .filter(|_| source.uses_embedded())
+        .and_then(|dir| dir.get_file(&path))
         .and_then(|f| f.contents_utf8())
+        .map(str::to_string)
         .ok_or_else(|| KaelError::RegistryNotFound {
-            name: format!("{} '{name}'", kind.label()),
+            kind: kind.label(),
+            name: name.to_string(),
+            categories: available_categories(kind),
         })
 }
 
-/// 특정 종류의 모든 컴포넌트 이름 목록을 반환한다.
+/// 특정 종류의 최상위 카테고리 목록을 반환한다. (예: `_common`, `rust`, `infra`)
+///
+/// commands는 `db/migrate`처럼 묶음 디렉토리(`db`)가 있을 때만 그 이름이 들어가며,
+/// 최상위 command만 있으면 빈 목록이다.
+pub fn available_categories(kind: ComponentKind) -> Vec<String> {
+    let mut categories: Vec<String> = Vec::new();
+    if source().uses_embedded() {
+        categories.extend(
+            kind.dir()
+                .dirs()
+                .filter_map(|d| d.path().file_name())
+                .map(|n| n.to_string_lossy().into_owned()),
+        );
+    }
+    for root in active_overlays() {
+        if let Ok(entries) = std::fs::read_dir(root.join(kind.dir_name())) {
+            categories.extend(
+                entries
+                    .flatten()
+                    .filter(|e| e.path().is_dir())
+                    .map(|e| e.file_name().to_string_lossy().into_owned()),
+            );
+        }
+    }
+    categories.sort();
+    categories.dedup();
+    categories
+}
+
+/// 특정 종류의 모든 컴포넌트 이름 목록을 반환한다. (overlay 포함, 정렬/중복 제거)
 pub fn list_components(kind: ComponentKind) -> Vec<String> {
-    match kind {
+    let mut names = match kind {
+        _ if !source().uses_embedded() => Vec::new(),
         ComponentKind::Skill => list_skills(),
         ComponentKind::Agent => list_agents(),
         ComponentKind::Command => list_commands(),
+    };
+    for root in active_overlays() {
+        names.extend(list_fs_components(kind, &root.join(kind.dir_name())));
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// `.claude/`에 설치할 컴포넌트 내용. agent는 [`ensure_agent_frontmatter`]를 거친다.
+///
+/// 생성·설치·복구와 레지스트리 비교가 모두 이 내용을 기준으로 한다.
+pub fn get_installable(kind: ComponentKind, name: &str) -> Result<String> {
+    let content = get_component(kind, name)?;
+    Ok(match kind {
+        ComponentKind::Agent => ensure_agent_frontmatter(name, &content),
+        ComponentKind::Skill | ComponentKind::Command => content,
+    })
+}
+
+/// Claude Code가 읽는 agent YAML frontmatter에 `name`이 있도록 한다.
+///
+/// frontmatter가 없으면 컴포넌트 경로의 마지막 부분(`_base/architect` → `architect`)을
+/// `name`으로 한 frontmatter를 앞에 붙이고, YAML frontmatter에 `name`이 없으면 첫 키로 넣는다.
+/// 이미 있거나 TOML frontmatter면 그대로 돌려준다.
+///
+/// frontmatter는 첫 줄이 구분자일 때만 인정한다. 제목 뒤에 오는 `---`는 Markdown 가로줄이다.
+pub fn ensure_agent_frontmatter(name: &str, content: &str) -> String {
+    let leaf = name.rsplit('/').next().unwrap_or(name);
+    let first_line = content
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim_end();
+    let parsed = ["---", "+++"]
+        .contains(&first_line)
+        .then(|| prd::extract_frontmatter(content).ok())
+        .flatten();
+    let source = match parsed {
+        None => return format!("---\nname: {leaf}\n---\n\n{content}"),
+        Some((FrontmatterFormat::Toml, ..)) => return content.to_string(),
+        Some((FrontmatterFormat::Yaml, source, _)) => source,
+    };
+    let has_name = serde_yaml_ng::from_str::<serde_yaml_ng::Value>(source)
+        .ok()
+        .is_some_and(|value| value.get("name").is_some());
+    if has_name {
+        return content.to_string();
     }
+    // 여는 `---` 줄(첫 줄) 바로 다음
+    let insert_at = content.find('\n').map_or(content.len(), |i| i + 1);
+    format!(
+        "{}name: {leaf}\n{}",
+        &content[..insert_at],
+        &content[insert_at..]
+    )
+}
+
+/// 컴포넌트 frontmatter의 메타데이터를 반환한다.
+///
+/// frontmatter가 없거나 파싱에 실패하면 기본값(태그 없음)을 돌려준다.
+pub fn component_meta(kind: ComponentKind, name: &str) -> Result<ComponentMeta> {
+    let content = get_component(kind, name)?;
+    Ok(parse_meta(&content))
+}
+
+/// 컴포넌트 파일 내용에서 frontmatter 메타데이터를 읽는다. 실패하면 기본값.
+pub fn parse_meta(content: &str) -> ComponentMeta {
+    frontmatter_of(content)
+        .and_then(|yaml| serde_yaml_ng::from_str(yaml).ok())
+        .unwrap_or_default()
+}
+
+/// `.claude/` 디렉토리에 설치된 컴포넌트 이름 목록 (레지스트리 이름 형식, 정렬됨)
+///
+/// 예: `.claude/skills/rust/async-patterns/SKILL.md` → `rust/async-patterns`
+pub fn installed_components(kind: ComponentKind, claude_dir: &Path) -> Vec<String> {
+    let mut names = list_fs_components(kind, &claude_dir.join(kind.dir_name()));
+    names.sort();
+    names
 }
 
 /// 템플릿 파일 내용을 반환한다. (예: `"CLAUDE.md"`, `"settings.json"`)
@@ -73,17 +360,158 @@ pub fn get_template(name: &str) -> Result<&'static str> {
         .get_file(name)
         .and_then(|f| f.contents_utf8())
         .ok_or_else(|| KaelError::RegistryNotFound {
-            name: format!("template '{name}'"),
+            kind: "template",
+            name: name.to_string(),
+            categories: Vec::new(),
         })
 }
 
-/// 컴포넌트 존재 여부를 확인한다.
+/// 임베디드 레지스트리의 종류별 컴포넌트 개수
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentStats {
+    pub skills: usize,
+    pub agents: usize,
+    pub commands: usize,
+}
+
+/// 임베디드 레지스트리의 컴포넌트 개수를 센다. overlay는 포함하지 않는다.
+pub fn component_stats() -> ComponentStats {
+    ComponentStats {
+        skills: list_skills().len(),
+        agents: list_agents().len(),
+        commands: list_commands().len(),
+    }
+}
+
+/// 임베디드 레지스트리 전체(컴포넌트 + 템플릿)의 SHA-256 해시를 hex로 반환한다.
+///
+/// 파일 경로 순으로 정렬해 해시하므로 같은 내용이면 빌드 환경과 무관하게 같은 값이 나온다.
+pub fn registry_hash() -> String {
+    let mut files = Vec::new();
+    for (prefix, dir) in [
+        ("skills", &SKILLS_DIR),
+        ("agents", &AGENTS_DIR),
+        ("commands", &COMMANDS_DIR),
+        ("templates", &TEMPLATES_DIR),
+    ] {
+        collect_files(prefix, dir, &mut files);
+    }
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for (path, contents) in files {
+        hasher.update(path.as_bytes());
+        hasher.update([0]);
+        hasher.update(contents);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// 임베디드 레지스트리 버전 (`registry/VERSION`, `2026.10.14` 형식)
+pub fn version() -> &'static str {
+    REGISTRY_VERSION.trim()
+}
+
+/// 점으로 나눈 숫자 버전 `a`가 `b`보다 오래되었는지. 숫자가 아닌 조각은 0으로 본다.
+pub fn is_older_version(a: &str, b: &str) -> bool {
+    let parts = |v: &str| -> Vec<u64> { v.split('.').map(|p| p.parse().unwrap_or(0)).collect() };
+    parts(a) < parts(b)
+}
+
+/// 컴포넌트 존재 여부를 확인한다. 잘못된 이름은 없는 것으로 본다.
 pub fn has_component(kind: ComponentKind, name: &str) -> bool {
-    get_component(kind, name).is_ok()
+    validate_name(name).is_ok() && get_component(kind, name).is_ok()
+}
+
+/// 레지스트리 밖을 가리킬 수 있는 이름을 거부한다.
+///
+/// `..`, `/`로 시작하는 절대 경로, 백슬래시가 들어간 이름은 [`KaelError::InvalidName`].
+/// overlay(`--registry` 등) 디렉토리는 실제 파일시스템이라 특히 중요하다.
+pub fn validate_name(name: &str) -> Result<()> {
+    if name.contains("..") || name.starts_with('/') || name.contains('\\') {
+        return Err(KaelError::InvalidName {
+            name: name.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// 종류 없이 주어진 이름에 해당하는 컴포넌트를 모든 종류에서 찾는다. (skill → agent → command 순)
+///
+/// 전체 이름(`_base/architect`)이나 마지막 경로 조각(`architect`)이 같으면 후보다.
+/// 결과가 둘 이상이면 이름만으로는 모호하므로 호출자가 종류를 지정하게 해야 한다.
+pub fn resolve_ambiguous(name: &str) -> Vec<(ComponentKind, String)> {
+    let mut out = Vec::new();
+    for kind in ComponentKind::ALL {
+        for candidate in list_components(kind) {
+            let leaf = candidate.rsplit('/').next().unwrap_or(&candidate);
+            if candidate == name || leaf == name {
+                out.push((kind, candidate));
+            }
+        }
+    }
+    out
+}
+
+/// 이름을 하나의 컴포넌트로 확정한다. `kind`를 주면 그 종류 안에서만 찾는다.
+///
+/// 전체 이름이 정확히 일치하는 후보가 하나뿐이면 `kind`가 없어도 그것을 고른다.
+/// 그 밖에 후보가 여러 개면 [`KaelError::AmbiguousComponent`]를 낸다.
+pub fn resolve_component(
+    name: &str,
+    kind: Option<ComponentKind>,
+) -> Result<(ComponentKind, String)> {
+    let mut candidates: Vec<_> = resolve_ambiguous(name)
+        .into_iter()
+        .filter(|(k, _)| kind.map_or(true, |kind| *k == kind))
+        .collect();
+    let exact: Vec<_> = candidates.iter().filter(|(_, n)| n == name).collect();
+    if exact.len() == 1 {
+        let (k, n) = exact[0];
+        return Ok((*k, n.clone()));
+    }
+    match candidates.len() {
+        0 => Err(KaelError::RegistryNotFound {
+            kind: kind.map_or("component", |k| k.label()),
+            name: name.to_string(),
+            categories: kind.map(available_categories).unwrap_or_default(),
+        }),
+        1 => Ok(candidates.remove(0)),
+        _ => Err(KaelError::AmbiguousComponent {
+            name: name.to_string(),
+            candidates: candidates
+                .iter()
+                .map(|(k, n)| format!("{} {n}", k.label()))
+                .collect(),
+        }),
+    }
 }
 
 // ── Internal helpers ────────────────────────────────────────────────
 
+/// `---`로 감싼 frontmatter 부분만 잘라낸다.
+fn frontmatter_of(content: &str) -> Option<&str> {
+    let rest = content.strip_prefix("---")?;
+    let closing = rest.find("\n---")?;
+    Some(&rest[..closing])
+}
+
+fn collect_files(prefix: &str, dir: &'static Dir<'static>, out: &mut Vec<(String, &'static [u8])>) {
+    for entry in dir.entries() {
+        match entry {
+            DirEntry::File(file) => {
+                let path = format!("{prefix}/{}", file.path().to_string_lossy());
+                out.push((path.replace('\\', "/"), file.contents()));
+            }
+            DirEntry::Dir(sub) => collect_files(prefix, sub, out),
+        }
+    }
+}
+
 /// skills 목록: `"category/skill-name"` 형태
 fn list_skills() -> Vec<String> {
     let mut names = Vec::new();
@@ -112,12 +540,13 @@ fn collect_skills(dir: &'static Dir<'static>, out: &mut Vec<String>) {
 /// agents 목록: `"category/agent-name"` 형태
 fn list_agents() -> Vec<String> {
     let mut names = Vec::new();
-    collect_agents(&AGENTS_DIR, &mut names);
+    collect_md(&AGENTS_DIR, &mut names);
     names.sort();
     names
 }
 
-fn collect_agents(dir: &'static Dir<'static>, out: &mut Vec<String>) {
+/// 하위 경로의 `.md` 파일을 확장자 없는 이름으로 모은다. (agents, commands)
+fn collect_md(dir: &'static Dir<'static>, out: &mut Vec<String>) {
     for file in dir.files() {
         if file.path().extension().is_some_and(|e| e == "md") {
             if let Some(stem) = file.path().file_stem() {
@@ -135,30 +564,103 @@ fn collect_agents(dir: &'static Dir<'static>, out: &mut Vec<String>) {
         }
     }
     for sub in dir.dirs() {
-        collect_agents(sub, out);
+        collect_md(sub, out);
     }
 }
 
-/// commands 목록: `"command-name"` 형태
+/// commands 목록: `"command-name"`, 하위 디렉토리면 `"group/command-name"` 형태
 fn list_commands() -> Vec<String> {
-    let mut names: Vec<String> = COMMANDS_DIR
-        .files()
-        .filter_map(|f| {
-            f.path()
-                .file_stem()
-                .map(|s| s.to_string_lossy().into_owned())
-        })
-        .collect();
+    let mut names = Vec::new();
+    collect_md(&COMMANDS_DIR, &mut names);
     names.sort();
     names
 }
 
+/// 파일시스템 레지스트리 디렉토리에서 컴포넌트 이름을 수집한다.
+///
+/// 레이아웃 규칙은 임베디드와 같다: skills는 `SKILL.md`가 있는 디렉토리,
+/// agents와 commands는 하위 경로의 `.md` 파일.
+fn list_fs_components(kind: ComponentKind, dir: &Path) -> Vec<String> {
+    let mut out = Vec::new();
+    collect_fs(kind, dir, dir, &mut out);
+    out
+}
+
+fn collect_fs(kind: ComponentKind, root: &Path, dir: &Path, out: &mut Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let relative = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        if path.is_dir() {
+            if kind == ComponentKind::Skill && path.join("SKILL.md").is_file() {
+                out.push(relative.clone());
+            }
+            collect_fs(kind, root, &path, out);
+        } else if kind != ComponentKind::Skill && path.extension().is_some_and(|e| e == "md") {
+            out.push(relative.trim_end_matches(".md").to_string());
+        }
+    }
+}
+
 // ── Tests ───────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn name_under_two_kinds_prefers_exact_match() {
+        let found = resolve_ambiguous("architect");
+        assert_eq!(
+            found,
+            vec![
+                (ComponentKind::Agent, "_base/architect".to_string()),
+                (ComponentKind::Command, "architect".to_string()),
+            ]
+        );
+
+        // `architect` 명령은 전체 이름이 같고, 에이전트는 마지막 부분만 같다
+        let (kind, name) = resolve_component("architect", None).unwrap();
+        assert_eq!((kind, name.as_str()), (ComponentKind::Command, "architect"));
+        let (kind, name) = resolve_component("architect", Some(ComponentKind::Agent)).unwrap();
+        assert_eq!(
+            (kind, name.as_str()),
+            (ComponentKind::Agent, "_base/architect")
+        );
+
+        let err = resolve_component("api-expert", None)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("agent _base/api-expert"));
+        assert!(err.contains("agent go/api-expert"));
+        assert!(resolve_component("testing", Some(ComponentKind::Skill)).is_err());
+    }
+
+    #[test]
+    fn traversal_names_are_rejected() {
+        for name in [
+            "rust/../../etc/passwd",
+            "/etc/passwd",
+            "rust\\async-patterns",
+        ] {
+            let err = get_component(ComponentKind::Skill, name).unwrap_err();
+            assert!(matches!(err, KaelError::InvalidName { .. }), "{name}");
+            assert!(!has_component(ComponentKind::Skill, name));
+        }
+        assert!(validate_name("rust/async-patterns").is_ok());
+        assert!(validate_name("_base/architect").is_ok());
+        assert!(has_component(ComponentKind::Command, "init"));
+    }
+
     #[test]
     fn get_skill() {
         let content = get_component(ComponentKind::Skill, "rust/async-patterns").unwrap();
@@ -177,6 +679,23 @@ mod tests {
         assert!(content.contains("init") || content.contains("Init"));
     }
 
+    #[test]
+    fn registry_hash_is_stable_hex() {
+        let hash = registry_hash();
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, registry_hash());
+        assert!(component_stats().skills > 0);
+    }
+
+    #[test]
+    fn version_is_embedded() {
+        assert!(!version().is_empty());
+        assert!(!version().contains('\n'));
+        assert!(is_older_version("2025.12.1", version()));
+        assert!(!is_older_version(version(), version()));
+        assert!(is_older_version("1.9", "1.10"));
+    }
+
     #[test]
     fn get_template() {
         let content = super::get_template("CLAUDE.md").unwrap();
@@ -189,6 +708,29 @@ mod tests {
         assert!(err.to_string().contains("not found"));
     }
 
+    #[test]
+    fn missing_component_lists_categories() {
+        let err = get_component(ComponentKind::Skill, "rus/async-patterns").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("skill"));
+        assert!(message.contains("'rus/async-patterns'"));
+        assert!(message.contains("available skill categories"));
+        assert!(message.contains("rust"));
+    }
+
+    #[test]
+    fn available_categories_per_kind() {
+        let skills = available_categories(ComponentKind::Skill);
+        assert!(skills.contains(&"_common".to_string()));
+        assert!(skills.contains(&"infra".to_string()));
+
+        let agents = available_categories(ComponentKind::Agent);
+        assert!(agents.contains(&"_base".to_string()));
+
+        // commands는 그룹 디렉토리만 카테고리다
+        assert_eq!(available_categories(ComponentKind::Command), vec!["db"]);
+    }
+
     #[test]
     fn list_skills_not_empty() {
         let skills = list_components(ComponentKind::Skill);
@@ -212,6 +754,175 @@ mod tests {
         assert!(commands.contains(&"review".to_string()));
     }
 
+    #[test]
+    fn nested_commands_list_with_group_prefix() {
+        let commands = list_components(ComponentKind::Command);
+        assert!(commands.contains(&"db/migrate".to_string()));
+        // 최상위 command는 그대로 이름만
+        assert!(commands.contains(&"commit".to_string()));
+        assert!(!commands.iter().any(|c| c.ends_with("/commit")));
+
+        let content = get_component(ComponentKind::Command, "db/migrate").unwrap();
+        assert!(content.contains("# DB Migrate"));
+        assert_eq!(
+            ComponentKind::Command.installed_path("db/migrate"),
+            "commands/db/migrate.md"
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("commands/ops")).unwrap();
+        std::fs::write(dir.path().join("commands/ops/rollback.md"), "# Rollback").unwrap();
+        std::fs::write(dir.path().join("commands/ship.md"), "# Ship").unwrap();
+        assert!(available_categories(ComponentKind::Command).contains(&"db".to_string()));
+
+        let listed = list_fs_components(ComponentKind::Command, &dir.path().join("commands"));
+        assert!(listed.contains(&"ops/rollback".to_string()));
+        assert!(listed.contains(&"ship".to_string()));
+    }
+
+    #[test]
+    fn component_meta_reads_tags() {
+        let meta = component_meta(ComponentKind::Skill, "go/testing").unwrap();
+        assert_eq!(meta.tags, vec!["testing"]);
+
+        let untagged = component_meta(ComponentKind::Skill, "rust/ffi").unwrap();
+        assert!(untagged.tags.is_empty());
+    }
+
+    #[test]
+    fn overlay_shadows_embedded_and_adds_components() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill = dir.path().join("skills/rust/async-patterns");
+        std::fs::create_dir_all(&skill).unwrap();
+        std::fs::write(skill.join("SKILL.md"), "# Private async guide").unwrap();
+        let custom = dir.path().join("skills/acme/house-style");
+        std::fs::create_dir_all(&custom).unwrap();
+        std::fs::write(custom.join("SKILL.md"), "# House style").unwrap();
+        std::fs::create_dir_all(dir.path().join("agents/acme")).unwrap();
+        std::fs::write(dir.path().join("agents/acme/oncall.md"), "# Oncall").unwrap();
+
+        set_overlays(vec![dir.path().to_path_buf()]);
+
+        let content = get_component(ComponentKind::Skill, "rust/async-patterns").unwrap();
+        assert_eq!(content, "# Private async guide");
+        // overlay에 없는 컴포넌트는 임베디드로 폴백
+        assert!(has_component(ComponentKind::Skill, "rust/error-handling"));
+
+        let skills = list_components(ComponentKind::Skill);
+        assert!(skills.contains(&"acme/house-style".to_string()));
+        assert_eq!(
+            skills
+                .iter()
+                .filter(|s| *s == "rust/async-patterns")
+                .count(),
+            1
+        );
+        assert!(list_components(ComponentKind::Agent).contains(&"acme/oncall".to_string()));
+        assert!(available_categories(ComponentKind::Skill).contains(&"acme".to_string()));
+
+        set_overlays(Vec::new());
+    }
+
+    #[test]
+    fn agent_frontmatter_gets_a_name() {
+        assert_eq!(
+            ensure_agent_frontmatter("acme/oncall", "# Oncall\n\nPage people.\n"),
+            "---\nname: oncall\n---\n\n# Oncall\n\nPage people.\n"
+        );
+        assert_eq!(
+            ensure_agent_frontmatter(
+                "acme/oncall",
+                "---\ndescription: Pager duty\n---\n# Oncall\n"
+            ),
+            "---\nname: oncall\ndescription: Pager duty\n---\n# Oncall\n"
+        );
+        // 제목 뒤의 두 가로줄은 frontmatter가 아니다
+        let ruled = "# Oncall\n\n---\n\nRunbook\n\n---\n";
+        assert_eq!(
+            ensure_agent_frontmatter("acme/oncall", ruled),
+            format!("---\nname: oncall\n---\n\n{ruled}")
+        );
+
+        let bundled = get_component(ComponentKind::Agent, "_base/architect").unwrap();
+        assert_eq!(
+            get_installable(ComponentKind::Agent, "_base/architect").unwrap(),
+            bundled
+        );
+    }
+
+    #[test]
+    fn registry_dir_comes_before_prd_overlays() {
+        let org = tempfile::tempdir().unwrap();
+        let skill = org.path().join("skills/acme/secrets");
+        std::fs::create_dir_all(&skill).unwrap();
+        std::fs::write(skill.join("SKILL.md"), "# Org secrets policy").unwrap();
+        let shadow = org.path().join("skills/rust/async-patterns");
+        std::fs::create_dir_all(&shadow).unwrap();
+        std::fs::write(shadow.join("SKILL.md"), "# Org async guide").unwrap();
+        let prd = tempfile::tempdir().unwrap();
+        let prd_skill = prd.path().join("skills/rust/async-patterns");
+        std::fs::create_dir_all(&prd_skill).unwrap();
+        std::fs::write(prd_skill.join("SKILL.md"), "# PRD async guide").unwrap();
+
+        set_registry_dir(Some(org.path().to_path_buf())).unwrap();
+        set_overlays(vec![prd.path().to_path_buf()]);
+
+        assert_eq!(
+            get_component(ComponentKind::Skill, "acme/secrets").unwrap(),
+            "# Org secrets policy"
+        );
+        assert_eq!(
+            get_component(ComponentKind::Skill, "rust/async-patterns").unwrap(),
+            "# Org async guide"
+        );
+        assert!(list_components(ComponentKind::Skill).contains(&"acme/secrets".to_string()));
+        assert!(has_component(ComponentKind::Skill, "rust/error-handling"));
+
+        set_overlays(Vec::new());
+        set_registry_dir(None).unwrap();
+        assert!(!has_component(ComponentKind::Skill, "acme/secrets"));
+
+        let missing = org.path().join("nope");
+        let err = set_registry_dir(Some(missing)).unwrap_err();
+        assert!(err.to_string().contains("Registry directory not found"));
+        assert!(err.to_string().ends_with("(from --registry)"), "{err}");
+    }
+
+    #[test]
+    fn registry_source_selects_lookup_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill = dir.path().join("skills/rust/async-patterns");
+        std::fs::create_dir_all(&skill).unwrap();
+        std::fs::write(skill.join("SKILL.md"), "# Private async guide").unwrap();
+        set_overlays(vec![dir.path().to_path_buf()]);
+        let overlaid = || get_component(ComponentKind::Skill, "rust/async-patterns");
+        let embedded_only = || get_component(ComponentKind::Skill, "rust/error-handling");
+
+        set_source(RegistrySource::Both);
+        assert_eq!(overlaid().unwrap(), "# Private async guide");
+        assert!(embedded_only().is_ok());
+
+        set_source(RegistrySource::Embedded);
+        assert_ne!(overlaid().unwrap(), "# Private async guide");
+        assert!(embedded_only().is_ok());
+
+        set_source(RegistrySource::Overlay);
+        assert_eq!(overlaid().unwrap(), "# Private async guide");
+        assert!(matches!(
+            embedded_only().unwrap_err(),
+            KaelError::RegistryNotFound { .. }
+        ));
+        assert_eq!(
+            list_components(ComponentKind::Skill),
+            vec!["rust/async-patterns"]
+        );
+
+        set_overlays(Vec::new());
+        let err = overlaid().unwrap_err();
+        assert!(err.to_string().contains("requires a registry path"));
+        set_source(RegistrySource::Both);
+    }
+
     #[test]
     fn has_component_check() {
         assert!(has_component(ComponentKind::Skill, "rust/async-patterns"));
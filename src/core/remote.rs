@@ -0,0 +1,126 @@
+use crate::error::{KaelError, Result};
+
+/// 원격 PRD를 받을 때의 연결 + 응답 제한 시간
+#[cfg(feature = "remote")]
+const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// `url`의 PRD 내용을 받아 온다. (`init --from-url`)
+///
+/// HTTPS만 받으며, 로컬 테스트 서버를 위해 loopback 주소만 HTTP를 허용한다.
+/// 리다이렉트는 따라가고, 최종 응답이 200이 아니면 [`KaelError::Remote`].
+pub fn fetch_prd(url: &str) -> Result<String> {
+    check_scheme(url)?;
+    fetch(url)
+}
+
+fn check_scheme(url: &str) -> Result<()> {
+    let loopback = ["http://localhost", "http://127.0.0.1", "http://[::1]"];
+    let is_loopback = loopback.iter().any(|prefix| {
+        url.strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with([':', '/']))
+    });
+    if url.starts_with("https://") || is_loopback {
+        return Ok(());
+    }
+    Err(KaelError::Remote {
+        url: url.to_string(),
+        message: "only https:// URLs are supported".into(),
+    })
+}
+
+#[cfg(feature = "remote")]
+fn fetch(url: &str) -> Result<String> {
+    let remote_error = |message: String| KaelError::Remote {
+        url: url.to_string(),
+        message,
+    };
+    let client = reqwest::blocking::Client::builder()
+        .timeout(TIMEOUT)
+        .user_agent(concat!("kael/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|err| remote_error(err.to_string()))?;
+    let response = client
+        .get(url)
+        .send()
+        .map_err(|err| remote_error(err.to_string()))?;
+    let status = response.status();
+    if status != reqwest::StatusCode::OK {
+        return Err(remote_error(format!("server responded with {status}")));
+    }
+    response.text().map_err(|err| remote_error(err.to_string()))
+}
+
+#[cfg(not(feature = "remote"))]
+fn fetch(url: &str) -> Result<String> {
+    Err(KaelError::Remote {
+        url: url.to_string(),
+        message: "this kael was built without the `remote` feature; reinstall with `cargo install kael --features remote`".into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_http_is_rejected_except_loopback() {
+        let err = fetch_prd("http://example.com/PRD.md").unwrap_err();
+        assert!(matches!(err, KaelError::Remote { .. }));
+        assert!(err.to_string().contains("only https://"), "{err}");
+
+        assert!(check_scheme("https://example.com/PRD.md").is_ok());
+        assert!(check_scheme("http://127.0.0.1:8080/PRD.md").is_ok());
+        assert!(check_scheme("http://localhost.evil.com/PRD.md").is_err());
+    }
+
+    /// 요청 하나에 `response`를 그대로 돌려주는 로컬 서버. 주소를 돌려준다.
+    #[cfg(feature = "remote")]
+    fn serve_once(response: String) -> String {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 4096];
+            let _ = stream.read(&mut request);
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        format!("http://{addr}")
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn fetched_prd_generates_configuration() {
+        use crate::cli::ctx::{Ctx, Verbosity};
+        use crate::cli::init::{self, Options};
+
+        let prd = "---\nname: remote\nstack:\n  language: go\ntype: api\n---\n";
+        let url = serve_once(format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{prd}",
+            prd.len()
+        ));
+        let dir = tempfile::tempdir().unwrap();
+        let options = Options {
+            from_url: Some(format!("{url}/PRD.md")),
+            output: Some(dir.path().to_path_buf()),
+            ..Options::default()
+        };
+        init::run(&mut Ctx::capturing(Verbosity::Quiet), Vec::new(), options).unwrap();
+
+        let claude_md = std::fs::read_to_string(dir.path().join("CLAUDE.md")).unwrap();
+        assert!(claude_md.starts_with("# remote\n"));
+        assert!(dir.path().join(".claude/settings.json").is_file());
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn non_200_response_is_a_remote_error() {
+        let url = serve_once(
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".into(),
+        );
+        let err = fetch_prd(&format!("{url}/missing.md")).unwrap_err();
+        assert!(matches!(err, KaelError::Remote { .. }));
+        assert!(err.to_string().contains("404"), "{err}");
+    }
+}
@@ -0,0 +1,115 @@
+use std::fmt::Write as _;
+use std::time::{Duration, Instant};
+
+/// 생성 파이프라인의 주요 단계
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Match,
+    Render,
+    Resolve,
+    Write,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 5] = [
+        Phase::Parse,
+        Phase::Match,
+        Phase::Render,
+        Phase::Resolve,
+        Phase::Write,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Match => "match",
+            Phase::Render => "render",
+            Phase::Resolve => "resolve components",
+            Phase::Write => "write",
+        }
+    }
+}
+
+/// `--timings`용 단계별 소요 시간. 실행되지 않은 단계는 `None`이다.
+#[derive(Debug, Clone, Default)]
+pub struct Timings {
+    durations: [Option<Duration>; 5],
+}
+
+impl Timings {
+    /// `f` 실행 시간을 `phase`에 누적한다.
+    pub fn time<T>(&mut self, phase: Phase, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let value = f();
+        let slot = &mut self.durations[phase as usize];
+        *slot = Some(slot.unwrap_or_default() + start.elapsed());
+        value
+    }
+
+    pub fn get(&self, phase: Phase) -> Option<Duration> {
+        self.durations[phase as usize]
+    }
+
+    pub fn total(&self) -> Duration {
+        self.durations.iter().flatten().sum()
+    }
+
+    /// 단계별 소요 시간을 사람이 읽는 표로 만든다.
+    pub fn render(&self) -> String {
+        let mut out = String::from("Timings:\n");
+        for phase in Phase::ALL {
+            let value = match self.get(phase) {
+                Some(d) => format!("{d:.2?}"),
+                None => "-".into(),
+            };
+            let _ = writeln!(out, "  {:<20} {value}", phase.label());
+        }
+        let _ = writeln!(out, "  {:<20} {:.2?}", "total", self.total());
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::matcher::MatchStages;
+    use crate::core::{generator, prd, project};
+
+    #[test]
+    fn every_phase_recorded_after_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let prd_path = dir.path().join("PRD.md");
+        std::fs::write(
+            &prd_path,
+            "---\nname: timed\nstack:\n  language: rust\ntype: cli\n---\n",
+        )
+        .unwrap();
+
+        let mut timings = Timings::default();
+        let parsed = timings
+            .time(Phase::Parse, || prd::parse_prd_file(&prd_path))
+            .unwrap();
+        let output = generator::generate_timed(
+            &parsed,
+            MatchStages::default(),
+            Default::default(),
+            &mut timings,
+        )
+        .unwrap();
+        timings
+            .time(Phase::Write, || {
+                project::apply(dir.path(), &output, project::ApplyMode::Create)
+            })
+            .unwrap();
+
+        for phase in Phase::ALL {
+            assert!(
+                timings.get(phase).is_some(),
+                "{} not recorded",
+                phase.label()
+            );
+        }
+        assert!(timings.render().contains("resolve components"));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::core::generator::GeneratedOutput;
+use crate::core::matcher::MatchResult;
+use crate::core::registry::ComponentKind;
+use crate::error::{KaelError, Result};
+
+/// CLAUDE.md 목록과 실제 생성 파일이 어긋난 컴포넌트
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub kind: ComponentKind,
+    pub name: String,
+}
+
+/// 매칭 결과(= 렌더링된 CLAUDE.md의 skill/agent 목록)에 있지만
+/// 대응하는 `GeneratedFile`이 없는 컴포넌트를 찾는다.
+///
+/// `output.missing`에 기록된 항목은 이미 경고 대상이므로 제외한다.
+pub fn unbacked_components(matched: &MatchResult, output: &GeneratedOutput) -> Vec<Mismatch> {
+    let groups = [
+        (ComponentKind::Skill, &matched.skills, &output.skills),
+        (ComponentKind::Agent, &matched.agents, &output.agents),
+        (ComponentKind::Command, &matched.commands, &output.commands),
+    ];
+    let mut out = Vec::new();
+    for (kind, names, files) in groups {
+        for name in names {
+            let path = kind.installed_path(name);
+            let backed = files.iter().any(|f| f.relative_path == path);
+            if !backed && !output.missing.contains(name) {
+                out.push(Mismatch {
+                    kind,
+                    name: name.clone(),
+                });
+            }
+        }
+    }
+    out
+}
+
+/// 기록 전 불변식: CLAUDE.md가 나열하는 컴포넌트는 모두 파일로 생성되어야 한다.
+pub fn ensure_consistent(matched: &MatchResult, output: &GeneratedOutput) -> Result<()> {
+    let mismatches = unbacked_components(matched, output);
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+    let names: Vec<_> = mismatches
+        .iter()
+        .map(|m| format!("{} {}", m.kind.label(), m.name))
+        .collect();
+    Err(KaelError::Project {
+        message: format!(
+            "CLAUDE.md lists components that were not generated: {}",
+            names.join(", ")
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::generator;
+    use crate::core::prd;
+
+    fn rust_cli() -> (MatchResult, GeneratedOutput) {
+        let parsed =
+            prd::parse_prd("---\nname: v\nstack:\n  language: rust\ntype: cli\n---\n").unwrap();
+        let matched =
+            crate::core::matcher::match_components_cached(&parsed.frontmatter, Default::default());
+        let output = generator::generate_with_match(&parsed.frontmatter, &matched).unwrap();
+        (matched, output)
+    }
+
+    #[test]
+    fn generated_output_is_consistent() {
+        let (matched, output) = rust_cli();
+        assert!(ensure_consistent(&matched, &output).is_ok());
+    }
+
+    #[test]
+    fn dropped_component_is_a_mismatch() {
+        let (matched, mut output) = rust_cli();
+        output
+            .agents
+            .retain(|f| f.relative_path != "agents/rust/perf-engineer.md");
+
+        assert_eq!(
+            unbacked_components(&matched, &output),
+            vec![Mismatch {
+                kind: ComponentKind::Agent,
+                name: "rust/perf-engineer".into(),
+            }]
+        );
+        let err = ensure_consistent(&matched, &output).unwrap_err();
+        assert!(err.to_string().contains("agent rust/perf-engineer"));
+
+        // 경고로 보고된 누락은 불일치로 보지 않는다
+        output.missing.push("rust/perf-engineer".into());
+        assert!(ensure_consistent(&matched, &output).is_ok());
+    }
+}
@@ -22,9 +22,111 @@ pub enum KaelError {
     #[error("Project error: {message}")]
     Project { message: String },
 
+    #[error("Remote PRD error ({url}): {message}")]
+    Remote { url: String, message: String },
+
     #[error("File already exists: {path}")]
     FileExists { path: PathBuf },
 
-    #[error("Registry resource not found: {name}")]
-    RegistryNotFound { name: String },
+    #[error(
+        "Registry {kind} not found: '{name}'{}",
+        categories_hint(kind, categories)
+    )]
+    RegistryNotFound {
+        kind: &'static str,
+        name: String,
+        /// 해당 종류에서 사용 가능한 최상위 카테고리 (오타 힌트용)
+        categories: Vec<String>,
+    },
+
+    #[error("Invalid component name '{name}': use a relative registry name without '..' or '\\'")]
+    InvalidName { name: String },
+
+    #[error(
+        "Ambiguous component name '{name}': matches {}. Specify the kind.",
+        candidates.join(", ")
+    )]
+    AmbiguousComponent {
+        name: String,
+        /// `"<kind> <name>"` 형식의 후보 목록
+        candidates: Vec<String>,
+    },
+
+    /// 일괄 작업에서 모은 여러 실패. 한 줄에 하나씩 표시한다.
+    #[error("{} errors:\n{}", .0.len(), multi_lines(.0))]
+    Multi(Vec<KaelError>),
+}
+
+/// 모든 결과가 `Ok`면 값 목록을, 아니면 실패를 모두 모은 에러를 돌려준다.
+///
+/// 첫 실패에서 멈추지 않는다. 실패가 하나면 그 에러를 그대로, 여럿이면 [`KaelError::Multi`]로 감싼다.
+pub fn collect_errors<T>(results: impl IntoIterator<Item = Result<T>>) -> Result<Vec<T>> {
+    let mut values = Vec::new();
+    let mut errors = Vec::new();
+    for result in results {
+        match result {
+            Ok(value) => values.push(value),
+            Err(err) => errors.push(err),
+        }
+    }
+    match errors.len() {
+        0 => Ok(values),
+        1 => Err(errors.remove(0)),
+        _ => Err(KaelError::Multi(errors)),
+    }
+}
+
+/// `  - <에러>` 목록. 여러 줄짜리 에러는 이어지는 줄을 들여쓴다.
+fn multi_lines(errors: &[KaelError]) -> String {
+    let lines: Vec<String> = errors
+        .iter()
+        .map(|err| format!("  - {}", err.to_string().replace('\n', "\n    ")))
+        .collect();
+    lines.join("\n")
+}
+
+fn categories_hint(kind: &str, categories: &[String]) -> String {
+    if categories.is_empty() {
+        String::new()
+    } else {
+        format!(" (available {kind} categories: {})", categories.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multi_lists_each_error_on_its_own_line() {
+        let err = KaelError::Multi(vec![
+            KaelError::InvalidName {
+                name: "../x".into(),
+            },
+            KaelError::Project {
+                message: "first line\nsecond line".into(),
+            },
+        ]);
+        assert_eq!(
+            err.to_string(),
+            "2 errors:\n  - Invalid component name '../x': use a relative registry name without '..' or '\\'\n  - Project error: first line\n    second line"
+        );
+    }
+
+    #[test]
+    fn collect_errors_keeps_going_after_a_failure() {
+        let project = |message: &str| KaelError::Project {
+            message: message.into(),
+        };
+        assert_eq!(collect_errors([Ok(1), Ok(2)]).unwrap(), [1, 2]);
+
+        let single = collect_errors([Ok(1), Err(project("a"))]).unwrap_err();
+        assert!(matches!(single, KaelError::Project { .. }));
+
+        let many = collect_errors([Err(project("a")), Ok(1), Err(project("b"))]).unwrap_err();
+        let KaelError::Multi(errors) = many else {
+            panic!("expected Multi");
+        };
+        assert_eq!(errors.len(), 2);
+    }
 }
@@ -0,0 +1,4 @@
+pub mod cli;
+pub mod core;
+pub mod error;
+pub mod template;
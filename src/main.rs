@@ -1,17 +1,50 @@
-mod cli;
-mod core;
-mod error;
-mod template;
-
 use clap::Parser;
 
+use kael::cli;
+use kael::cli::color::{self, ColorChoice};
+use kael::cli::ctx::{Ctx, Verbosity};
+use kael::cli::list::{Filter, ListKind, OutputFormat, Scope};
+use kael::core::config;
+use kael::core::matcher::{self, ComponentOverrides, MatchResult, MatchStages};
+use kael::core::registry::{self, RegistrySource};
+use kael::error;
+use kael::template::engine::SettingsFormat;
+
 #[derive(Parser)]
 #[command(
     name = "kael",
-    version,
+    version = cli::version::short(),
     about = "Claude Code configuration framework CLI"
 )]
 struct Cli {
+    /// When to use colored output
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// Disable colored output (same as --color never)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Which registry to read components from [default: both]
+    #[arg(long, global = true, value_enum)]
+    registry_source: Option<RegistrySource>,
+
+    /// Look up components in this registry directory before any other
+    #[arg(long, global = true, value_name = "DIR", env = "KAEL_REGISTRY")]
+    registry: Option<std::path::PathBuf>,
+
+    /// Load this config file instead of ~/.config/kael/config.toml
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<std::path::PathBuf>,
+
+    /// Print only errors and the final summary (init, list)
+    #[arg(long, short, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Also print each matched component and the resolved PRD path (init, list, version)
+    #[arg(long, short, global = true)]
+    verbose: bool,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -20,9 +53,13 @@ struct Cli {
 enum Command {
     /// Initialize Claude Code setup for a project
     Init {
-        /// Path to PRD.md file
-        #[arg(long = "from")]
-        from: Option<std::path::PathBuf>,
+        /// Path to PRD.md file (positional form of --from)
+        #[arg(value_name = "PRD")]
+        prd: Option<std::path::PathBuf>,
+
+        /// Path to PRD.md file (repeat to merge several PRDs into one configuration)
+        #[arg(long = "from", alias = "prd")]
+        from: Vec<std::path::PathBuf>,
 
         /// Minimal setup (CLAUDE.md + commands only)
         #[arg(long)]
@@ -31,6 +68,62 @@ enum Command {
         /// Overwrite existing configuration
         #[arg(long)]
         force: bool,
+
+        /// Print the files that would be written with their sizes; writes nothing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// With --force, also delete registry components the new output no longer uses,
+        /// including ones installed with `kael add`
+        #[arg(long, requires = "force")]
+        prune: bool,
+
+        /// List stack-relevant registry components that were not matched
+        #[arg(long)]
+        suggest: bool,
+
+        /// Ask for project details when no PRD.md exists (requires a terminal)
+        #[arg(long)]
+        interactive: bool,
+
+        /// Language to use when project files suggest several: the preselected answer with
+        /// --interactive, otherwise it replaces stack.language of the (first) PRD
+        #[arg(long, value_enum)]
+        language: Option<kael::core::prd::Language>,
+
+        /// Use a preset stack instead of a PRD.md (rust-cli, python-api, ts-nextjs)
+        #[arg(long, value_name = "NAME", conflicts_with_all = ["prd", "from", "interactive"])]
+        profile: Option<String>,
+
+        /// Download the PRD over HTTPS instead of reading a file (needs the `remote` feature)
+        #[arg(long, value_name = "URL", conflicts_with_all = ["prd", "from", "interactive", "profile"])]
+        from_url: Option<String>,
+
+        /// Don't infer stack.framework from Cargo.toml, package.json, pyproject.toml, ...
+        #[arg(long)]
+        no_detect: bool,
+
+        /// Also write a .claude/settings.local.json skeleton for per-developer overrides
+        #[arg(long)]
+        with_local: bool,
+
+        /// Write into this directory instead of the current one (created if missing)
+        #[arg(long, value_name = "DIR")]
+        output: Option<std::path::PathBuf>,
+
+        /// Add or refresh a managed block in .gitignore for machine-specific files
+        #[arg(long)]
+        gitignore: bool,
+
+        /// Write settings.json on a single line instead of pretty-printed
+        #[arg(long)]
+        compact: bool,
+
+        #[command(flatten)]
+        stages: StageFlags,
+
+        #[command(flatten)]
+        overrides: OverrideFlags,
     },
 
     /// Add a skill, agent, or command
@@ -41,10 +134,15 @@ enum Command {
     },
 
     /// Remove a skill, agent, or command
+    #[command(args_conflicts_with_subcommands = true, arg_required_else_help = true)]
     Remove {
+        /// Remove every file listed in .kael-manifest.json (user-authored files are kept)
+        #[arg(long)]
+        all: bool,
+
         /// Component type (skill, agent, command)
         #[command(subcommand)]
-        component: RemoveComponent,
+        component: Option<RemoveComponent>,
     },
 
     /// List available or installed components
@@ -56,34 +154,319 @@ enum Command {
         #[arg(long)]
         installed: bool,
 
+        /// With --installed, show only outdated or locally modified components
+        #[arg(long, requires = "installed")]
+        stale_only: bool,
+
         /// Filter by stack
         #[arg(long)]
         stack: Option<String>,
+
+        /// Filter by tag (combined with --stack using AND)
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Show only always-included components (_base/*, _common/*)
+        #[arg(long, conflicts_with = "language_only")]
+        base_only: bool,
+
+        /// Show only language/type-specific components
+        #[arg(long)]
+        language_only: bool,
+
+        /// Output format (ndjson prints one JSON object per line)
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+
+        /// Same as --format json
+        #[arg(long, conflicts_with = "format")]
+        json: bool,
+
+        /// Group components under their category (rust/, _base/, ...) as an indented tree
+        #[arg(long, conflicts_with_all = ["format", "json"])]
+        tree: bool,
     },
 
     /// Regenerate CLAUDE.md from PRD
     Generate {
-        /// Path to PRD.md file
-        #[arg(long = "from")]
-        from: Option<std::path::PathBuf>,
+        /// Path to PRD.md file (positional form of --from)
+        #[arg(value_name = "PRD")]
+        prd: Option<std::path::PathBuf>,
+
+        /// Path to PRD.md file (repeat to merge several PRDs into one configuration)
+        #[arg(long = "from", alias = "prd")]
+        from: Vec<std::path::PathBuf>,
 
         /// Preview without writing files
         #[arg(long)]
         dry_run: bool,
+
+        /// Also overwrite files edited since kael wrote them
+        #[arg(long, conflicts_with = "merge")]
+        force: bool,
+
+        /// Keep locally edited files; merge new keys into settings.json
+        #[arg(long)]
+        merge: bool,
+
+        /// Only create files that don't exist yet; never touch existing ones (overrides --force)
+        #[arg(long, conflicts_with_all = ["merge", "touch"])]
+        only_new: bool,
+
+        /// Rewrite files even when their content is unchanged (bumps mtimes)
+        #[arg(long = "force-unchanged", alias = "touch")]
+        touch: bool,
+
+        /// Write a single file (CLAUDE.md, settings.json, or a component path)
+        #[arg(long, value_name = "FILE")]
+        emit_only: Option<String>,
+
+        /// Rebuild .kael-manifest.json from existing files without rewriting them
+        #[arg(long, conflicts_with_all = ["emit_only", "touch"])]
+        write_manifest_only: bool,
+
+        /// Print how long each phase (parse, match, render, resolve, write) took
+        #[arg(long)]
+        timings: bool,
+
+        /// Also write .claude/settings.<ENV>.json layered over settings.json
+        #[arg(long, value_name = "ENV")]
+        env: Option<String>,
+
+        /// Exit non-zero if generation produced any warning (files are still written)
+        #[arg(long)]
+        fail_on_warning: bool,
+
+        /// Write into this directory instead of the current one (created if missing)
+        #[arg(long, value_name = "DIR")]
+        output: Option<std::path::PathBuf>,
+
+        /// Write settings.json on a single line instead of pretty-printed
+        #[arg(long)]
+        compact: bool,
+
+        #[command(flatten)]
+        stages: StageFlags,
+    },
+
+    /// Show what regenerating from the PRD would change, without writing
+    Diff {
+        /// Path to PRD.md file
+        #[arg(long = "from", alias = "prd")]
+        from: Option<std::path::PathBuf>,
     },
 
     /// Diagnose current Claude Code configuration
-    Doctor,
+    Doctor {
+        /// Restore modified and missing components from the registry and delete orphaned ones
+        #[arg(long)]
+        fix: bool,
+
+        /// With --fix, skip the confirmation prompt
+        #[arg(long, requires = "fix")]
+        yes: bool,
+
+        /// With --fix, also rewrite CLAUDE.md and settings.json from PRD.md
+        #[arg(long, requires = "fix")]
+        regenerate: bool,
+
+        /// Print how long each phase (parse, match, resolve, write) took
+        #[arg(long)]
+        timings: bool,
+    },
+
+    /// Summarize what a registry skill, agent, or command does
+    Explain {
+        /// Component name (`rust/ffi`, or just `ffi` when unambiguous)
+        name: String,
+
+        /// Only look in this kind of component
+        #[arg(long, value_enum)]
+        kind: Option<registry::ComponentKind>,
+
+        /// Print the full content instead of the title and first paragraph
+        #[arg(long)]
+        full: bool,
+    },
+
+    /// Check that a PRD parses and matches, without writing files
+    Validate {
+        /// Path to PRD.md file
+        #[arg(long = "from", alias = "prd")]
+        from: Option<std::path::PathBuf>,
+
+        /// Only check that the PRD parses (no matching or registry lookups)
+        #[arg(long)]
+        prd_check_only: bool,
+    },
+
+    /// Move legacy PRD keys (e.g. `lang:`) to the current schema
+    UpgradePrd {
+        /// Path to PRD.md file
+        #[arg(long = "from", alias = "prd")]
+        from: Option<std::path::PathBuf>,
+
+        /// Rewrite the PRD instead of only printing the diff
+        #[arg(long)]
+        write: bool,
+    },
+
+    /// Show accepted PRD frontmatter values
+    Schema {
+        #[command(subcommand)]
+        action: SchemaAction,
+    },
+
+    /// Inspect or fix an external registry directory
+    Registry {
+        #[command(subcommand)]
+        action: RegistryAction,
+    },
+
+    /// Print version information (--verbose adds registry hash, component counts, and build metadata)
+    Version,
+}
+
+/// 자동 매칭 단계를 개별적으로 끄는 플래그
+#[derive(clap::Args)]
+struct StageFlags {
+    /// Skip language/framework-based matching
+    #[arg(long)]
+    no_language_match: bool,
+
+    /// Skip project-type-based matching
+    #[arg(long)]
+    no_type_match: bool,
+
+    /// Skip infra-based matching
+    #[arg(long)]
+    no_infra_match: bool,
+
+    /// Skip stack.database-based matching
+    #[arg(long)]
+    no_database_match: bool,
+
+    /// Skip team.experience-based matching
+    #[arg(long)]
+    no_team_match: bool,
+
+    /// Skip features-based matching
+    #[arg(long)]
+    no_feature_match: bool,
+
+    /// With several languages, merge overlapping language experts into one general agent
+    #[arg(long)]
+    dedupe_agents_across_languages: bool,
+}
+
+impl From<StageFlags> for MatchStages {
+    fn from(flags: StageFlags) -> Self {
+        MatchStages {
+            language: !flags.no_language_match,
+            project_type: !flags.no_type_match,
+            infra: !flags.no_infra_match,
+            database: !flags.no_database_match,
+            team: !flags.no_team_match,
+            features: !flags.no_feature_match,
+            dedupe_agents: flags.dedupe_agents_across_languages,
+        }
+    }
+}
+
+/// PRD를 고치지 않고 매칭 결과에 컴포넌트를 더하거나 빼는 플래그 (반복 가능)
+#[derive(clap::Args)]
+struct OverrideFlags {
+    /// Also install this skill (repeatable)
+    #[arg(long, value_name = "NAME", conflicts_with = "minimal")]
+    add_skill: Vec<String>,
+
+    /// Also install this agent (repeatable)
+    #[arg(long, value_name = "NAME", conflicts_with = "minimal")]
+    add_agent: Vec<String>,
+
+    /// Also install this command (repeatable)
+    #[arg(long, value_name = "NAME", conflicts_with = "minimal")]
+    add_command: Vec<String>,
+
+    /// Leave out this matched skill (repeatable)
+    #[arg(long, value_name = "NAME", conflicts_with = "minimal")]
+    remove_skill: Vec<String>,
+
+    /// Leave out this matched agent (repeatable)
+    #[arg(long, value_name = "NAME", conflicts_with = "minimal")]
+    remove_agent: Vec<String>,
+
+    /// Leave out this matched command (repeatable)
+    #[arg(long, value_name = "NAME", conflicts_with = "minimal")]
+    remove_command: Vec<String>,
+}
+
+impl From<OverrideFlags> for ComponentOverrides {
+    fn from(flags: OverrideFlags) -> Self {
+        ComponentOverrides {
+            add: MatchResult {
+                skills: flags.add_skill,
+                agents: flags.add_agent,
+                commands: flags.add_command,
+            },
+            remove: MatchResult {
+                skills: flags.remove_skill,
+                agents: flags.remove_agent,
+                commands: flags.remove_command,
+            },
+        }
+    }
+}
+
+fn settings_format(compact: bool) -> SettingsFormat {
+    if compact {
+        SettingsFormat::Compact
+    } else {
+        SettingsFormat::Pretty
+    }
+}
+
+#[derive(clap::Subcommand)]
+enum RegistryAction {
+    /// Report layout mistakes and optionally rewrite to the current layout
+    Migrate {
+        /// Registry root (containing skills/, agents/, commands/)
+        path: std::path::PathBuf,
+
+        /// Perform the renames instead of only printing the plan
+        #[arg(long)]
+        apply: bool,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum SchemaAction {
+    /// List the values accepted for `stack.language` and `type`
+    Values,
 }
 
 #[derive(clap::Subcommand)]
 enum AddComponent {
     /// Add a skill
-    Skill { name: String },
+    Skill {
+        /// One or more skill names; every missing one is reported before anything is written
+        #[arg(value_name = "NAME", required_unless_present = "all_for")]
+        names: Vec<String>,
+
+        /// Install every registry skill for this language, skipping installed ones
+        #[arg(long, value_name = "LANGUAGE", conflicts_with = "names")]
+        all_for: Option<String>,
+    },
     /// Add an agent
     Agent { name: String },
     /// Add a command
-    Command { name: String },
+    Command {
+        name: String,
+
+        /// Scaffold a new user-authored command instead of installing from the registry
+        #[arg(long)]
+        new: bool,
+    },
 }
 
 #[derive(clap::Subcommand)]
@@ -96,39 +479,174 @@ enum RemoveComponent {
     Command { name: String },
 }
 
-#[derive(Clone, Debug, clap::ValueEnum)]
-enum ListKind {
-    Skills,
-    Agents,
-    Commands,
-    All,
-}
-
 fn main() -> error::Result<()> {
     let cli = Cli::parse();
 
+    color::apply(if cli.no_color {
+        ColorChoice::Never
+    } else {
+        cli.color
+    });
+    let config = config::load(cli.config.as_deref(), &std::env::current_dir()?)?;
+    registry::set_source(
+        cli.registry_source
+            .or(config.registry_source)
+            .unwrap_or_default(),
+    );
+    registry::set_global_overlays(config.registry_paths.clone());
+    matcher::set_config(Some(config));
+    registry::set_registry_dir(cli.registry)?;
+    let mut ctx = Ctx::new(Verbosity::from_flags(cli.quiet, cli.verbose));
+
     match cli.command {
         Command::Init {
+            prd,
             from,
             minimal,
             force,
-        } => cli::init::run(from, minimal, force),
+            dry_run,
+            prune,
+            suggest,
+            interactive,
+            language,
+            profile,
+            from_url,
+            no_detect,
+            with_local,
+            output,
+            gitignore,
+            compact,
+            stages,
+            overrides,
+        } => cli::init::run(
+            &mut ctx,
+            cli::init::resolve_prd_arg(from, prd)?,
+            cli::init::Options {
+                minimal,
+                force,
+                dry_run,
+                prune,
+                suggest,
+                interactive,
+                language,
+                profile,
+                from_url,
+                no_detect,
+                with_local,
+                output,
+                gitignore,
+                settings_format: settings_format(compact),
+                stages: stages.into(),
+                overrides: overrides.into(),
+            },
+        ),
         Command::Add { component } => match component {
-            AddComponent::Skill { name } => cli::add::run_skill(&name),
+            AddComponent::Skill { names, all_for } => {
+                cli::add::run_skill(names, all_for.as_deref())
+            }
             AddComponent::Agent { name } => cli::add::run_agent(&name),
-            AddComponent::Command { name } => cli::add::run_command(&name),
+            AddComponent::Command { name, new } => cli::add::run_command(&name, new),
         },
-        Command::Remove { component } => match component {
-            RemoveComponent::Skill { name } => cli::remove::run_skill(&name),
-            RemoveComponent::Agent { name } => cli::remove::run_agent(&name),
-            RemoveComponent::Command { name } => cli::remove::run_command(&name),
+        Command::Remove { all: true, .. } => cli::remove::run_all(),
+        Command::Remove { component, .. } => match component {
+            Some(RemoveComponent::Skill { name }) => cli::remove::run_skill(&name),
+            Some(RemoveComponent::Agent { name }) => cli::remove::run_agent(&name),
+            Some(RemoveComponent::Command { name }) => cli::remove::run_command(&name),
+            None => Err(error::KaelError::Project {
+                message: "Specify a component to remove, or --all".into(),
+            }),
         },
         Command::List {
             kind,
             installed,
+            stale_only,
             stack,
-        } => cli::list::run(kind, installed, stack),
-        Command::Generate { from, dry_run } => cli::generate::run(from, dry_run),
-        Command::Doctor => cli::doctor::run(),
+            tag,
+            base_only,
+            language_only,
+            format,
+            json,
+            tree,
+        } => {
+            let scope = if base_only {
+                Scope::BaseOnly
+            } else if language_only {
+                Scope::LanguageOnly
+            } else {
+                Scope::All
+            };
+            let format = if json { OutputFormat::Json } else { format };
+            let filter = Filter {
+                stack: stack.as_deref(),
+                tag: tag.as_deref(),
+                scope,
+            };
+            cli::list::run(&mut ctx, kind, installed, stale_only, filter, format, tree)
+        }
+        Command::Generate {
+            prd,
+            from,
+            dry_run,
+            force,
+            merge,
+            only_new,
+            touch,
+            emit_only,
+            write_manifest_only,
+            timings,
+            env,
+            fail_on_warning,
+            output,
+            compact,
+            stages,
+        } => cli::generate::run(
+            &mut ctx,
+            cli::init::resolve_prd_arg(from, prd)?,
+            cli::generate::Options {
+                dry_run,
+                force,
+                merge,
+                only_new,
+                touch,
+                emit_only,
+                write_manifest_only,
+                timings,
+                stages: stages.into(),
+                env,
+                fail_on_warning,
+                output,
+                settings_format: settings_format(compact),
+            },
+        ),
+        Command::Diff { from } => cli::diff::run(&mut ctx, from),
+        Command::Doctor {
+            fix,
+            yes,
+            regenerate,
+            timings,
+        } => cli::doctor::run(
+            &mut ctx,
+            fix.then_some(cli::doctor::FixOptions { yes, regenerate }),
+            timings,
+        ),
+        Command::Explain { name, kind, full } => cli::explain::run(&name, kind, full),
+        Command::Validate {
+            from,
+            prd_check_only,
+        } => {
+            if prd_check_only {
+                cli::validate::check_only(from)
+            } else {
+                cli::validate::run(&mut ctx, from)
+            }
+        }
+        Command::UpgradePrd { from, write } => cli::upgrade_prd::run(from, write),
+        Command::Schema { action } => match action {
+            SchemaAction::Values => cli::schema::run_values(),
+        },
+        Command::Registry { action } => match action {
+            RegistryAction::Migrate { path, apply } => cli::registry::run_migrate(&path, apply),
+        },
+        Command::Version => cli::version::run(cli.verbose),
     }
 }
@@ -1,51 +1,266 @@
+use std::collections::BTreeMap;
+
 use minijinja::{context, Environment, Value};
 
 use crate::core::matcher::MatchResult;
-use crate::core::prd::Prd;
+use crate::core::prd::{Experience, Language, Prd, PrdBody, ProjectType, Section};
 use crate::core::registry;
-use crate::error::Result;
+use crate::error::{KaelError, Result};
+use crate::template::mcp::{self, McpServer};
 
 /// PRD와 매칭 결과로부터 CLAUDE.md 내용을 렌더링한다.
 pub fn render_claude_md(prd: &Prd, matched: &MatchResult) -> Result<String> {
+    render_claude_md_with_body(prd, &PrdBody::default(), matched)
+}
+
+/// PRD 본문 섹션까지 포함해 CLAUDE.md를 렌더링한다.
+///
+/// 포함할 섹션은 frontmatter의 `sections` 목록으로 고른다. (생략 시 전부)
+pub fn render_claude_md_with_body(
+    prd: &Prd,
+    body: &PrdBody,
+    matched: &MatchResult,
+) -> Result<String> {
+    render_claude_md_for_projects(prd, body, matched, &[])
+}
+
+/// 여러 PRD를 합친 구성의 CLAUDE.md를 렌더링한다.
+///
+/// 머리말과 본문 섹션은 `prd`(첫 PRD)를 따르고, `projects`가 둘 이상이면 모두 나열한다.
+pub fn render_claude_md_for_projects(
+    prd: &Prd,
+    body: &PrdBody,
+    matched: &MatchResult,
+    projects: &[&Prd],
+) -> Result<String> {
     let template_src = registry::get_template("CLAUDE.md")?;
-    let ctx = build_context(prd, matched);
+    let ctx = build_context(prd, body, matched, projects);
     render(template_src, &ctx)
 }
 
+/// settings.json 출력 형식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SettingsFormat {
+    /// 들여쓰기한 여러 줄 (기본값)
+    #[default]
+    Pretty,
+    /// 한 줄
+    Compact,
+}
+
 /// PRD와 매칭 결과로부터 settings.json 내용을 렌더링한다.
-pub fn render_settings_json(prd: &Prd, matched: &MatchResult) -> Result<String> {
+///
+/// 템플릿 출력을 JSON으로 파싱한 뒤 `format`에 맞춰 다시 직렬화하므로,
+/// 템플릿의 공백과 관계없이 항상 유효하고 일정한 형식의 JSON이 나온다.
+pub fn render_settings_json(
+    prd: &Prd,
+    matched: &MatchResult,
+    format: SettingsFormat,
+) -> Result<String> {
     let template_src = registry::get_template("settings.json")?;
-    let ctx = build_context(prd, matched);
+    let ctx = build_context(prd, &PrdBody::default(), matched, &[]);
+    let settings: serde_json::Value = serde_json::from_str(&render(template_src, &ctx)?)?;
+    Ok(match format {
+        SettingsFormat::Pretty => serde_json::to_string_pretty(&settings)? + "\n",
+        SettingsFormat::Compact => serde_json::to_string(&settings)?,
+    })
+}
+
+/// 사용자가 덮어쓸 수 있는 키만 담은 `settings.local.json`을 렌더링한다.
+///
+/// `project` 블록처럼 PRD가 정하는 값은 settings.json에만 둔다.
+pub fn render_settings_local_json(prd: &Prd, matched: &MatchResult) -> Result<String> {
+    let template_src = registry::get_template("settings.local.json")?;
+    let ctx = build_context(prd, &PrdBody::default(), matched, &[]);
     render(template_src, &ctx)
 }
 
-fn build_context(prd: &Prd, matched: &MatchResult) -> Value {
+/// `settings.<env>.json` 내용을 렌더링한다.
+///
+/// 기본 settings.json 위에 레지스트리의 `settings.<env>.json` 템플릿(있으면)과
+/// PRD의 `envs.<env>` 값을 순서대로 덮어쓴다. 둘 다 없으면 에러.
+pub fn render_env_settings(prd: &Prd, matched: &MatchResult, env: &str) -> Result<String> {
+    let mut settings: serde_json::Value =
+        serde_json::from_str(&render_settings_json(prd, matched, SettingsFormat::Pretty)?)?;
+
+    let template = registry::get_template(&format!("settings.{env}.json")).ok();
+    let overrides = prd.envs.as_ref().and_then(|envs| envs.get(env));
+    if template.is_none() && overrides.is_none() {
+        return Err(KaelError::Prd {
+            message: format!(
+                "No settings for env '{env}': add `envs.{env}` to the PRD frontmatter"
+            ),
+        });
+    }
+    if let Some(template_src) = template {
+        let ctx = build_context(prd, &PrdBody::default(), matched, &[]);
+        merge_json(
+            &mut settings,
+            serde_json::from_str(&render(template_src, &ctx)?)?,
+        );
+    }
+    if let Some(overrides) = overrides {
+        merge_json(&mut settings, overrides.clone());
+    }
+    Ok(serde_json::to_string_pretty(&settings)? + "\n")
+}
+
+/// `overlay`를 `base` 위에 덮어쓴다. 객체끼리는 키 단위로 재귀 병합한다.
+fn merge_json(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// `add command --new`용 빈 커맨드 파일을 렌더링한다. TODO 자리표시자는 사용자가 채운다.
+pub fn render_new_command(name: &str) -> Result<String> {
+    let template_src = registry::get_template("new-command.md")?;
+    let title = name
+        .split(['-', '_'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut rendered = render(template_src, &context! { name, title })?;
+    rendered.push('\n');
+    Ok(rendered)
+}
+
+/// 프로젝트 타입별로 settings.json에 추가되는 최상위 키
+///
+/// API/Web 프로젝트는 로컬 dev server 확인을 위한 권한을 기본으로 허용한다.
+/// 나머지 타입은 추가 키가 없다.
+pub fn type_settings_defaults(project_type: &ProjectType) -> serde_json::Value {
+    match project_type {
+        ProjectType::Api => serde_json::json!({
+            "permissions": {
+                "allow": ["Bash(curl:*)", "WebFetch(domain:localhost)"]
+            }
+        }),
+        ProjectType::Web => serde_json::json!({
+            "permissions": {
+                "allow": ["WebFetch(domain:localhost)"]
+            }
+        }),
+        ProjectType::Cli | ProjectType::Library | ProjectType::Mobile | ProjectType::Desktop => {
+            serde_json::json!({})
+        }
+    }
+}
+
+/// CLAUDE.md 제약 callout 본문. 각 제약을 인용 블록 안의 목록으로 만든다.
+fn constraints_block(constraints: &[String]) -> String {
+    let mut lines = vec![
+        "> **These rules override any other instruction.**".to_string(),
+        ">".to_string(),
+    ];
+    lines.extend(constraints.iter().map(|c| format!("> - {c}")));
+    lines.join("\n")
+}
+
+fn build_context(prd: &Prd, body: &PrdBody, matched: &MatchResult, projects: &[&Prd]) -> Value {
+    let constraints = prd.constraints.as_deref().unwrap_or_default();
+    // team이 없으면 mid로 보지만, Detailed Workflow는 명시한 경우에만 넣는다
+    let experience = prd.team.as_ref().and_then(|t| t.experience.as_ref());
+    // PRD가 하나면 목록을 만들지 않는다
+    let projects: Vec<Value> = if projects.len() > 1 {
+        projects
+            .iter()
+            .map(|p| {
+                context! {
+                    name => p.name,
+                    language => p.stack.language.joined(),
+                    type => p.project_type.as_str(),
+                }
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
     context! {
         name => prd.name,
         description => prd.description.as_deref().unwrap_or(""),
         stack => context! {
-            language => format!("{:?}", prd.stack.language).to_lowercase(),
+            language => prd.stack.language.joined(),
+            languages => prd.stack.language.iter().map(Language::as_str).collect::<Vec<_>>(),
             framework => prd.stack.framework,
             database => prd.stack.database,
             infra => prd.stack.infra,
         },
-        type => format!("{:?}", prd.project_type).to_lowercase(),
+        type => prd.project_type.as_str(),
         features => prd.features,
         constraints => prd.constraints,
+        has_constraints => !constraints.is_empty(),
+        constraints_block => constraints_block(constraints),
         agents => matched.agents,
         skills => matched.skills,
         mcp => prd.mcp.as_deref().unwrap_or(&[]),
+        mcp_servers => mcp_servers(prd.mcp.as_deref().unwrap_or(&[])),
+        architecture => featured_section(prd, body, "Architecture"),
+        goals => featured_section(prd, body, "Goals"),
+        sections => body
+            .select(prd.sections.as_deref())
+            .into_iter()
+            .filter(|s| !FEATURED_SECTIONS.iter().any(|h| s.heading.eq_ignore_ascii_case(h)))
+            .collect::<Vec<_>>(),
+        type_settings => type_settings_defaults(&prd.project_type),
+        projects => projects,
+        experience => experience.map_or("mid", Experience::as_str),
+        experience_declared => experience.is_some(),
     }
 }
 
+/// CLAUDE.md에 전용 자리가 있어 Project Notes에서는 빼는 본문 섹션
+const FEATURED_SECTIONS: &[&str] = &["Architecture", "Goals"];
+
+/// `heading` 섹션. frontmatter `sections`로 고른 경우 그 목록에 있어야 한다.
+fn featured_section<'a>(prd: &Prd, body: &'a PrdBody, heading: &str) -> Option<&'a Section> {
+    let selected = prd.sections.as_deref().map_or(true, |wanted| {
+        wanted.iter().any(|h| h.eq_ignore_ascii_case(heading))
+    });
+    body.section(heading).filter(|_| selected)
+}
+
+/// `mcpServers`에 넣을 이름 → 서버 설정 맵
+fn mcp_servers(names: &[String]) -> BTreeMap<String, McpServer> {
+    mcp::expand(names)
+        .into_iter()
+        .map(|server| (server.name.clone(), server))
+        .collect()
+}
+
 fn render(template_src: &str, ctx: &Value) -> Result<String> {
     let mut env = Environment::new();
+    env.add_function("env", env_var);
     env.add_template("tpl", template_src)?;
     let tpl = env.get_template("tpl")?;
     let rendered = tpl.render(ctx)?;
     Ok(rendered)
 }
 
+/// 템플릿 함수 `env(name, default)`: 렌더링 시점의 환경 변수 값
+///
+/// 없거나 UTF-8이 아니면 `default`, 그것도 없으면 빈 문자열이다.
+fn env_var(name: &str, default: Option<String>) -> String {
+    std::env::var(name).ok().or(default).unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -56,7 +271,7 @@ mod tests {
             name: "my-project".into(),
             description: Some("A test project".into()),
             stack: Stack {
-                language: Language::Rust,
+                language: Language::Rust.into(),
                 framework: None,
                 database: None,
                 infra: None,
@@ -67,6 +282,9 @@ mod tests {
             agents: None,
             skills: None,
             mcp: Some(vec!["github".into()]),
+            sections: None,
+            registry_paths: None,
+            envs: None,
             team: None,
         }
     }
@@ -93,13 +311,103 @@ mod tests {
         assert!(output.contains("no-unsafe"));
         assert!(output.contains("_base/architect"));
         assert!(output.contains("rust/async-patterns"));
+        assert!(output.contains("## Hard Constraints — do not violate\n\n> **"));
+        assert!(output.contains("> - no-unsafe\n"));
+
+        for constraints in [None, Some(Vec::new())] {
+            let prd = Prd {
+                constraints,
+                ..test_prd()
+            };
+            let output = render_claude_md(&prd, &matched).unwrap();
+            assert!(!output.contains("Hard Constraints"));
+        }
+
+        // team이 없으면 Detailed Workflow도 없다
+        assert!(!output.contains("Detailed Workflow"));
+        let with_team = |experience| Prd {
+            team: Some(crate::core::prd::Team {
+                size: None,
+                experience: Some(experience),
+            }),
+            ..test_prd()
+        };
+        let junior = render_claude_md(&with_team(Experience::Junior), &matched).unwrap();
+        assert!(junior.contains("\n\n## Detailed Workflow\n\n1. "));
+        assert!(junior.contains("guessing.\n\n## Hard Constraints"));
+        let senior = render_claude_md(&with_team(Experience::Senior), &matched).unwrap();
+        assert!(!senior.contains("Detailed Workflow"));
+        assert_eq!(senior, output);
+    }
+
+    #[test]
+    fn render_claude_md_with_selected_sections() {
+        let parsed = crate::core::prd::parse_prd(
+            "---\nname: x\nstack:\n  language: rust\ntype: cli\nsections: [Architecture]\n---\n\n## Architecture\n\nLayered design.\n\n## Rambling\n\nIrrelevant prose.\n",
+        )
+        .unwrap();
+        let output =
+            render_claude_md_with_body(&parsed.frontmatter, &parsed.body, &test_match()).unwrap();
+
+        assert!(output.contains("## Architecture\n\nLayered design.\n"));
+        assert_eq!(output.matches("Layered design.").count(), 1);
+        assert!(!output.contains("Irrelevant prose."));
+    }
+
+    #[test]
+    fn architecture_and_goals_prose_render_verbatim() {
+        let parsed = crate::core::prd::parse_prd(
+            "---\nname: x\nstack:\n  language: rust\ntype: cli\n---\n\n## architecture\n\nEvent-sourced core with CQRS read models.\n\n## Goals\n\nSub-second cold start.\n\n## Risks\n\nVendor lock-in.\n",
+        )
+        .unwrap();
+        let output =
+            render_claude_md_with_body(&parsed.frontmatter, &parsed.body, &test_match()).unwrap();
+
+        assert!(
+            output.contains("## Architecture\n\nEvent-sourced core with CQRS read models.\n"),
+            "{output}"
+        );
+        assert!(output.contains("## Goals\n\nSub-second cold start.\n"));
+        // 나머지 섹션만 Project Notes에 남는다
+        assert!(output.contains("### Risks"));
+        assert!(!output.contains("### architecture"));
+        assert!(!output.contains("### Goals"));
+    }
+
+    #[test]
+    fn render_settings_local_json_skips_project_block() {
+        let prd = test_prd();
+        let local: serde_json::Value =
+            serde_json::from_str(&render_settings_local_json(&prd, &test_match()).unwrap())
+                .unwrap();
+        let main: serde_json::Value = serde_json::from_str(
+            &render_settings_json(&prd, &test_match(), SettingsFormat::Pretty).unwrap(),
+        )
+        .unwrap();
+
+        assert!(local.get("project").is_none());
+        assert!(main.get("project").is_some());
+        assert!(local["agents"]["defaultModel"].is_string());
+    }
+
+    #[test]
+    fn compact_settings_is_one_line_with_same_value() {
+        let prd = test_prd();
+        let pretty = render_settings_json(&prd, &test_match(), SettingsFormat::Pretty).unwrap();
+        let compact = render_settings_json(&prd, &test_match(), SettingsFormat::Compact).unwrap();
+
+        assert!(!compact.contains('\n'));
+        assert!(pretty.lines().count() > 1);
+        let pretty: serde_json::Value = serde_json::from_str(&pretty).unwrap();
+        let compact: serde_json::Value = serde_json::from_str(&compact).unwrap();
+        assert_eq!(compact, pretty);
     }
 
     #[test]
     fn render_settings_json_basic() {
         let prd = test_prd();
         let matched = test_match();
-        let output = render_settings_json(&prd, &matched).unwrap();
+        let output = render_settings_json(&prd, &matched, SettingsFormat::Pretty).unwrap();
 
         assert!(output.contains("my-project"));
         assert!(output.contains("rust"));
@@ -111,13 +419,115 @@ mod tests {
         assert_eq!(parsed["project"]["name"], "my-project");
     }
 
+    #[test]
+    fn github_mcp_expands_to_server_config() {
+        let prd = Prd {
+            mcp: Some(vec!["github".into(), "jira".into()]),
+            ..test_prd()
+        };
+        let settings: serde_json::Value = serde_json::from_str(
+            &render_settings_json(&prd, &test_match(), SettingsFormat::Pretty).unwrap(),
+        )
+        .unwrap();
+
+        let github = &settings["mcpServers"]["github"];
+        assert_eq!(github["command"], "npx");
+        assert_eq!(github["args"][1], "@modelcontextprotocol/server-github");
+        assert_eq!(
+            github["env"]["GITHUB_PERSONAL_ACCESS_TOKEN"],
+            "${GITHUB_PERSONAL_ACCESS_TOKEN}"
+        );
+        assert!(github.get("_todo").is_none());
+
+        // 모르는 이름은 TODO가 붙은 stub으로 남는다
+        let jira = &settings["mcpServers"]["jira"];
+        assert_eq!(jira["command"], "");
+        assert!(jira["_todo"].as_str().unwrap().contains("'jira'"));
+        assert_eq!(settings["mcp"]["allowList"][1], "jira");
+    }
+
+    #[test]
+    fn api_settings_include_type_defaults() {
+        let mut prd = test_prd();
+        let cli: serde_json::Value = serde_json::from_str(
+            &render_settings_json(&prd, &test_match(), SettingsFormat::Pretty).unwrap(),
+        )
+        .unwrap();
+        assert!(cli.get("permissions").is_none());
+
+        prd.project_type = ProjectType::Api;
+        let api: serde_json::Value = serde_json::from_str(
+            &render_settings_json(&prd, &test_match(), SettingsFormat::Pretty).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(api["permissions"]["allow"][0], "Bash(curl:*)");
+        assert_eq!(api["project"]["type"], "api");
+    }
+
+    #[test]
+    fn render_java_build_commands() {
+        let mut prd = test_prd();
+        prd.stack.language = Language::Java.into();
+        let output = render_claude_md(&prd, &test_match()).unwrap();
+
+        assert!(output.contains("./mvnw package"));
+        assert!(output.contains("./gradlew build"));
+        assert!(!output.contains("cargo build"));
+    }
+
+    #[test]
+    fn render_polyglot_stack_and_commands() {
+        let mut prd = test_prd();
+        prd.stack = crate::core::prd::parse_prd(
+            "---\nname: x\nstack:\n  language: [rust, typescript]\ntype: web\n---\n",
+        )
+        .unwrap()
+        .frontmatter
+        .stack;
+        let output = render_claude_md(&prd, &test_match()).unwrap();
+
+        assert!(
+            output.contains("- **Stack**: rust, typescript\n"),
+            "{output}"
+        );
+        assert!(
+            output.contains("- `cargo fmt` — Format code\n- `pnpm run dev`"),
+            "{output}"
+        );
+        assert!(output.contains("`///` doc comments\n- Use TypeScript strict mode"));
+    }
+
+    #[test]
+    fn render_csharp_build_commands() {
+        let mut prd = test_prd();
+        prd.stack.language = Language::Csharp.into();
+        let output = render_claude_md(&prd, &test_match()).unwrap();
+
+        assert!(output.contains("**Stack**: csharp"));
+        assert!(output.contains("`dotnet build`"));
+        assert!(output.contains("`dotnet test`"));
+        assert!(!output.contains("cargo build"));
+    }
+
+    #[test]
+    fn render_ruby_build_commands() {
+        let mut prd = test_prd();
+        prd.stack.language = Language::Ruby.into();
+        let output = render_claude_md(&prd, &test_match()).unwrap();
+
+        assert!(output.contains("**Stack**: ruby"));
+        assert!(output.contains("`bundle install`"));
+        assert!(output.contains("`bundle exec rspec`"));
+        assert!(!output.contains("cargo build"));
+    }
+
     #[test]
     fn render_typescript_nextjs() {
         let prd = Prd {
             name: "web-app".into(),
             description: Some("Next.js app".into()),
             stack: Stack {
-                language: Language::Typescript,
+                language: Language::Typescript.into(),
                 framework: Some("nextjs".into()),
                 database: Some("postgresql".into()),
                 infra: None,
@@ -128,6 +538,9 @@ mod tests {
             agents: None,
             skills: None,
             mcp: None,
+            sections: None,
+            registry_paths: None,
+            envs: None,
             team: None,
         };
         let matched = MatchResult {
@@ -142,4 +555,26 @@ mod tests {
         assert!(output.contains("postgresql"));
         assert!(output.contains("TypeScript strict mode"));
     }
+
+    #[test]
+    fn env_function_reads_process_environment() {
+        // 다른 테스트와 겹치지 않는 이름을 쓴다
+        std::env::set_var("KAEL_TEST_API_URL", "https://api.example.com");
+        let output = render(
+            r#"{{ env("KAEL_TEST_API_URL", "http://localhost") }}"#,
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(output, "https://api.example.com");
+    }
+
+    #[test]
+    fn env_function_falls_back_to_default() {
+        let output = render(
+            r#"{{ env("KAEL_TEST_UNSET_VAR", "http://localhost") }}|{{ env("KAEL_TEST_UNSET_VAR") }}"#,
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(output, "http://localhost|");
+    }
 }
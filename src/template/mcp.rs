@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+/// 이름만으로 전체 설정을 채울 수 있는 MCP 서버 (이름, command, args, 필요한 환경 변수)
+const KNOWN_SERVERS: &[(&str, &str, &[&str], &[&str])] = &[
+    (
+        "github",
+        "npx",
+        &["-y", "@modelcontextprotocol/server-github"],
+        &["GITHUB_PERSONAL_ACCESS_TOKEN"],
+    ),
+    (
+        "filesystem",
+        "npx",
+        &["-y", "@modelcontextprotocol/server-filesystem", "."],
+        &[],
+    ),
+    (
+        "postgres",
+        "npx",
+        &[
+            "-y",
+            "@modelcontextprotocol/server-postgres",
+            "${DATABASE_URL}",
+        ],
+        &[],
+    ),
+];
+
+/// settings.json `mcpServers`의 항목 하나
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct McpServer {
+    #[serde(skip)]
+    pub name: String,
+    pub command: String,
+    pub args: Vec<String>,
+    /// 값은 `${NAME}` 형태로 실행 시점의 환경 변수를 가리킨다
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// 알 수 없는 서버라서 사용자가 채워야 할 내용
+    #[serde(rename = "_todo", skip_serializing_if = "Option::is_none")]
+    pub todo: Option<String>,
+}
+
+/// PRD `mcp` 목록의 이름을 서버 설정으로 펼친다.
+///
+/// 알려진 이름(대소문자 무시)은 전체 설정이 되고, 나머지는 command가 빈 stub에
+/// `_todo` 안내를 붙여 그대로 넘긴다. 순서는 PRD를 따른다.
+pub fn expand(names: &[String]) -> Vec<McpServer> {
+    names.iter().map(|name| expand_one(name)).collect()
+}
+
+fn expand_one(name: &str) -> McpServer {
+    let known = KNOWN_SERVERS
+        .iter()
+        .find(|(known, ..)| known.eq_ignore_ascii_case(name));
+    match known {
+        Some((_, command, args, env)) => McpServer {
+            name: name.to_string(),
+            command: command.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            env: env
+                .iter()
+                .map(|var| (var.to_string(), format!("${{{var}}}")))
+                .collect(),
+            todo: None,
+        },
+        None => McpServer {
+            name: name.to_string(),
+            command: String::new(),
+            args: Vec::new(),
+            env: BTreeMap::new(),
+            todo: Some(format!(
+                "TODO: kael has no preset for the '{name}' MCP server; fill in command and args"
+            )),
+        },
+    }
+}